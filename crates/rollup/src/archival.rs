@@ -0,0 +1,123 @@
+//! Batched archival queries, served next to the module REST APIs.
+//!
+//! Each batched route answers many archival lookups in one response by replaying the
+//! corresponding single-slot route of the wrapped router, so the results are exactly what the
+//! per-slot route would return.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Path, State};
+use axum::http::{Request, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sov_bank::TokenId;
+use tower::ServiceExt;
+
+/// Largest response body read from a single-slot lookup.
+const MAX_LOOKUP_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest number of slots a single batched request may ask for.
+pub const MAX_BATCH_SLOTS: usize = 1000;
+
+/// Body of `POST /modules/bank/tokens/{token_id}/total-supply/batch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TotalSupplyBatchRequest {
+    /// Slots to read the total supply at.
+    pub slot_numbers: Vec<u64>,
+}
+
+/// Total supply of a token at one slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotTotalSupply {
+    /// The slot the supply was read at.
+    pub slot_number: u64,
+    /// The response of `GET /modules/bank/tokens/{token_id}/total-supply?slot_number=..`, or
+    /// `None` if the token didn't exist at that slot.
+    pub supply: Option<serde_json::Value>,
+}
+
+/// Response of `POST /modules/bank/tokens/{token_id}/total-supply/batch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TotalSupplyBatchResponse {
+    /// One entry per requested slot, in request order.
+    pub supplies: Vec<SlotTotalSupply>,
+}
+
+/// Returns a router serving the batched total-supply route on top of `modules`, the router that
+/// serves `GET /modules/bank/tokens/{token_id}/total-supply`.
+pub fn total_supply_batch_router(modules: Router) -> Router {
+    Router::new()
+        .route(
+            "/modules/bank/tokens/:token_id/total-supply/batch",
+            post(total_supply_batch),
+        )
+        .with_state(modules)
+}
+
+async fn total_supply_batch(
+    State(modules): State<Router>,
+    Path(token_id): Path<String>,
+    Json(request): Json<TotalSupplyBatchRequest>,
+) -> Result<Json<TotalSupplyBatchResponse>, (StatusCode, String)> {
+    // Replayed as its canonical encoding, so that the id can't alter the single-slot route
+    let token_id = token_id.parse::<TokenId>().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid token id {token_id:?}: {e}"),
+        )
+    })?;
+    if request.slot_numbers.len() > MAX_BATCH_SLOTS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Requested {} slots, at most {} are allowed per batch",
+                request.slot_numbers.len(),
+                MAX_BATCH_SLOTS
+            ),
+        ));
+    }
+
+    let mut supplies = Vec::with_capacity(request.slot_numbers.len());
+    for slot_number in request.slot_numbers {
+        let uri = format!(
+            "/modules/bank/tokens/{}/total-supply?slot_number={}",
+            token_id, slot_number
+        );
+        let supply = get_json(&modules, &uri).await?;
+        supplies.push(SlotTotalSupply {
+            slot_number,
+            supply,
+        });
+    }
+
+    Ok(Json(TotalSupplyBatchResponse { supplies }))
+}
+
+/// Serves `GET uri` with `router`, mapping a 404 to `None` and any other failure to its status.
+async fn get_json(
+    router: &Router,
+    uri: &str,
+) -> Result<Option<serde_json::Value>, (StatusCode, String)> {
+    let request = Request::get(uri)
+        .body(Body::empty())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let response = match router.clone().oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let body = to_bytes(response.into_body(), MAX_LOOKUP_BODY_BYTES)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !status.is_success() {
+        return Err((status, String::from_utf8_lossy(&body).into_owned()));
+    }
+
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
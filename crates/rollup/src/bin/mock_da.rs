@@ -3,7 +3,9 @@ use tracing_subscriber::EnvFilter;
 
 use sov_mock_da::storable::rpc::start_server;
 use sov_mock_da::storable::StorableMockDaService;
-use sov_mock_da::{MockAddress, MockDaConfig};
+use sov_mock_da::{MockAddress, MockDaConfig, RandomizationBehaviour, RandomizationConfig};
+use sov_rollup_interface::common::HexHash;
+use std::ops::RangeInclusive;
 
 // Run with cargo run --bin mock-da-server --no-default-features --features="mock_da_external,mock_zkvm"
 #[derive(Parser, Debug)]
@@ -25,17 +27,137 @@ struct Cli {
     /// Block time in milliseconds for periodic block production
     #[arg(long, default_value = "6000")]
     block_time_ms: u64,
+
+    /// Number of blocks after which a block is considered final
+    #[arg(long, default_value = "0")]
+    finalization_blocks: u32,
+
+    /// Address of the blob sender, as 32 hex-encoded bytes with an optional 0x prefix.
+    /// Defaults to the zero address
+    #[arg(long, value_parser = parse_mock_address)]
+    sender_address: Option<MockAddress>,
+
+    /// Seed of the block randomization, as 32 hex-encoded bytes with an optional 0x prefix.
+    /// Passing any `--randomization-*` flag enables randomization; the seed then defaults to zero
+    #[arg(long, value_parser = parse_seed)]
+    randomization_seed: Option<[u8; HEX_BYTES_LEN]>,
+
+    /// Range of the number of blocks between two randomized reorgs, as `MIN..=MAX`.
+    /// Defaults to `1..=5` when randomization is enabled
+    #[arg(long, value_parser = parse_range)]
+    randomization_reorg_interval: Option<RangeInclusive<u32>>,
+
+    /// Percentage of blobs dropped when a reorg shuffles the blocks.
+    /// Defaults to 0 when randomization is enabled
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    randomization_drop_percent: Option<u8>,
+
+    /// Format of the log output. `RUST_LOG` filters the logs in either format
+    #[arg(long, value_enum, env = "LOG_FORMAT", default_value_t = LogFormat::Pretty)]
+    log_format: LogFormat,
+}
+
+/// Format of the server's log output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines.
+    Pretty,
+    /// One JSON object per line, for log aggregation.
+    Json,
+}
+
+impl Cli {
+    /// The randomization config, or `None` if no `--randomization-*` flag was passed.
+    fn randomization(&self) -> Option<RandomizationConfig> {
+        if self.randomization_seed.is_none()
+            && self.randomization_reorg_interval.is_none()
+            && self.randomization_drop_percent.is_none()
+        {
+            return None;
+        }
+
+        Some(RandomizationConfig {
+            seed: HexHash::new(self.randomization_seed.unwrap_or_default()),
+            reorg_interval: self
+                .randomization_reorg_interval
+                .clone()
+                .unwrap_or(DEFAULT_REORG_INTERVAL),
+            behaviour: RandomizationBehaviour::only_shuffle(
+                self.randomization_drop_percent.unwrap_or(0),
+            ),
+        })
+    }
+}
+
+/// Default of `--randomization-reorg-interval`.
+const DEFAULT_REORG_INTERVAL: RangeInclusive<u32> = 1..=5;
+
+/// Number of bytes in a [`MockAddress`] and a randomization seed.
+const HEX_BYTES_LEN: usize = 32;
+
+fn parse_mock_address(input: &str) -> Result<MockAddress, String> {
+    parse_hex_bytes(input)
+        .map(MockAddress::new)
+        .map_err(|e| format!("invalid address: {e}"))
+}
+
+fn parse_seed(input: &str) -> Result<[u8; HEX_BYTES_LEN], String> {
+    parse_hex_bytes(input).map_err(|e| format!("invalid seed: {e}"))
+}
+
+fn parse_hex_bytes(input: &str) -> Result<[u8; HEX_BYTES_LEN], String> {
+    let hex = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    if hex.len() != HEX_BYTES_LEN * 2 {
+        return Err(format!(
+            "expected {} hex characters, got {}",
+            HEX_BYTES_LEN * 2,
+            hex.len()
+        ));
+    }
+
+    let mut bytes = [0u8; HEX_BYTES_LEN];
+    for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let chunk = std::str::from_utf8(chunk).map_err(|_| "not valid hex")?;
+        *byte = u8::from_str_radix(chunk, 16)
+            .map_err(|_| format!("{chunk:?} is not a valid hex byte"))?;
+    }
+    Ok(bytes)
+}
+
+fn parse_range(input: &str) -> Result<RangeInclusive<u32>, String> {
+    let (min, max) = input
+        .split_once("..=")
+        .ok_or_else(|| format!("expected a range like 1..=5, got {input:?}"))?;
+    let parse = |bound: &str| {
+        bound
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("invalid range bound {bound:?}: {e}"))
+    };
+    let (min, max) = (parse(min)?, parse(max)?);
+    if min == 0 || min > max {
+        return Err(format!("expected 0 < MIN <= MAX, got {input:?}"));
+    }
+    Ok(min..=max)
+}
+
+fn init_logging(format: LogFormat) {
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug")),
+    );
+    match format {
+        LogFormat::Pretty => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug")),
-        )
-        .init();
-
     let cli = Cli::parse();
+    init_logging(cli.log_format);
 
     let block_producing = sov_mock_da::BlockProducingConfig::Periodic {
         block_time_ms: cli.block_time_ms,
@@ -44,11 +166,13 @@ async fn main() -> anyhow::Result<()> {
     // Create DA configuration
     let config = MockDaConfig {
         connection_string: cli.db.clone(),
-        sender_address: MockAddress::new([0u8; 32]),
-        finalization_blocks: 0,
+        sender_address: cli
+            .sender_address
+            .unwrap_or_else(|| MockAddress::new([0u8; HEX_BYTES_LEN])),
+        finalization_blocks: cli.finalization_blocks,
         block_producing,
         da_layer: None,
-        randomization: None,
+        randomization: cli.randomization(),
     };
 
     tracing::info!("Starting mock-da server with configuration:");
@@ -56,6 +180,9 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("  Port: {}", cli.port);
     tracing::info!("  Database: {}", cli.db);
     tracing::info!("  Block producing: {:?}", config.block_producing);
+    tracing::info!("  Finalization blocks: {}", config.finalization_blocks);
+    tracing::info!("  Sender address: {:?}", config.sender_address);
+    tracing::info!("  Randomization: {:?}", config.randomization);
 
     let (shutdown_sender, shutdown_receiver) = tokio::sync::watch::channel(());
     let da_service = StorableMockDaService::from_config(config, shutdown_receiver).await;
@@ -3,7 +3,9 @@
 use anyhow::Context;
 use clap::Parser;
 use rollup_starter::da::DaService;
-use rollup_starter::rollup::StarterRollup;
+use rollup_starter::health::DEFAULT_MAX_SLOT_AGE_SECS;
+use rollup_starter::rollup::{StarterRollup, DEFAULT_MAX_EIP712_PAYLOAD_BYTES};
+use rollup_starter::shutdown::{TxDrain, DEFAULT_DRAIN_TIMEOUT_SECS};
 use rollup_starter::zkvm::{rollup_host_args, InnerZkvm};
 use sov_modules_rollup_blueprint::logging::initialize_logging;
 use sov_modules_rollup_blueprint::FullNodeBlueprint;
@@ -13,6 +15,8 @@ use sov_stf_runner::processes::{RollupProverConfig, RollupProverConfigDiscrimina
 use sov_stf_runner::{from_toml_path, RollupConfig};
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use sov_address::EthereumAddress;
 use sov_modules_api::capabilities::RollupHeight;
@@ -92,6 +96,18 @@ struct Args {
     /// Stops the rollup at a given height.
     #[arg(long, default_value = None)]
     stop_at_rollup_height: Option<u64>,
+
+    /// Seconds without a processed slot after which `GET /health` reports the rollup as unhealthy.
+    #[arg(long, default_value_t = DEFAULT_MAX_SLOT_AGE_SECS)]
+    max_slot_age_secs: u64,
+
+    /// Largest decoded EIP712 transaction, in bytes, accepted by `POST /sequencer/eip712_tx`.
+    #[arg(long, default_value_t = DEFAULT_MAX_EIP712_PAYLOAD_BYTES)]
+    max_eip712_payload_bytes: usize,
+
+    /// Longest time, in seconds, shutdown waits for accepted transactions to be processed.
+    #[arg(long, default_value_t = DEFAULT_DRAIN_TIMEOUT_SECS)]
+    drain_timeout_secs: u64,
 }
 
 #[tokio::main]
@@ -120,6 +136,9 @@ async fn main() {
         prover_config,
         args.start_at_rollup_height.map(RollupHeight::new),
         args.stop_at_rollup_height.map(RollupHeight::new),
+        Duration::from_secs(args.max_slot_age_secs),
+        args.max_eip712_payload_bytes,
+        Duration::from_secs(args.drain_timeout_secs),
     )
     .await
     .expect("Couldn't start rollup");
@@ -153,12 +172,18 @@ async fn new_rollup(
     prover_config: Option<RollupProverConfig<InnerZkvm>>,
     start_at_rollup_height: Option<RollupHeight>,
     stop_at_rollup_height: Option<RollupHeight>,
+    max_slot_age: Duration,
+    max_eip712_payload_bytes: usize,
+    drain_timeout: Duration,
 ) -> Result<Rollup<StarterRollup<Native>, Native>, anyhow::Error> {
     tracing::info!(
         ?rollup_config_path,
         ?genesis_path,
         ?start_at_rollup_height,
         ?stop_at_rollup_height,
+        ?max_slot_age,
+        max_eip712_payload_bytes,
+        ?drain_timeout,
         "Starting rollup with config"
     );
 
@@ -170,7 +195,9 @@ async fn new_rollup(
             )
         })?;
 
-    let rollup = StarterRollup::default();
+    let rollup = StarterRollup::with_max_slot_age(max_slot_age)
+        .with_max_eip712_payload_bytes(max_eip712_payload_bytes)
+        .with_tx_drain(Arc::new(TxDrain::new(drain_timeout)));
 
     rollup
         .create_new_rollup(
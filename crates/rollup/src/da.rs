@@ -18,25 +18,63 @@ mod celestia {
     pub const ROLLUP_PROOF_NAMESPACE: Namespace =
         Namespace::const_v0(config_value!("PROOF_NAMESPACE"));
 
-    pub fn new_verifier() -> CelestiaVerifier {
-        CelestiaVerifier::new(RollupParams {
-            rollup_batch_namespace: ROLLUP_BATCH_NAMESPACE,
-            rollup_proof_namespace: ROLLUP_PROOF_NAMESPACE,
+    /// Builds the [`RollupParams`] for the given namespaces.
+    ///
+    /// Fails if the batch and proof namespaces are equal, since batches and proofs would then be
+    /// read from and written to the same blobs.
+    fn rollup_params(
+        batch_namespace: Namespace,
+        proof_namespace: Namespace,
+    ) -> anyhow::Result<RollupParams> {
+        anyhow::ensure!(
+            batch_namespace != proof_namespace,
+            "Celestia batch and proof namespaces must differ, but both are {:?}. Check BATCH_NAMESPACE and PROOF_NAMESPACE in constants.toml",
+            batch_namespace
+        );
+
+        Ok(RollupParams {
+            rollup_batch_namespace: batch_namespace,
+            rollup_proof_namespace: proof_namespace,
         })
     }
 
+    fn configured_rollup_params() -> RollupParams {
+        rollup_params(ROLLUP_BATCH_NAMESPACE, ROLLUP_PROOF_NAMESPACE)
+            .expect("Invalid Celestia namespace configuration")
+    }
+
+    pub fn new_verifier() -> CelestiaVerifier {
+        CelestiaVerifier::new(configured_rollup_params())
+    }
+
     pub async fn new_da_service<S: Spec>(
         rollup_config: &RollupConfig<S::Address, DaService>,
         _shutdown_receiver: Receiver<()>,
     ) -> DaService {
-        DaService::new(
-            rollup_config.da.clone(),
-            RollupParams {
-                rollup_batch_namespace: ROLLUP_BATCH_NAMESPACE,
-                rollup_proof_namespace: ROLLUP_PROOF_NAMESPACE,
-            },
-        )
-        .await
+        DaService::new(rollup_config.da.clone(), configured_rollup_params()).await
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_identical_namespaces_are_rejected() {
+            let namespace = Namespace::const_v0(*b"sov-test-b");
+
+            let err = rollup_params(namespace, namespace).unwrap_err();
+            assert!(err.to_string().contains("must differ"));
+        }
+
+        #[test]
+        fn test_distinct_namespaces_are_accepted() {
+            let batch_namespace = Namespace::const_v0(*b"sov-test-b");
+            let proof_namespace = Namespace::const_v0(*b"sov-test-p");
+
+            let params = rollup_params(batch_namespace, proof_namespace).unwrap();
+            assert_eq!(params.rollup_batch_namespace, batch_namespace);
+            assert_eq!(params.rollup_proof_namespace, proof_namespace);
+        }
     }
 }
 
@@ -0,0 +1,141 @@
+//! Processing health of the node, served at `GET /health`.
+//!
+//! Unlike `GET /sequencer/ready`, which only reports whether the sequencer accepts transactions,
+//! the health route reports whether the node keeps processing slots:
+//! - `last_slot`: number of the latest slot, read from the ledger, or `null` if it can't be read.
+//! - `last_slot_age_secs`: seconds since the node last processed a slot, or since startup if it
+//!   hasn't processed one yet.
+//! - `da_connected`: whether the DA service answered a head block request.
+//!
+//! The route answers `503 Service Unavailable` once `last_slot_age_secs` exceeds the configured
+//! staleness threshold, and `200 OK` otherwise.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::node::da::DaService as DaServiceTrait;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+use crate::metrics::latest_slot_number;
+
+/// Default staleness threshold of `GET /health`, in seconds.
+pub const DEFAULT_MAX_SLOT_AGE_SECS: u64 = 60;
+
+/// Longest time the DA service gets to answer the connectivity probe of a health request.
+const DA_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Response of `GET /health`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// Number of the latest slot, or `None` if the ledger couldn't be read.
+    pub last_slot: Option<u64>,
+    /// Seconds since the node last processed a slot.
+    pub last_slot_age_secs: u64,
+    /// Whether the DA service answered a head block request.
+    pub da_connected: bool,
+}
+
+/// Tracks when the node last processed a slot.
+pub struct HealthMonitor {
+    max_slot_age: Duration,
+    last_update: Mutex<Instant>,
+}
+
+impl HealthMonitor {
+    /// Creates a monitor reporting the node as stale once no slot was processed for
+    /// `max_slot_age`. The first slot is awaited from now on.
+    pub fn new(max_slot_age: Duration) -> Self {
+        Self {
+            max_slot_age,
+            last_update: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Records that the node just processed a slot.
+    pub fn record_update(&self) {
+        *self
+            .last_update
+            .lock()
+            .expect("Health monitor lock is poisoned") = Instant::now();
+    }
+
+    /// Time since the node last processed a slot.
+    pub fn last_update_age(&self) -> Duration {
+        self.last_update
+            .lock()
+            .expect("Health monitor lock is poisoned")
+            .elapsed()
+    }
+
+    /// Whether the node went longer than the staleness threshold without processing a slot.
+    pub fn is_stale(&self) -> bool {
+        self.last_update_age() > self.max_slot_age
+    }
+
+    /// Records an update each time `updates` changes, until its sender is dropped.
+    pub fn track<T>(self: Arc<Self>, mut updates: watch::Receiver<T>)
+    where
+        T: Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                self.record_update();
+            }
+        });
+    }
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_MAX_SLOT_AGE_SECS))
+    }
+}
+
+/// Returns a router serving `GET /health` on top of `modules`, the router that serves
+/// `GET /ledger/slots/latest`.
+pub fn health_router<Da>(monitor: Arc<HealthMonitor>, da_service: Da, modules: Router) -> Router
+where
+    Da: DaServiceTrait + Clone,
+{
+    Router::new()
+        .route("/health", get(serve_health::<Da>))
+        .with_state((monitor, da_service, modules))
+}
+
+async fn serve_health<Da>(
+    State((monitor, da_service, modules)): State<(Arc<HealthMonitor>, Da, Router)>,
+) -> (StatusCode, Json<HealthResponse>)
+where
+    Da: DaServiceTrait + Clone,
+{
+    let last_slot = match latest_slot_number(&modules).await {
+        Ok(slot_number) => Some(slot_number),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read the latest slot for /health");
+            None
+        }
+    };
+    let da_connected = matches!(
+        tokio::time::timeout(DA_PROBE_TIMEOUT, da_service.get_head_block_header()).await,
+        Ok(Ok(_))
+    );
+
+    let status = if monitor.is_stale() {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+
+    (
+        status,
+        Json(HealthResponse {
+            last_slot,
+            last_slot_age_secs: monitor.last_update_age().as_secs(),
+            da_connected,
+        }),
+    )
+}
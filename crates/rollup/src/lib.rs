@@ -1,3 +1,7 @@
+pub mod archival;
 pub mod da;
+pub mod health;
+pub mod metrics;
 pub mod rollup;
+pub mod shutdown;
 pub mod zkvm;
@@ -0,0 +1,165 @@
+//! Prometheus metrics served at `GET /metrics`.
+//!
+//! Exposed metrics:
+//! - [`ACCEPTED_TXS_METRIC`]: counter of transactions the sequencer accepted, through
+//!   `POST /sequencer/txs`, `POST /sequencer/eip712_tx` or `POST /sequencer/eip712_tx_batch`.
+//! - [`REJECTED_TXS_METRIC`]: counter of transactions the sequencer rejected on those routes.
+//! - [`SLOT_HEIGHT_METRIC`]: gauge of the latest slot number, read from the ledger on each scrape.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::{header, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use prometheus_exporter::prometheus::{Encoder, IntCounter, IntGauge, Registry, TextEncoder};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Name of the accepted transactions counter.
+pub const ACCEPTED_TXS_METRIC: &str = "rollup_accepted_txs_total";
+/// Name of the rejected transactions counter.
+pub const REJECTED_TXS_METRIC: &str = "rollup_rejected_txs_total";
+/// Name of the current slot height gauge.
+pub const SLOT_HEIGHT_METRIC: &str = "rollup_slot_height";
+
+/// Route of the standard transaction submission endpoint.
+const ACCEPT_TX_ROUTE: &str = "/sequencer/txs";
+/// Ledger route the slot height is read from.
+const LATEST_SLOT_ROUTE: &str = "/ledger/slots/latest";
+/// Largest response body read from the latest slot lookup.
+const MAX_SLOT_BODY_BYTES: usize = 1024 * 1024;
+
+/// Transaction and slot metrics of a rollup node, kept in their own registry.
+pub struct RollupMetrics {
+    registry: Registry,
+    accepted_txs: IntCounter,
+    rejected_txs: IntCounter,
+    slot_height: IntGauge,
+}
+
+impl RollupMetrics {
+    /// Creates the metrics, all starting at zero.
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let accepted_txs = IntCounter::new(
+            ACCEPTED_TXS_METRIC,
+            "Transactions accepted by the sequencer",
+        )
+        .expect("Metric name and help are valid");
+        let rejected_txs = IntCounter::new(
+            REJECTED_TXS_METRIC,
+            "Transactions rejected by the sequencer",
+        )
+        .expect("Metric name and help are valid");
+        let slot_height = IntGauge::new(SLOT_HEIGHT_METRIC, "Number of the latest slot")
+            .expect("Metric name and help are valid");
+        for metric in [&accepted_txs, &rejected_txs] {
+            registry
+                .register(Box::new(metric.clone()))
+                .expect("Metric names are unique");
+        }
+        registry
+            .register(Box::new(slot_height.clone()))
+            .expect("Metric names are unique");
+
+        Self {
+            registry,
+            accepted_txs,
+            rejected_txs,
+            slot_height,
+        }
+    }
+
+    /// Counts one submitted transaction as accepted or rejected.
+    pub fn record_tx(&self, accepted: bool) {
+        if accepted {
+            self.accepted_txs.inc();
+        } else {
+            self.rejected_txs.inc();
+        }
+    }
+
+    /// Sets the slot height gauge.
+    pub fn set_slot_height(&self, slot_number: u64) {
+        self.slot_height
+            .set(i64::try_from(slot_number).unwrap_or(i64::MAX));
+    }
+
+    /// Renders all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Encoding gathered metrics into a buffer can't fail");
+        String::from_utf8(buffer).expect("The text format is UTF-8")
+    }
+}
+
+impl Default for RollupMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns a router serving `GET /metrics` on top of `modules`, the router that serves
+/// `GET /ledger/slots/latest`.
+pub fn metrics_router(metrics: Arc<RollupMetrics>, modules: Router) -> Router {
+    Router::new()
+        .route("/metrics", get(serve_metrics))
+        .with_state((metrics, modules))
+}
+
+/// Middleware counting the responses of `POST /sequencer/txs` as accepted or rejected
+/// transactions. Other requests pass through untouched.
+pub async fn count_submitted_txs(
+    State(metrics): State<Arc<RollupMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_submission = request.method() == Method::POST && request.uri().path() == ACCEPT_TX_ROUTE;
+    let response = next.run(request).await;
+    if is_submission {
+        metrics.record_tx(response.status().is_success());
+    }
+    response
+}
+
+async fn serve_metrics(
+    State((metrics, modules)): State<(Arc<RollupMetrics>, Router)>,
+) -> impl IntoResponse {
+    match latest_slot_number(&modules).await {
+        Ok(slot_number) => metrics.set_slot_height(slot_number),
+        Err(e) => tracing::warn!(error = %e, "Failed to read the latest slot for /metrics"),
+    }
+
+    (
+        [(
+            header::CONTENT_TYPE,
+            TextEncoder::new().format_type().to_string(),
+        )],
+        metrics.render(),
+    )
+}
+
+/// Reads the `number` of `GET /ledger/slots/latest` served by `router`.
+pub(crate) async fn latest_slot_number(router: &Router) -> anyhow::Result<u64> {
+    let request = axum::http::Request::get(LATEST_SLOT_ROUTE).body(Body::empty())?;
+    let response = match router.clone().oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+
+    let status = response.status();
+    let body = to_bytes(response.into_body(), MAX_SLOT_BODY_BYTES).await?;
+    anyhow::ensure!(
+        status == StatusCode::OK,
+        "{LATEST_SLOT_ROUTE} returned {status}: {}",
+        String::from_utf8_lossy(&body)
+    );
+    let slot: serde_json::Value = serde_json::from_slice(&body)?;
+    slot["number"]
+        .as_u64()
+        .ok_or_else(|| anyhow::anyhow!("{LATEST_SLOT_ROUTE} has no slot number"))
+}
@@ -2,10 +2,13 @@
 //! StarterRollup provides a minimal self-contained rollup implementation
 
 use async_trait::async_trait;
-use axum::extract::State;
-use axum::response::IntoResponse;
+use axum::body::to_bytes;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::routing::post;
 use axum::Json;
+use serde::{Deserialize, Serialize};
 use sov_address::{EthereumAddress, EvmCryptoSpec, FromVmAddress};
 use sov_db::ledger_db::LedgerDb;
 use sov_db::storage_manager::NomtStorageManager;
@@ -36,10 +39,15 @@ use sov_state::Storage;
 use sov_stf_runner::processes::{ParallelProverService, ProverService, RollupProverConfig};
 use sov_stf_runner::RollupConfig;
 use std::sync::Arc;
+use std::time::Duration;
 use stf_starter::Runtime;
 use tokio::sync::watch;
 
+use crate::archival::total_supply_batch_router;
 use crate::da::{new_da_service, new_verifier, DaService, DaSpec};
+use crate::health::{health_router, HealthMonitor};
+use crate::metrics::{count_submitted_txs, metrics_router, RollupMetrics};
+use crate::shutdown::{track_submissions, TxDrain};
 use crate::zkvm::{create_inner_vm_from_config, get_outer_vm, Hasher, InnerZkvm, OuterZkvm};
 
 type NativeStorage = NomtProverStorage<
@@ -57,10 +65,54 @@ pub type EthSpec<Da, InnerZkvm, OuterZkvm> = ConfigurableSpec<
     NativeStorage,
 >;
 
+/// Default largest decoded transaction accepted by `POST /sequencer/eip712_tx`, in bytes.
+pub const DEFAULT_MAX_EIP712_PAYLOAD_BYTES: usize = 128 * 1024;
+
 /// Starter rollup implementation.
-#[derive(Default)]
 pub struct StarterRollup<M> {
     phantom: std::marker::PhantomData<M>,
+    metrics: Arc<RollupMetrics>,
+    health: Arc<HealthMonitor>,
+    drain: Arc<TxDrain>,
+    max_eip712_payload_bytes: usize,
+}
+
+impl<M> StarterRollup<M> {
+    /// Creates a rollup whose `GET /health` reports it stale once it went `max_slot_age` without
+    /// processing a slot.
+    pub fn with_max_slot_age(max_slot_age: Duration) -> Self {
+        Self {
+            health: Arc::new(HealthMonitor::new(max_slot_age)),
+            ..Self::default()
+        }
+    }
+
+    /// Rejects EIP712 transactions whose decoded body is longer than `max_bytes` with
+    /// `413 Payload Too Large`. Request bodies too long to hold such transactions are rejected
+    /// before being read.
+    pub fn with_max_eip712_payload_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_eip712_payload_bytes = max_bytes;
+        self
+    }
+
+    /// Waits for accepted transactions on shutdown with `drain`, for instance one with a custom
+    /// timeout.
+    pub fn with_tx_drain(mut self, drain: Arc<TxDrain>) -> Self {
+        self.drain = drain;
+        self
+    }
+}
+
+impl<M> Default for StarterRollup<M> {
+    fn default() -> Self {
+        Self {
+            phantom: std::marker::PhantomData,
+            metrics: Arc::default(),
+            health: Arc::default(),
+            drain: Arc::default(),
+            max_eip712_payload_bytes: DEFAULT_MAX_EIP712_PAYLOAD_BYTES,
+        }
+    }
 }
 
 /// This is the place where all the rollup components come together, and
@@ -105,18 +157,49 @@ impl FullNodeBlueprint<Native> for StarterRollup<Native> {
         shutdown_receiver: watch::Receiver<()>,
         ledger_db: &LedgerDb,
         sequencer: &SequencerCreationReceipt<Self::Spec>,
-        _da_service: &Self::DaService,
+        da_service: &Self::DaService,
         rollup_config: &RollupConfig<<Self::Spec as Spec>::Address, Self::DaService>,
     ) -> anyhow::Result<sov_modules_api::NodeEndpoints> {
-        sov_modules_rollup_blueprint::register_endpoints::<Self, _>(
+        self.health.clone().track(state_update_receiver.clone());
+
+        // Keep serving the node APIs while buffered txs are drained
+        let mut endpoints = sov_modules_rollup_blueprint::register_endpoints::<Self, _>(
             state_update_receiver.clone(),
             sync_status_receiver,
-            shutdown_receiver,
+            self.drain.shutdown_signal(shutdown_receiver),
             ledger_db,
             sequencer,
             rollup_config,
         )
-        .await
+        .await?;
+        self.drain
+            .clone()
+            .track_inclusion(state_update_receiver.clone(), endpoints.axum_router.clone());
+
+        let batch_router = total_supply_batch_router(endpoints.axum_router.clone());
+        endpoints.axum_router = endpoints.axum_router.merge(batch_router);
+
+        let health_router = health_router(
+            self.health.clone(),
+            da_service.clone(),
+            endpoints.axum_router.clone(),
+        );
+        endpoints.axum_router = endpoints.axum_router.merge(health_router);
+
+        let metrics_router = metrics_router(self.metrics.clone(), endpoints.axum_router.clone());
+        endpoints.axum_router = endpoints
+            .axum_router
+            .merge(metrics_router)
+            .layer(axum::middleware::from_fn_with_state(
+                self.metrics.clone(),
+                count_submitted_txs,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                self.drain.clone(),
+                track_submissions,
+            ));
+
+        Ok(endpoints)
     }
 
     async fn create_da_service(
@@ -124,6 +207,8 @@ impl FullNodeBlueprint<Native> for StarterRollup<Native> {
         rollup_config: &RollupConfig<<Self::Spec as Spec>::Address, Self::DaService>,
         shutdown_receiver: tokio::sync::watch::Receiver<()>,
     ) -> Self::DaService {
+        // Transactions buffered when shutdown is requested still need DA to be posted
+        let shutdown_receiver = self.drain.shutdown_signal(shutdown_receiver);
         new_da_service::<Self::Spec>(rollup_config, shutdown_receiver).await
     }
 
@@ -163,12 +248,32 @@ impl FullNodeBlueprint<Native> for StarterRollup<Native> {
                 response_size_limit: (1024 * 1024) - (1024 * 30), // Limit our response size to 1MB, leaving 30kb for headers, overhead, and misestimation.
             },
             buffer_raw_txs: true,
-            shutdown_receiver,
+            // Keep flushing buffered txs until they are drained once shutdown is requested
+            shutdown_receiver: self.drain.shutdown_signal(shutdown_receiver),
         };
 
+        // Oversized requests are turned down before their body is buffered and deserialized
+        let max_request_bytes = max_eip712_request_bytes(self.max_eip712_payload_bytes);
         let axum_router = axum::Router::new()
-            .route("/sequencer/eip712_tx", post(accept_eip712_tx::<Seq>))
-            .with_state(sequencer.clone());
+            .route(
+                "/sequencer/eip712_tx",
+                post(accept_eip712_tx::<Seq>).layer(DefaultBodyLimit::max(max_request_bytes)),
+            )
+            .route(
+                "/sequencer/eip712_tx_batch",
+                post(accept_eip712_tx_batch::<Seq>).layer(DefaultBodyLimit::max(
+                    max_request_bytes.saturating_mul(MAX_EIP712_BATCH_TXS),
+                )),
+            )
+            .with_state((
+                sequencer.clone(),
+                self.metrics.clone(),
+                self.max_eip712_payload_bytes,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                self.drain.clone(),
+                track_submissions,
+            ));
 
         Ok(NodeEndpoints {
             axum_router,
@@ -196,24 +301,164 @@ impl FullNodeBlueprint<Native> for StarterRollup<Native> {
 
 impl sov_modules_rollup_blueprint::WalletBlueprint<Native> for StarterRollup<Native> {}
 
+/// Most transactions accepted in one `POST /sequencer/eip712_tx_batch` request. Larger batches are
+/// rejected with `413 Payload Too Large` before any of their transactions is submitted.
+pub const MAX_EIP712_BATCH_TXS: usize = 64;
+
+/// Room left in an EIP712 request body for the JSON around the base64 encoded transaction.
+const EIP712_REQUEST_OVERHEAD_BYTES: usize = 1024;
+
+/// Largest request body accepted by `POST /sequencer/eip712_tx` for transactions of at most
+/// `max_payload_bytes`, which are sent base64 encoded.
+fn max_eip712_request_bytes(max_payload_bytes: usize) -> usize {
+    max_payload_bytes
+        .div_ceil(3)
+        .saturating_mul(4)
+        .saturating_add(EIP712_REQUEST_OVERHEAD_BYTES)
+}
+
+/// Largest error body read back from a rejected transaction of a batch.
+const MAX_BATCH_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// State of the EIP712 handlers: the sequencer, the metrics and the payload size limit.
+type Eip712State<Seq> = (Arc<Seq>, Arc<RollupMetrics>, usize);
+
+type Eip712TxInfo<Seq> = TxInfoWithConfirmation<
+    DaBlobHash<<<Seq as Sequencer>::Da as DaServiceTrait>::Spec>,
+    <Seq as Sequencer>::Confirmation,
+>;
+
+/// Result of one transaction of a `POST /sequencer/eip712_tx_batch` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Eip712TxBatchItem<T> {
+    /// The transaction was accepted by the sequencer.
+    Submitted(T),
+    /// The transaction was rejected; `error` is what `POST /sequencer/eip712_tx` would have
+    /// answered with `status`.
+    Failed {
+        /// HTTP status of the rejection.
+        status: u16,
+        /// The error body of the rejection.
+        error: serde_json::Value,
+    },
+}
+
+/// Response of `POST /sequencer/eip712_tx_batch`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eip712TxBatchResponse<T> {
+    /// One entry per submitted transaction, in request order.
+    pub results: Vec<Eip712TxBatchItem<T>>,
+}
+
 /// Handler for accepting EIP712 authenticated transactions
 async fn accept_eip712_tx<Seq>(
-    State(sequencer): State<Arc<Seq>>,
+    State((sequencer, metrics, max_payload_bytes)): State<Eip712State<Seq>>,
     tx: Json<AcceptTx>,
-) -> ApiResult<
-    TxInfoWithConfirmation<DaBlobHash<<Seq::Da as DaServiceTrait>::Spec>, Seq::Confirmation>,
->
+) -> ApiResult<Eip712TxInfo<Seq>>
+where
+    Seq: Sequencer + 'static,
+    Seq::Rt: Eip712AuthenticatorTrait<Seq::Spec>,
+    <Seq::Rt as RuntimeTrait<Seq::Spec>>::Auth: TransactionAuthenticator<Seq::Spec>,
+{
+    Ok(
+        submit_eip712_tx(sequencer, &metrics, max_payload_bytes, tx.0)
+            .await?
+            .into(),
+    )
+}
+
+/// Handler for accepting a batch of EIP712 authenticated transactions.
+///
+/// The transactions are submitted one after the other, in order, and a rejected transaction
+/// doesn't stop the rest of the batch. Answers `207 Multi-Status` if any transaction was rejected,
+/// and `413 Payload Too Large` without submitting anything if the batch has more than
+/// [`MAX_EIP712_BATCH_TXS`] transactions.
+async fn accept_eip712_tx_batch<Seq>(
+    State((sequencer, metrics, max_payload_bytes)): State<Eip712State<Seq>>,
+    txs: Json<Vec<AcceptTx>>,
+) -> Result<(StatusCode, Json<Eip712TxBatchResponse<Eip712TxInfo<Seq>>>), Response>
+where
+    Seq: Sequencer + 'static,
+    Seq::Rt: Eip712AuthenticatorTrait<Seq::Spec>,
+    <Seq::Rt as RuntimeTrait<Seq::Spec>>::Auth: TransactionAuthenticator<Seq::Spec>,
+{
+    if txs.0.len() > MAX_EIP712_BATCH_TXS {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "The batch has {} transactions, more than the limit of {MAX_EIP712_BATCH_TXS}",
+                txs.0.len()
+            ),
+        )
+            .into_response());
+    }
+
+    let mut results = Vec::with_capacity(txs.0.len());
+    for tx in txs.0 {
+        let submission = submit_eip712_tx(sequencer.clone(), &metrics, max_payload_bytes, tx);
+        let item = match submission.await {
+            Ok(info) => Eip712TxBatchItem::Submitted(info),
+            Err(response) => {
+                let status = response.status().as_u16();
+                let error = match to_bytes(response.into_body(), MAX_BATCH_ERROR_BODY_BYTES).await {
+                    Ok(body) => serde_json::from_slice(&body).unwrap_or_else(|_| {
+                        serde_json::Value::String(String::from_utf8_lossy(&body).into_owned())
+                    }),
+                    Err(e) => serde_json::Value::String(e.to_string()),
+                };
+                Eip712TxBatchItem::Failed { status, error }
+            }
+        };
+        results.push(item);
+    }
+
+    let all_submitted = results
+        .iter()
+        .all(|item| matches!(item, Eip712TxBatchItem::Submitted(_)));
+    let status = if all_submitted {
+        StatusCode::OK
+    } else {
+        StatusCode::MULTI_STATUS
+    };
+    Ok((status, Json(Eip712TxBatchResponse { results })))
+}
+
+/// Encodes `tx` with EIP712 auth and submits it to the sequencer, counting it in `metrics`.
+///
+/// Transactions whose decoded body is longer than `max_payload_bytes` are rejected with
+/// `413 Payload Too Large` before being encoded.
+async fn submit_eip712_tx<Seq>(
+    sequencer: Arc<Seq>,
+    metrics: &RollupMetrics,
+    max_payload_bytes: usize,
+    tx: AcceptTx,
+) -> Result<Eip712TxInfo<Seq>, Response>
 where
     Seq: Sequencer + 'static,
     Seq::Rt: Eip712AuthenticatorTrait<Seq::Spec>,
     <Seq::Rt as RuntimeTrait<Seq::Spec>>::Auth: TransactionAuthenticator<Seq::Spec>,
 {
-    let raw_tx = RawTx::new(tx.0.body.blob);
+    let payload_bytes = tx.body.blob.len();
+    if payload_bytes > max_payload_bytes {
+        metrics.record_tx(false);
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "The EIP712 transaction is {payload_bytes} bytes long, \
+                 more than the limit of {max_payload_bytes} bytes"
+            ),
+        )
+            .into_response());
+    }
+
+    let raw_tx = RawTx::new(tx.body.blob);
     let encoded_tx = Seq::Rt::encode_with_eip712_auth(raw_tx);
 
     // Submit to sequencer (similar to axum_accept_tx but with EIP712 auth)
-    let tx_with_hash = tokio::spawn(async move { sequencer.accept_tx(encoded_tx).await })
-        .await
+    let result = tokio::spawn(async move { sequencer.accept_tx(encoded_tx).await }).await;
+    metrics.record_tx(matches!(result, Ok(Ok(_))));
+    let tx_with_hash = result
         .map_err(|e| {
             tracing::error!(error = %e, "A panic occurred while accepting an EIP712 transaction");
             sov_rest_utils::errors::internal_server_error_response_500(
@@ -231,6 +476,5 @@ where
         id: tx_with_hash.tx_hash,
         confirmation: tx_with_hash.confirmation,
         status: TxStatus::Submitted,
-    }
-    .into())
+    })
 }
@@ -0,0 +1,265 @@
+//! Draining of in-flight transactions on shutdown.
+//!
+//! Once the node is asked to shut down, [`track_submissions`] answers
+//! `503 Service Unavailable` to every transaction submission, while the node APIs, the DA service
+//! and the components holding buffered transactions keep their shutdown signal back until
+//! [`TxDrain`] saw every accepted transaction in the ledger, or its drain timeout ran out, so
+//! that the transactions they already accepted still get sequenced.
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tower::ServiceExt;
+
+/// Default longest time buffered transactions get to be sequenced after shutdown was requested,
+/// in seconds.
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+/// Routes accepting new transactions, which are closed while draining.
+const SUBMISSION_ROUTES: [&str; 3] = [
+    "/sequencer/txs",
+    "/sequencer/eip712_tx",
+    "/sequencer/eip712_tx_batch",
+];
+
+/// How often the ledger is polled for the accepted transactions while draining.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Accepted transactions not seen in the ledger after this long are assumed dropped by the
+/// sequencer, and no longer waited for.
+const PENDING_TX_TTL: Duration = Duration::from_secs(600);
+
+/// Largest submission response read back to find the hashes of the accepted transactions.
+const MAX_SUBMISSION_BODY_BYTES: usize = 1024 * 1024;
+
+/// Tracks the transactions the node accepted but didn't process yet, so that shutdown can wait
+/// for them.
+pub struct TxDrain {
+    timeout: Duration,
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    /// Hashes of the accepted transactions not found in the ledger yet, with when they were
+    /// accepted.
+    pending: Mutex<HashMap<String, Instant>>,
+    /// Router serving `GET /ledger/txs/{tx_hash}`, set once the node endpoints exist.
+    ledger: OnceLock<Router>,
+    shutdown: OnceLock<watch::Receiver<()>>,
+}
+
+impl TxDrain {
+    /// Creates a drain giving buffered transactions at most `timeout` to be sequenced.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            draining: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            pending: Mutex::default(),
+            ledger: OnceLock::new(),
+            shutdown: OnceLock::new(),
+        }
+    }
+
+    /// Whether shutdown was requested, so new transactions are rejected.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Number of accepted transactions not found in the ledger yet.
+    pub fn pending_txs(&self) -> usize {
+        self.pending.lock().expect("Drain lock is poisoned").len()
+    }
+
+    /// Returns a shutdown signal that fires once the node drained its transactions after
+    /// `shutdown` fired, or its sender was dropped.
+    ///
+    /// The drain runs once: later calls return the signal of the first one, whose `shutdown` is
+    /// the one waited for.
+    pub fn shutdown_signal(self: &Arc<Self>, shutdown: watch::Receiver<()>) -> watch::Receiver<()> {
+        self.shutdown
+            .get_or_init(|| {
+                let (drained_sender, drained_receiver) = watch::channel(());
+                tokio::spawn(self.clone().drain(shutdown, drained_sender));
+                drained_receiver
+            })
+            .clone()
+    }
+
+    /// Looks the accepted transactions up with `ledger`, the router serving
+    /// `GET /ledger/txs/{tx_hash}`, each time `updates` changes, until its sender is dropped.
+    pub fn track_inclusion<T>(self: Arc<Self>, mut updates: watch::Receiver<T>, ledger: Router)
+    where
+        T: Send + Sync + 'static,
+    {
+        if self.ledger.set(ledger).is_err() {
+            tracing::warn!("The drain already tracks a ledger, ignoring the new one");
+            return;
+        }
+        tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                self.settle().await;
+            }
+        });
+    }
+
+    async fn drain(
+        self: Arc<Self>,
+        mut shutdown: watch::Receiver<()>,
+        drained_sender: watch::Sender<()>,
+    ) {
+        // An error means the sender was dropped, which is a shutdown as well
+        let _ = shutdown.changed().await;
+        self.draining.store(true, Ordering::SeqCst);
+        tracing::info!(
+            timeout = ?self.timeout,
+            pending_txs = self.pending_txs(),
+            "Shutdown requested, rejecting new transactions while buffered ones are sequenced"
+        );
+
+        let drained = tokio::time::timeout(self.timeout, async {
+            loop {
+                self.settle().await;
+                if self.in_flight.load(Ordering::SeqCst) == 0 && self.pending_txs() == 0 {
+                    break;
+                }
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            }
+        })
+        .await;
+        match drained {
+            Ok(()) => tracing::info!("All accepted transactions were processed, shutting down"),
+            Err(_) => tracing::warn!(
+                pending_txs = self.pending_txs(),
+                "Drain timeout reached before all accepted transactions were processed, shutting down"
+            ),
+        }
+
+        let _ = drained_sender.send(());
+    }
+
+    fn record_accepted(&self, tx_hashes: impl IntoIterator<Item = String>) {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().expect("Drain lock is poisoned");
+        pending.extend(tx_hashes.into_iter().map(|tx_hash| (tx_hash, now)));
+    }
+
+    /// Forgets the pending transactions found in the ledger, or accepted too long ago.
+    async fn settle(&self) {
+        let Some(ledger) = self.ledger.get() else {
+            return;
+        };
+        let candidates: Vec<(String, Instant)> = self
+            .pending
+            .lock()
+            .expect("Drain lock is poisoned")
+            .iter()
+            .map(|(tx_hash, accepted_at)| (tx_hash.clone(), *accepted_at))
+            .collect();
+
+        for (tx_hash, accepted_at) in candidates {
+            let expired = accepted_at.elapsed() > PENDING_TX_TTL;
+            if expired {
+                tracing::warn!(%tx_hash, "Accepted transaction never reached the ledger");
+            }
+            if expired || is_in_ledger(ledger, &tx_hash).await {
+                self.pending
+                    .lock()
+                    .expect("Drain lock is poisoned")
+                    .remove(&tx_hash);
+            }
+        }
+    }
+}
+
+impl Default for TxDrain {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS))
+    }
+}
+
+/// Whether `GET /ledger/txs/{tx_hash}` served by `ledger` finds the transaction.
+async fn is_in_ledger(ledger: &Router, tx_hash: &str) -> bool {
+    let Ok(request) = Request::get(format!("/ledger/txs/{tx_hash}")).body(Body::empty()) else {
+        return false;
+    };
+    match ledger.clone().oneshot(request).await {
+        Ok(response) => response.status().is_success(),
+        Err(never) => match never {},
+    }
+}
+
+/// Decrements the in-flight submissions of a drain when dropped.
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Middleware tracking transaction submissions: it answers `503 Service Unavailable` to them once
+/// the node is draining, and otherwise records the transactions they got accepted for the drain
+/// to wait on. Other requests pass through untouched.
+pub async fn track_submissions(
+    State(drain): State<Arc<TxDrain>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_submission =
+        request.method() == Method::POST && SUBMISSION_ROUTES.contains(&request.uri().path());
+    if !is_submission {
+        return next.run(request).await;
+    }
+
+    // Counted before checking the flag, so that the drain can't miss a submission it let through
+    drain.in_flight.fetch_add(1, Ordering::SeqCst);
+    let _in_flight = InFlightGuard(&drain.in_flight);
+    if drain.is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "The rollup is shutting down and no longer accepts transactions",
+        )
+            .into_response();
+    }
+
+    let response = next.run(request).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_SUBMISSION_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to read a transaction submission response");
+            Bytes::new()
+        }
+    };
+    drain.record_accepted(accepted_tx_hashes(&body));
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Hashes of the transactions accepted by a submission response: the `id` of a single
+/// submission, or those of the submitted entries of an EIP712 batch.
+fn accepted_tx_hashes(body: &[u8]) -> Vec<String> {
+    let Ok(response) = serde_json::from_slice::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    if let Some(id) = response["id"].as_str() {
+        return vec![id.to_string()];
+    }
+    response["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item["submitted"]["id"].as_str())
+        .map(str::to_string)
+        .collect()
+}
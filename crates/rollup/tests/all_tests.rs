@@ -1,3 +1,8 @@
+mod archival;
 mod bank;
+mod eip712;
+mod health;
+mod metrics;
+mod shutdown;
 // Add additional tests here
 mod test_helpers;
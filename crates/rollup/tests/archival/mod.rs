@@ -0,0 +1,117 @@
+use super::test_helpers::{create_token_tx, run_with_rollup};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use rollup_starter::archival::{
+    SlotTotalSupply, TotalSupplyBatchRequest, TotalSupplyBatchResponse, MAX_BATCH_SLOTS,
+};
+use sov_bank::TokenId;
+use sov_cli::NodeClient;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_total_supply_batch_matches_single_slot_lookups() -> Result<(), anyhow::Error> {
+    run_with_rollup(check_total_supply_batch).await
+}
+
+async fn post_batch(
+    rest_port: u16,
+    token_id: &str,
+    slot_numbers: Vec<u64>,
+) -> Result<reqwest::Response, anyhow::Error> {
+    Ok(reqwest::Client::new()
+        .post(format!(
+            "http://127.0.0.1:{rest_port}/modules/bank/tokens/{token_id}/total-supply/batch"
+        ))
+        .json(&TotalSupplyBatchRequest { slot_numbers })
+        .send()
+        .await?)
+}
+
+/// `GET /modules/bank/tokens/{token_id}/total-supply?slot_number=..`, or `None` on a 404.
+async fn total_supply_at(
+    rest_port: u16,
+    token_id: &TokenId,
+    slot_number: u64,
+) -> Result<Option<serde_json::Value>, anyhow::Error> {
+    let response = reqwest::get(format!(
+        "http://127.0.0.1:{rest_port}/modules/bank/tokens/{token_id}/total-supply?slot_number={slot_number}"
+    ))
+    .await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    anyhow::ensure!(
+        response.status().is_success(),
+        "Total supply lookup at slot {slot_number} returned {}",
+        response.status()
+    );
+    Ok(Some(response.json().await?))
+}
+
+async fn check_total_supply_batch(rest_port: u16) -> Result<(), anyhow::Error> {
+    let client = NodeClient::new_at_localhost(rest_port).await?;
+
+    // Wait until the rollup has processed a slot
+    let mut slot_subscription = client.client.subscribe_slots().await?;
+    let created_before = slot_subscription
+        .next()
+        .await
+        .transpose()?
+        .map(|slot| slot.number)
+        .unwrap_or_default();
+
+    let (tx, token_id, _) = create_token_tx("archival-token");
+    client.client.send_txs_to_sequencer(&[tx]).await?;
+
+    // Advance until the token shows up, then a few slots more
+    let mut latest_slot = created_before;
+    while total_supply_at(rest_port, &token_id, latest_slot)
+        .await?
+        .is_none()
+    {
+        latest_slot = slot_subscription
+            .next()
+            .await
+            .transpose()?
+            .map(|slot| slot.number)
+            .ok_or_else(|| anyhow::anyhow!("The slot subscription ended"))?;
+    }
+    for _ in 0..2 {
+        latest_slot = slot_subscription
+            .next()
+            .await
+            .transpose()?
+            .map(|slot| slot.number)
+            .ok_or_else(|| anyhow::anyhow!("The slot subscription ended"))?;
+    }
+
+    let mut slot_numbers: Vec<u64> = (1..=latest_slot).rev().collect();
+    slot_numbers.push(created_before);
+    let response = post_batch(rest_port, &token_id.to_string(), slot_numbers.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let batch: TotalSupplyBatchResponse = response.json().await?;
+
+    let mut expected = Vec::with_capacity(slot_numbers.len());
+    for slot_number in slot_numbers {
+        expected.push(SlotTotalSupply {
+            slot_number,
+            supply: total_supply_at(rest_port, &token_id, slot_number).await?,
+        });
+    }
+    assert_eq!(batch.supplies, expected);
+    assert!(
+        batch.supplies.iter().any(|slot| slot.supply.is_none()),
+        "The token didn't exist before it was created"
+    );
+    assert!(
+        batch.supplies.iter().any(|slot| slot.supply.is_some()),
+        "The token exists once created"
+    );
+
+    // Oversized batches and ids that aren't token ids are turned down
+    let oversized = (0..=MAX_BATCH_SLOTS as u64).collect();
+    let response = post_batch(rest_port, &token_id.to_string(), oversized).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = post_batch(rest_port, "not-a-token%3Fslot_number=1", vec![latest_slot]).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -1,22 +1,19 @@
-use super::test_helpers::{read_private_keys, start_rollup};
+use super::test_helpers::{
+    create_token_tx, create_token_tx_with_gas, read_private_keys, run_with_rollup, TestSpec,
+    MAX_TX_FEE,
+};
 use anyhow::Context;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use futures::StreamExt;
-use sov_address::{EthereumAddress, EvmCryptoSpec};
+use reqwest::StatusCode;
 use sov_cli::NodeClient;
-use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
-use sov_mock_zkvm::MockZkvm;
 use sov_modules_api::capabilities::UniquenessData;
-use sov_modules_api::configurable_spec::ConfigurableSpec;
-use sov_modules_api::execution_mode::Native;
 use sov_modules_api::macros::config_value;
 use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
-use sov_modules_api::{Amount, Spec};
+use sov_modules_api::{Amount, GasArray, Spec};
 use sov_modules_rollup_blueprint::logging::default_rust_log_value;
 use sov_rollup_interface::common::SafeVec;
-use sov_rollup_interface::da::DaSpec;
-use sov_rollup_interface::zk::CryptoSpec;
-use sov_state::nomt::prover_storage::NomtProverStorage;
-use sov_state::DefaultStorageSpec;
 use std::env;
 use std::str::FromStr;
 use stf_starter::Runtime;
@@ -27,20 +24,9 @@ use tracing_subscriber::{fmt, EnvFilter};
 
 const TOKEN_NAME: &str = "sov-token";
 const TOKEN_DECIMALS: u8 = 6;
-const MAX_TX_FEE: Amount = Amount::new(100_000_000);
-
-type Hasher = <EvmCryptoSpec as CryptoSpec>::Hasher;
-type NomtStorage = NomtProverStorage<DefaultStorageSpec<Hasher>, <MockDaSpec as DaSpec>::SlotHash>;
-type TestSpec = ConfigurableSpec<
-    MockDaSpec,
-    MockZkvm,
-    MockZkvm,
-    EthereumAddress,
-    Native,
-    EvmCryptoSpec,
-    NomtStorage,
->;
 
+/// Runs against the DA backend of the enabled feature. With `mock_da_external` the rollup talks
+/// to an in-process mock-da server over RPC.
 #[tokio::test(flavor = "multi_thread")]
 async fn bank_tx_tests() -> Result<(), anyhow::Error> {
     tracing_subscriber::registry()
@@ -49,34 +35,12 @@ async fn bank_tx_tests() -> Result<(), anyhow::Error> {
             &env::var("RUST_LOG").unwrap_or_else(|_| default_rust_log_value().to_string()),
         )?)
         .init();
-    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
-
-    let rollup_task = tokio::spawn(async {
-        start_rollup(
-            rest_port_tx,
-            std::path::PathBuf::from_str("../../configs/mock/genesis.json")
-                .expect("Failed to build genesis config path"),
-            None,
-            MockDaConfig {
-                connection_string: MockDaConfig::sqlite_in_memory(),
-                sender_address: MockAddress::new([0; 32]),
-                finalization_blocks: 3,
-                block_producing: BlockProducingConfig::Periodic { block_time_ms: 300 },
-                da_layer: None,
-                randomization: None,
-            },
-        )
-        .await;
-    });
-    let rest_port = rest_port_rx.await?.port();
-    let client = NodeClient::new_at_localhost(rest_port).await?;
 
-    // If the rollup throws an error, return it and stop trying to send the transaction
-    tokio::select! {
-        err = rollup_task => err?,
-        res = send_test_create_token_tx(&client) => res?,
-    }
-    Ok(())
+    run_with_rollup(|rest_port| async move {
+        let client = NodeClient::new_at_localhost(rest_port).await?;
+        send_test_create_token_tx(&client).await
+    })
+    .await
 }
 
 async fn send_test_create_token_tx(client: &NodeClient) -> Result<(), anyhow::Error> {
@@ -144,3 +108,53 @@ async fn send_test_create_token_tx(client: &NodeClient) -> Result<(), anyhow::Er
 
     Ok(())
 }
+
+/// A create token tx whose max fee or gas limit can't cover its execution is turned down by the
+/// sequencer, while the same tx with ample gas goes through.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_under_gassed_txs_are_rejected() -> Result<(), anyhow::Error> {
+    run_with_rollup(submit_under_gassed_txs).await
+}
+
+/// Submits `tx` to `POST /sequencer/txs`, returning the response status and body.
+async fn submit_tx(
+    rest_port: u16,
+    tx: &Transaction<Runtime<TestSpec>, TestSpec>,
+) -> Result<(StatusCode, String), anyhow::Error> {
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{rest_port}/sequencer/txs"))
+        .json(&serde_json::json!({ "body": BASE64_STANDARD.encode(borsh::to_vec(tx)?) }))
+        .send()
+        .await?;
+    Ok((response.status(), response.text().await?))
+}
+
+async fn submit_under_gassed_txs(rest_port: u16) -> Result<(), anyhow::Error> {
+    let token_name = "under-gassed-token";
+    let tight_gas_limit = <TestSpec as Spec>::Gas::from_slice(&[1, 1]);
+
+    for (description, max_fee, gas_limit) in [
+        ("a max fee of 1", Amount::new(1), None),
+        ("a gas limit of 1", MAX_TX_FEE, Some(tight_gas_limit)),
+    ] {
+        let (tx, _, _) = create_token_tx_with_gas(token_name, max_fee, gas_limit);
+        let (status, body) = submit_tx(rest_port, &tx).await?;
+        assert!(
+            status.is_client_error(),
+            "The tx with {description} should be rejected as invalid, got {status}: {body}"
+        );
+        let body = body.to_lowercase();
+        assert!(
+            body.contains("gas") || body.contains("fee"),
+            "The tx with {description} should be rejected for its gas, got {status}: {body}"
+        );
+    }
+
+    let (tx, _, _) = create_token_tx(token_name);
+    let (status, body) = submit_tx(rest_port, &tx).await?;
+    assert!(
+        status.is_success(),
+        "The tx with ample gas should be accepted, got {status}: {body}"
+    );
+    Ok(())
+}
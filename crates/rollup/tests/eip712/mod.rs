@@ -0,0 +1,153 @@
+use super::test_helpers::{
+    create_token_call, read_private_keys, run_with_rollup, TestSpec, MAX_TX_FEE,
+};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use reqwest::StatusCode;
+use rollup_starter::rollup::{
+    Eip712TxBatchItem, Eip712TxBatchResponse, DEFAULT_MAX_EIP712_PAYLOAD_BYTES,
+    MAX_EIP712_BATCH_TXS,
+};
+use sov_eip712_auth::SchemaProvider;
+use sov_modules_api::capabilities::UniquenessData;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{CryptoSpec, PrivateKey, Spec};
+use sov_universal_wallet::schema::{RollupRoots, Schema};
+use stf_starter::Runtime;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_oversized_eip712_tx_is_rejected() -> Result<(), anyhow::Error> {
+    run_with_rollup(submit_oversized_tx).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_eip712_tx_batch_reports_each_tx() -> Result<(), anyhow::Error> {
+    run_with_rollup(submit_batches).await
+}
+
+/// A base64 body that decodes to one chunk past the default payload limit. Every 3 zero bytes
+/// encode to "AAAA" in base64.
+fn oversized_body() -> String {
+    let payload_bytes = DEFAULT_MAX_EIP712_PAYLOAD_BYTES / 3 * 3 + 3;
+    "A".repeat(payload_bytes / 3 * 4)
+}
+
+/// A body within the payload limit that the sequencer can't decode as a transaction.
+fn undecodable_body() -> String {
+    "AAAA".to_string()
+}
+
+/// A creation of the `token_name` token signed by the genesis tx signer over its EIP712 signing
+/// hash, the way a browser wallet signs it.
+fn eip712_signed_body(token_name: &str) -> String {
+    let (msg, _, _) = create_token_call(token_name);
+    let chain_id = config_value!("CHAIN_ID");
+    let utx = UnsignedTransaction::<Runtime<TestSpec>, TestSpec>::new(
+        msg.clone(),
+        chain_id,
+        PriorityFeeBips::ZERO,
+        MAX_TX_FEE,
+        UniquenessData::Generation(0),
+        None,
+    );
+
+    let schema: Schema =
+        borsh::from_slice(<Runtime<TestSpec> as SchemaProvider>::SCHEMA_BORSH).unwrap();
+    let utx_index = schema
+        .rollup_expected_index(RollupRoots::UnsignedTransaction)
+        .unwrap();
+    let signing_hash = schema
+        .eip712_signing_hash(utx_index, &borsh::to_vec(&utx).unwrap())
+        .unwrap();
+
+    let private_key = read_private_keys::<TestSpec>("tx_signer_private_key.json").private_key;
+    let signer: PrivateKeySigner = serde_json::to_value(&private_key)
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let signature = signer.sign_hash_sync(&signing_hash.into()).unwrap();
+    let signature = <<TestSpec as Spec>::CryptoSpec as CryptoSpec>::Signature::try_from(
+        &signature.as_bytes()[..64],
+    )
+    .unwrap();
+
+    let tx = Transaction::<Runtime<TestSpec>, TestSpec>::new_with_details_v0(
+        private_key.pub_key(),
+        msg,
+        signature,
+        chain_id,
+        PriorityFeeBips::ZERO,
+        MAX_TX_FEE,
+        UniquenessData::Generation(0),
+        None,
+    );
+    BASE64_STANDARD.encode(borsh::to_vec(&tx).unwrap())
+}
+
+async fn submit_oversized_tx(rest_port: u16) -> Result<(), anyhow::Error> {
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{rest_port}/sequencer/eip712_tx"))
+        .json(&serde_json::json!({ "body": oversized_body() }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A request body too long for any transaction within the limit is turned down before it is
+    // parsed, so it is never reported as malformed JSON
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{rest_port}/sequencer/eip712_tx"))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body("x".repeat(DEFAULT_MAX_EIP712_PAYLOAD_BYTES * 4))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    Ok(())
+}
+
+async fn submit_batches(rest_port: u16) -> Result<(), anyhow::Error> {
+    let client = reqwest::Client::new();
+    let url = format!("http://127.0.0.1:{rest_port}/sequencer/eip712_tx_batch");
+
+    // Each transaction gets its own result, in request order, and a rejected transaction doesn't
+    // stop the ones after it from being submitted
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!([
+            { "body": undecodable_body() },
+            { "body": eip712_signed_body("Eip712BatchToken") },
+            { "body": oversized_body() },
+        ]))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+    let batch: Eip712TxBatchResponse<serde_json::Value> = response.json().await?;
+    match batch.results.as_slice() {
+        [Eip712TxBatchItem::Failed { status: first, .. }, Eip712TxBatchItem::Submitted(_), Eip712TxBatchItem::Failed { status: last, .. }] =>
+        {
+            assert!((400..500).contains(first) && *first != 413, "{first}");
+            assert_eq!(*last, 413);
+        }
+        results => panic!("expected [Failed, Submitted, Failed], got {results:?}"),
+    }
+
+    // With no rejected transaction the batch answers 200
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!([]))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let batch: Eip712TxBatchResponse<serde_json::Value> = response.json().await?;
+    assert!(batch.results.is_empty());
+
+    // A batch over the limit is turned down as a whole
+    let txs = vec![serde_json::json!({ "body": undecodable_body() }); MAX_EIP712_BATCH_TXS + 1];
+    let response = client.post(&url).json(&txs).send().await?;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    Ok(())
+}
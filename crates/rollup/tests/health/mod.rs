@@ -0,0 +1,37 @@
+use super::test_helpers::run_with_rollup;
+use futures::StreamExt;
+use reqwest::StatusCode;
+use rollup_starter::health::HealthResponse;
+use sov_cli::NodeClient;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_health_is_ok_while_processing_slots() -> Result<(), anyhow::Error> {
+    run_with_rollup(check_health).await
+}
+
+async fn check_health(rest_port: u16) -> Result<(), anyhow::Error> {
+    let client = NodeClient::new_at_localhost(rest_port).await?;
+
+    // Wait until the rollup has processed a slot
+    let mut slot_subscription = client.client.subscribe_slots().await?;
+    let slot_number = slot_subscription
+        .next()
+        .await
+        .transpose()?
+        .map(|slot| slot.number)
+        .unwrap_or_default();
+
+    let response = reqwest::get(format!("http://127.0.0.1:{rest_port}/health")).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let health: HealthResponse = response.json().await?;
+    assert!(health.da_connected, "The DA service should be reachable");
+    assert!(
+        health
+            .last_slot
+            .is_some_and(|last_slot| last_slot >= slot_number),
+        "The last slot should be at least {slot_number}, got {:?}",
+        health.last_slot
+    );
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use super::test_helpers::{create_token_tx, run_with_rollup};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use rollup_starter::metrics::{ACCEPTED_TXS_METRIC, REJECTED_TXS_METRIC, SLOT_HEIGHT_METRIC};
+use sov_cli::NodeClient;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_metrics_count_submitted_txs_and_report_slot_height() -> Result<(), anyhow::Error> {
+    run_with_rollup(check_metrics).await
+}
+
+async fn scrape(rest_port: u16) -> Result<String, anyhow::Error> {
+    let response = reqwest::get(format!("http://127.0.0.1:{rest_port}/metrics")).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    Ok(response.text().await?)
+}
+
+/// Returns the value of the sample named `name` in a Prometheus text exposition.
+fn sample(exposition: &str, name: &str) -> Option<f64> {
+    exposition.lines().find_map(|line| {
+        let (metric, value) = line.split_once(' ')?;
+        (metric == name).then(|| value.parse().unwrap())
+    })
+}
+
+async fn check_metrics(rest_port: u16) -> Result<(), anyhow::Error> {
+    let client = NodeClient::new_at_localhost(rest_port).await?;
+
+    // Wait until the rollup has processed a slot
+    let mut slot_subscription = client.client.subscribe_slots().await?;
+    let slot_number = slot_subscription
+        .next()
+        .await
+        .transpose()?
+        .map(|slot| slot.number)
+        .unwrap_or_default();
+
+    // Scraping and reading the ledger don't count as submissions
+    let exposition = scrape(rest_port).await?;
+    assert_eq!(sample(&exposition, ACCEPTED_TXS_METRIC), Some(0.0));
+    assert_eq!(sample(&exposition, REJECTED_TXS_METRIC), Some(0.0));
+
+    client
+        .client
+        .send_txs_to_sequencer(&[create_token_tx("metrics-token").0])
+        .await?;
+
+    // A body that doesn't decode to a transaction is rejected
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{rest_port}/sequencer/txs"))
+        .json(&serde_json::json!({ "body": "AAAA" }))
+        .send()
+        .await?;
+    assert!(response.status().is_client_error(), "{}", response.status());
+
+    let exposition = scrape(rest_port).await?;
+    assert_eq!(sample(&exposition, ACCEPTED_TXS_METRIC), Some(1.0));
+    assert_eq!(sample(&exposition, REJECTED_TXS_METRIC), Some(1.0));
+    let slot_height = sample(&exposition, SLOT_HEIGHT_METRIC).expect("slot height is exported");
+    assert!(
+        slot_height >= slot_number as f64,
+        "The slot height should be at least {slot_number}, got {slot_height}"
+    );
+    Ok(())
+}
@@ -0,0 +1,268 @@
+use super::test_helpers::{create_token_tx, start_rollup_with_shutdown, start_test_da};
+use axum::body::Body;
+use axum::extract::Path;
+use axum::http::{Request, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::StreamExt;
+use rollup_starter::shutdown::{track_submissions, TxDrain};
+use sov_cli::NodeClient;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{oneshot, watch};
+use tokio::time::Instant;
+use tower::ServiceExt;
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+const BATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Stands in for a sequencer that buffers accepted transactions and, while producing, moves its
+/// buffer into the ledger every [`BATCH_INTERVAL`], until its shutdown signal fires.
+struct MockSequencer {
+    buffer: Arc<Mutex<Vec<String>>>,
+    ledger: Arc<Mutex<HashSet<String>>>,
+    producing: Arc<AtomicBool>,
+    next_id: AtomicUsize,
+}
+
+impl MockSequencer {
+    fn start(drain: &Arc<TxDrain>, shutdown: watch::Receiver<()>, producing: bool) -> Arc<Self> {
+        let sequencer = Arc::new(Self {
+            buffer: Arc::default(),
+            ledger: Arc::default(),
+            producing: Arc::new(AtomicBool::new(producing)),
+            next_id: AtomicUsize::new(0),
+        });
+
+        let (slot_sender, slot_receiver) = watch::channel(0u64);
+        drain
+            .clone()
+            .track_inclusion(slot_receiver, sequencer.ledger_router());
+
+        let mut shutdown = drain.shutdown_signal(shutdown);
+        let producer = sequencer.clone();
+        tokio::spawn(async move {
+            let mut slot_number = 0;
+            loop {
+                tokio::select! {
+                    _ = shutdown.changed() => break,
+                    _ = tokio::time::sleep(BATCH_INTERVAL) => {}
+                }
+                if producer.producing.load(Ordering::SeqCst) {
+                    let batch = std::mem::take(&mut *producer.buffer.lock().unwrap());
+                    producer.ledger.lock().unwrap().extend(batch);
+                    slot_number += 1;
+                    let _ = slot_sender.send(slot_number);
+                }
+            }
+        });
+
+        sequencer
+    }
+
+    /// The `POST /sequencer/txs` route of the sequencer, behind the drain middleware the same way
+    /// `StarterRollup::create_endpoints` wires it.
+    fn router(self: &Arc<Self>, drain: Arc<TxDrain>) -> Router {
+        let sequencer = self.clone();
+        Router::new()
+            .route(
+                "/sequencer/txs",
+                post(move |_body: String| async move {
+                    let id = format!(
+                        "0x{:064x}",
+                        sequencer.next_id.fetch_add(1, Ordering::SeqCst)
+                    );
+                    sequencer.buffer.lock().unwrap().push(id.clone());
+                    Json(serde_json::json!({ "id": id, "status": "submitted" }))
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                drain,
+                track_submissions,
+            ))
+    }
+
+    /// `GET /ledger/txs/{tx_hash}`, finding the transactions moved into the ledger.
+    fn ledger_router(&self) -> Router {
+        let ledger = self.ledger.clone();
+        Router::new().route(
+            "/ledger/txs/:tx_hash",
+            get(move |Path(tx_hash): Path<String>| async move {
+                if ledger.lock().unwrap().contains(&tx_hash) {
+                    StatusCode::OK
+                } else {
+                    StatusCode::NOT_FOUND
+                }
+            }),
+        )
+    }
+
+    fn ledger_size(&self) -> usize {
+        self.ledger.lock().unwrap().len()
+    }
+}
+
+async fn submit_tx(router: &Router) -> StatusCode {
+    let request = Request::post("/sequencer/txs")
+        .body(Body::from("tx"))
+        .unwrap();
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shutdown_waits_for_accepted_txs() {
+    let drain = Arc::new(TxDrain::new(DRAIN_TIMEOUT));
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    let sequencer = MockSequencer::start(&drain, shutdown_receiver.clone(), false);
+    let router = sequencer.router(drain.clone());
+    let mut drained = drain.shutdown_signal(shutdown_receiver);
+
+    assert_eq!(submit_tx(&router).await, StatusCode::OK);
+    assert_eq!(drain.pending_txs(), 1);
+
+    shutdown_sender.send(()).unwrap();
+    tokio::time::sleep(BATCH_INTERVAL * 4).await;
+    assert!(drain.is_draining());
+    assert_eq!(submit_tx(&router).await, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(
+        !drained.has_changed().unwrap(),
+        "Shutdown should wait for the accepted tx"
+    );
+
+    let resumed = Instant::now();
+    sequencer.producing.store(true, Ordering::SeqCst);
+    tokio::time::timeout(DRAIN_TIMEOUT, drained.changed())
+        .await
+        .expect("Shutdown should fire once the accepted tx is in the ledger")
+        .unwrap();
+    assert!(resumed.elapsed() < DRAIN_TIMEOUT);
+    assert_eq!(
+        sequencer.ledger_size(),
+        1,
+        "Only the tx accepted before shutdown should be sequenced"
+    );
+    assert_eq!(drain.pending_txs(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_idle_shutdown_is_immediate() {
+    let drain = Arc::new(TxDrain::new(DRAIN_TIMEOUT));
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    let _sequencer = MockSequencer::start(&drain, shutdown_receiver.clone(), true);
+    let mut drained = drain.shutdown_signal(shutdown_receiver);
+
+    shutdown_sender.send(()).unwrap();
+    tokio::time::timeout(DRAIN_TIMEOUT / 4, drained.changed())
+        .await
+        .expect("Shutdown shouldn't wait when nothing was accepted")
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_drain_timeout_caps_shutdown() {
+    let drain = Arc::new(TxDrain::new(DRAIN_TIMEOUT));
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    let sequencer = MockSequencer::start(&drain, shutdown_receiver.clone(), false);
+    let router = sequencer.router(drain.clone());
+    let mut drained = drain.shutdown_signal(shutdown_receiver);
+
+    assert_eq!(submit_tx(&router).await, StatusCode::OK);
+    let shutdown_requested = Instant::now();
+    shutdown_sender.send(()).unwrap();
+
+    tokio::time::timeout(DRAIN_TIMEOUT * 2, drained.changed())
+        .await
+        .expect("Shutdown should fire once the drain timeout is reached")
+        .unwrap();
+    assert!(shutdown_requested.elapsed() >= DRAIN_TIMEOUT);
+    assert_eq!(drain.pending_txs(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_txs_are_accepted_until_shutdown() {
+    let drain = Arc::new(TxDrain::new(DRAIN_TIMEOUT));
+    let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+    let sequencer = MockSequencer::start(&drain, shutdown_receiver, false);
+    let router = sequencer.router(drain.clone());
+
+    for _ in 0..3 {
+        assert_eq!(submit_tx(&router).await, StatusCode::OK);
+    }
+    assert!(!drain.is_draining());
+    assert_eq!(sequencer.buffer.lock().unwrap().len(), 3);
+    assert_eq!(drain.pending_txs(), 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rollup_sequences_txs_submitted_before_shutdown() -> Result<(), anyhow::Error> {
+    const ROLLUP_DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+    let (rest_port_tx, rest_port_rx) = oneshot::channel();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let drain = Arc::new(TxDrain::new(ROLLUP_DRAIN_TIMEOUT));
+
+    let test_da = start_test_da().await;
+    let genesis_path = test_da.genesis_path.clone();
+    let da_config = test_da.config.clone();
+    let rollup_drain = drain.clone();
+    let mut rollup_task = tokio::spawn(async {
+        start_rollup_with_shutdown(
+            rest_port_tx,
+            genesis_path,
+            da_config,
+            rollup_drain,
+            shutdown_rx,
+        )
+        .await;
+    });
+    let rest_port = rest_port_rx.await?.port();
+
+    let shutdown_requested = tokio::select! {
+        err = &mut rollup_task => {
+            err?;
+            anyhow::bail!("The rollup stopped before shutdown was requested");
+        }
+        res = submit_and_shut_down(rest_port, &drain, shutdown_tx) => res?,
+    };
+
+    // The rollup shuts down once the tx accepted before shutdown got processed
+    tokio::time::timeout(ROLLUP_DRAIN_TIMEOUT * 2, rollup_task).await??;
+    assert_eq!(
+        drain.pending_txs(),
+        0,
+        "The tx submitted before shutdown wasn't processed"
+    );
+    assert!(
+        shutdown_requested.elapsed() < ROLLUP_DRAIN_TIMEOUT,
+        "The rollup waited for the drain timeout instead of the tx"
+    );
+    Ok(())
+}
+
+async fn submit_and_shut_down(
+    rest_port: u16,
+    drain: &TxDrain,
+    shutdown_tx: oneshot::Sender<()>,
+) -> Result<Instant, anyhow::Error> {
+    let client = NodeClient::new_at_localhost(rest_port).await?;
+
+    // Wait until the rollup has processed a slot
+    let mut slot_subscription = client.client.subscribe_slots().await?;
+    slot_subscription.next().await.transpose()?;
+
+    let (tx, _, _) = create_token_tx("drained-token");
+    client.client.send_txs_to_sequencer(&[tx]).await?;
+    shutdown_tx
+        .send(())
+        .map_err(|_| anyhow::anyhow!("The rollup stopped before shutdown was requested"))?;
+    let shutdown_requested = Instant::now();
+
+    // How long the node keeps draining depends on when the tx lands, so the rejection of new txs
+    // is only checked against the mock sequencer above
+    while !drain.is_draining() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    Ok(shutdown_requested)
+}
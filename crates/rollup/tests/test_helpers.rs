@@ -1,32 +1,171 @@
 use sov_cli::wallet_state::PrivateKeyAndAddress;
 use std::net::SocketAddr;
 use std::num::{NonZero, NonZeroU64, NonZeroUsize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use rollup_starter::da::DaService;
 use rollup_starter::rollup::StarterRollup;
+use rollup_starter::shutdown::TxDrain;
 use rollup_starter::zkvm::InnerZkvm;
-use sov_address::EthereumAddress;
+use sov_address::{EthereumAddress, EvmCryptoSpec};
+use sov_bank::TokenId;
 use sov_db::config::RollupDbConfig;
-use sov_mock_da::MockDaConfig;
-use sov_modules_api::Spec;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_mock_zkvm::MockZkvm;
+use sov_modules_api::capabilities::UniquenessData;
+use sov_modules_api::configurable_spec::ConfigurableSpec;
+use sov_modules_api::execution_mode::Native;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{Amount, Spec};
 use sov_modules_rollup_blueprint::FullNodeBlueprint;
+use sov_rollup_interface::common::SafeVec;
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::node::da::DaService as DaServiceTrait;
+use sov_rollup_interface::zk::CryptoSpec;
 use sov_sequencer::preferred::PreferredSequencerConfig;
 use sov_sequencer::preferred::RecoveryStrategy;
 use sov_sequencer::SeqConfigExtension;
 use sov_sequencer::{SequencerConfig, SequencerKindConfig};
+use sov_state::nomt::prover_storage::NomtProverStorage;
+use sov_state::DefaultStorageSpec;
 use sov_stf_runner::processes::RollupProverConfig;
 use sov_stf_runner::{HttpServerConfig, MonitoringConfig, ProofManagerConfig};
 use sov_stf_runner::{RollupConfig, RunnerConfig};
+use std::future::Future;
 use std::str::FromStr;
-use tokio::sync::oneshot;
+use std::sync::Arc;
+use stf_starter::{Runtime, RuntimeCall};
+use tokio::sync::{oneshot, watch};
 
 const PROVER_ADDRESS: &str = "0x4fD62a0D0c35e1Fdcd97231A4586E65e7Eb454a5";
 
+/// DA backend for integration tests, matching the enabled DA feature.
+pub struct TestDa {
+    pub config: <DaService as DaServiceTrait>::Config,
+    pub genesis_path: PathBuf,
+    /// Keeps the in-process mock-da server of `mock_da_external` running.
+    _shutdown_sender: watch::Sender<()>,
+}
+
+fn test_mock_da_config() -> MockDaConfig {
+    MockDaConfig {
+        connection_string: MockDaConfig::sqlite_in_memory(),
+        sender_address: MockAddress::new([0; 32]),
+        finalization_blocks: 3,
+        block_producing: BlockProducingConfig::Periodic { block_time_ms: 300 },
+        da_layer: None,
+        randomization: None,
+    }
+}
+
+/// Uses an in-memory mock DA inside the rollup.
+#[cfg(feature = "mock_da")]
+pub async fn start_test_da() -> TestDa {
+    let (shutdown_sender, _) = watch::channel(());
+
+    TestDa {
+        config: test_mock_da_config(),
+        genesis_path: PathBuf::from("../../configs/mock/genesis.json"),
+        _shutdown_sender: shutdown_sender,
+    }
+}
+
+/// Starts the mock-da server in-process and points the rollup at it over RPC.
+#[cfg(feature = "mock_da_external")]
+pub async fn start_test_da() -> TestDa {
+    use sov_mock_da::storable::rpc::start_server;
+    use sov_mock_da::storable::StorableMockDaService;
+
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    let da_service =
+        StorableMockDaService::from_config(test_mock_da_config(), shutdown_receiver).await;
+    let addr = start_server(da_service, "127.0.0.1", 0)
+        .await
+        .expect("Failed to start the mock-da server");
+
+    let config = serde_json::from_value(serde_json::json!({ "url": format!("http://{}", addr) }))
+        .expect("Mock-da client config is not valid");
+
+    TestDa {
+        config,
+        genesis_path: PathBuf::from("../../configs/mock_external/genesis.json"),
+        _shutdown_sender: shutdown_sender,
+    }
+}
+
+/// Starts a rollup on the DA backend of the enabled feature and runs `test` against its REST
+/// port. Fails if the rollup stops before `test` finished.
+pub async fn run_with_rollup<F, Fut>(test: F) -> Result<(), anyhow::Error>
+where
+    F: FnOnce(u16) -> Fut,
+    Fut: Future<Output = Result<(), anyhow::Error>>,
+{
+    let (rest_port_tx, rest_port_rx) = oneshot::channel();
+
+    let test_da = start_test_da().await;
+    let rollup_task = tokio::spawn(start_rollup(
+        rest_port_tx,
+        test_da.genesis_path.clone(),
+        None,
+        test_da.config.clone(),
+    ));
+    let rest_port = rest_port_rx.await?.port();
+
+    // If the rollup throws an error, return it and stop running the test
+    tokio::select! {
+        res = rollup_task => {
+            res?;
+            anyhow::bail!("The rollup stopped before the test finished");
+        }
+        res = test(rest_port) => res,
+    }
+}
+
 pub async fn start_rollup(
     rest_reporting_channel: oneshot::Sender<SocketAddr>,
     genesis_input: std::path::PathBuf,
     rollup_prover_config: Option<RollupProverConfig<InnerZkvm>>,
-    da_config: MockDaConfig,
+    da_config: <DaService as DaServiceTrait>::Config,
+) {
+    run_rollup(
+        StarterRollup::default(),
+        rest_reporting_channel,
+        genesis_input,
+        rollup_prover_config,
+        da_config,
+        None,
+    )
+    .await;
+}
+
+/// Same as [`start_rollup`], but requests the rollup to shut down once `shutdown` fires, and
+/// returns once it did. Accepted transactions are drained on shutdown by `drain`.
+pub async fn start_rollup_with_shutdown(
+    rest_reporting_channel: oneshot::Sender<SocketAddr>,
+    genesis_input: std::path::PathBuf,
+    da_config: <DaService as DaServiceTrait>::Config,
+    drain: Arc<TxDrain>,
+    shutdown: oneshot::Receiver<()>,
+) {
+    run_rollup(
+        StarterRollup::default().with_tx_drain(drain),
+        rest_reporting_channel,
+        genesis_input,
+        None,
+        da_config,
+        Some(shutdown),
+    )
+    .await;
+}
+
+async fn run_rollup(
+    rollup: StarterRollup<Native>,
+    rest_reporting_channel: oneshot::Sender<SocketAddr>,
+    genesis_input: std::path::PathBuf,
+    rollup_prover_config: Option<RollupProverConfig<InnerZkvm>>,
+    da_config: <DaService as DaServiceTrait>::Config,
+    shutdown: Option<oneshot::Receiver<()>>,
 ) {
     let temp_dir = tempfile::tempdir().unwrap();
     let temp_path = temp_dir.path();
@@ -75,8 +214,6 @@ pub async fn start_rollup(
         monitoring: MonitoringConfig::standard(),
     };
 
-    let rollup = StarterRollup::default();
-
     let rollup = rollup
         .create_new_rollup(
             &genesis_input,
@@ -88,7 +225,8 @@ pub async fn start_rollup(
         .await
         .unwrap();
 
-    // Ensure there is a non-zero finalized block
+    // Ensure there is a non-zero finalized block. The external mock-da server produces its own.
+    #[cfg(feature = "mock_da")]
     rollup
         .runner
         .da_service()
@@ -96,6 +234,15 @@ pub async fn start_rollup(
         .await
         .unwrap();
 
+    if let Some(shutdown) = shutdown {
+        let shutdown_sender = rollup.shutdown_sender.clone();
+        tokio::spawn(async move {
+            if shutdown.await.is_ok() {
+                let _ = shutdown_sender.send(());
+            }
+        });
+    }
+
     rollup
         .run_and_report_addr(Some(rest_reporting_channel))
         .await
@@ -125,3 +272,80 @@ pub fn read_private_keys<S: Spec>(suffix: &str) -> PrivateKeyAndAddress<S> {
 
     key_and_address
 }
+
+type Hasher = <EvmCryptoSpec as CryptoSpec>::Hasher;
+type NomtStorage = NomtProverStorage<DefaultStorageSpec<Hasher>, <MockDaSpec as DaSpec>::SlotHash>;
+/// Spec of the rollup under test, for building its transactions.
+pub type TestSpec = ConfigurableSpec<
+    MockDaSpec,
+    MockZkvm,
+    MockZkvm,
+    EthereumAddress,
+    Native,
+    EvmCryptoSpec,
+    NomtStorage,
+>;
+
+/// Initial balance minted by [`create_token_tx`].
+pub const CREATED_TOKEN_BALANCE: Amount = Amount::new(1000);
+
+/// Max fee of the transactions built by [`create_token_tx`].
+pub const MAX_TX_FEE: Amount = Amount::new(100_000_000);
+
+/// A creation of the `token_name` token signed by the genesis tx signer, which the sequencer
+/// accepts. Returns the transaction along with the id of the token and the address it's minted to.
+pub fn create_token_tx(
+    token_name: &str,
+) -> (
+    Transaction<Runtime<TestSpec>, TestSpec>,
+    TokenId,
+    <TestSpec as Spec>::Address,
+) {
+    create_token_tx_with_gas(token_name, MAX_TX_FEE, None)
+}
+
+/// The call of [`create_token_tx`], along with the id of the token and the address it's minted to.
+pub fn create_token_call(
+    token_name: &str,
+) -> (RuntimeCall<TestSpec>, TokenId, <TestSpec as Spec>::Address) {
+    let user_address: <TestSpec as Spec>::Address =
+        read_private_keys::<TestSpec>("tx_signer_private_key.json").address;
+    let token_id = sov_bank::get_token_id::<TestSpec>(token_name, None, &user_address);
+
+    let msg = RuntimeCall::<TestSpec>::Bank(sov_bank::CallMessage::<TestSpec>::CreateToken {
+        token_name: token_name.try_into().unwrap(),
+        token_decimals: None,
+        initial_balance: CREATED_TOKEN_BALANCE,
+        mint_to_address: user_address,
+        admins: SafeVec::default(),
+        supply_cap: None,
+    });
+    (msg, token_id, user_address)
+}
+
+/// Same as [`create_token_tx`], paying at most `max_fee` and using at most `gas_limit`.
+pub fn create_token_tx_with_gas(
+    token_name: &str,
+    max_fee: Amount,
+    gas_limit: Option<<TestSpec as Spec>::Gas>,
+) -> (
+    Transaction<Runtime<TestSpec>, TestSpec>,
+    TokenId,
+    <TestSpec as Spec>::Address,
+) {
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+    let (msg, token_id, user_address) = create_token_call(token_name);
+    let tx = Transaction::<Runtime<TestSpec>, TestSpec>::new_signed_tx(
+        &key_and_address.private_key,
+        &<Runtime<TestSpec> as sov_modules_stf_blueprint::Runtime<TestSpec>>::CHAIN_HASH,
+        UnsignedTransaction::new(
+            msg,
+            config_value!("CHAIN_ID"),
+            PriorityFeeBips::ZERO,
+            max_fee,
+            UniquenessData::Generation(0),
+            gas_limit,
+        ),
+    );
+    (tx, token_id, user_address)
+}
@@ -9,9 +9,18 @@ use sov_modules_api::runtime::capabilities::AuthenticationError;
 use sov_modules_api::{
     DispatchCall, FullyBakedTx, GetGasPrice, ProvableStateReader, RawTx, Runtime, Spec,
 };
+use sov_rollup_interface::zk::{ZkVerifier, Zkvm};
 use sov_state::User;
 use std::marker::PhantomData;
 
+/// Crypto spec of [`EvmAndEip712AuthenticatorInput::Ed25519`] transactions: ed25519 signatures
+/// over a transaction encoded the same way as a standard sov transaction.
+///
+/// This is the ed25519 spec of the rollup's inner zkVM, so signatures are verified with the
+/// implementation the guest accelerates.
+pub type Ed25519CryptoSpec<S> =
+    <<<S as Spec>::InnerZkvm as Zkvm>::Verifier as ZkVerifier>::CryptoSpec;
+
 /// See [`TransactionAuthenticator::Input`].
 #[derive(std::fmt::Debug, Clone, BorshDeserialize, BorshSerialize)]
 pub enum EvmAndEip712AuthenticatorInput<T = RawTx, U = RawTx> {
@@ -24,11 +33,32 @@ pub enum EvmAndEip712AuthenticatorInput<T = RawTx, U = RawTx> {
     /// Authenticate using the standard `sov-module` authenticator, which uses the default
     /// signature scheme and hashing algorithm defined in the rollup's [`Spec`].
     Standard(U),
+    /// Authenticate using a raw ed25519 signature, which expects a transaction encoded the same way
+    /// as a standard sov transaction but signed with [`Ed25519CryptoSpec`] instead of the rollup's
+    /// default signature scheme.
+    Ed25519(U),
 }
 
 /// EVM-compatible transaction authenticator. See [`TransactionAuthenticator`].
 pub struct EvmAndEip712Authenticator<S, Rt, SP>(PhantomData<(S, Rt, SP)>);
 
+impl<S: Spec, Rt, SP> EvmAndEip712Authenticator<S, Rt, SP> {
+    /// Rejects the `Evm` transaction `tx` with a [`AuthenticationError::FatalError`] when the
+    /// `disable_evm_auth` feature is enabled, so the STF only accepts sov-encoded transactions.
+    /// Does nothing otherwise.
+    fn ensure_evm_auth_enabled(tx: &FullyBakedTx) -> Result<(), AuthenticationError> {
+        if cfg!(feature = "disable_evm_auth") {
+            return Err(AuthenticationError::FatalError(
+                FatalError::Other(
+                    "EVM-authenticated transactions are disabled on this rollup".to_string(),
+                ),
+                capabilities::calculate_hash::<S>(&tx.data),
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl<S, Rt, SP> TransactionAuthenticator<S> for EvmAndEip712Authenticator<S, Rt, SP>
 where
     S: Spec<CryptoSpec: Secp256k1CryptoSpec>,
@@ -54,9 +84,10 @@ where
         })?;
 
         match input {
-            EvmAndEip712AuthenticatorInput::Evm(tx) => {
+            EvmAndEip712AuthenticatorInput::Evm(evm_tx) => {
+                Self::ensure_evm_auth_enabled(tx)?;
                 let (tx_and_raw_hash, auth_data, runtime_call) =
-                    sov_evm::authenticate::<_, _>(&tx.data, state)?;
+                    sov_evm::authenticate::<_, _>(&evm_tx.data, state)?;
 
                 Ok((
                     tx_and_raw_hash,
@@ -88,6 +119,21 @@ where
                     EvmAndEip712AuthenticatorInput::Standard(runtime_call),
                 ))
             }
+            EvmAndEip712AuthenticatorInput::Ed25519(tx) => {
+                let (tx_and_raw_hash, auth_data, runtime_call) =
+                    sov_modules_api::capabilities::authenticate_with_cryptospec::<
+                        _,
+                        S,
+                        Rt,
+                        Ed25519CryptoSpec<S>,
+                    >(&tx.data, &Rt::CHAIN_HASH, state)?;
+
+                Ok((
+                    tx_and_raw_hash,
+                    auth_data,
+                    EvmAndEip712AuthenticatorInput::Ed25519(runtime_call),
+                ))
+            }
         }
     }
 
@@ -103,7 +149,8 @@ where
                 Ok(sov_rollup_interface::TxHash::new(**tx.hash()))
             }
             EvmAndEip712AuthenticatorInput::Eip712(tx)
-            | EvmAndEip712AuthenticatorInput::Standard(tx) => {
+            | EvmAndEip712AuthenticatorInput::Standard(tx)
+            | EvmAndEip712AuthenticatorInput::Ed25519(tx) => {
                 Ok(capabilities::calculate_hash::<S>(&tx.data))
             }
         }
@@ -137,6 +184,14 @@ where
                 >(&raw_tx.data)?;
                 Ok(EvmAndEip712AuthenticatorInput::Eip712(call))
             }
+            EvmAndEip712AuthenticatorInput::Ed25519(raw_tx) => {
+                let call = sov_modules_api::capabilities::decode_sov_tx_with_cryptospec::<
+                    S,
+                    Rt,
+                    Ed25519CryptoSpec<S>,
+                >(&raw_tx.data)?;
+                Ok(EvmAndEip712AuthenticatorInput::Ed25519(call))
+            }
         }
     }
 
@@ -147,10 +202,14 @@ where
         capabilities::AuthenticationOutput<S, Self::Decodable>,
         capabilities::UnregisteredAuthenticationError,
     > {
-        let Self::Input::Standard(input) = borsh::from_slice(&batch.tx.data)
+        let input = match borsh::from_slice(&batch.tx.data)
             .map_err(|_| UnregisteredAuthenticationError::InvalidAuthenticationDiscriminant)?
-        else {
-            return Err(UnregisteredAuthenticationError::InvalidAuthenticationDiscriminant);
+        {
+            Self::Input::Standard(input) => input,
+            // Unregistered sequencers may only submit standard sov transactions.
+            Self::Input::Evm(_) | Self::Input::Eip712(_) | Self::Input::Ed25519(_) => {
+                return Err(UnregisteredAuthenticationError::InvalidAuthenticationDiscriminant);
+            }
         };
 
         let (tx_and_raw_hash, auth_data, runtime_call) =
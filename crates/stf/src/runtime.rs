@@ -99,6 +99,7 @@ where
             EvmAndEip712AuthenticatorInput::Evm(call) => Self::Decodable::Evm(call),
             EvmAndEip712AuthenticatorInput::Eip712(call) => call,
             EvmAndEip712AuthenticatorInput::Standard(call) => call,
+            EvmAndEip712AuthenticatorInput::Ed25519(call) => call,
         }
     }
 
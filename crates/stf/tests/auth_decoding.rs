@@ -0,0 +1,246 @@
+#![cfg(feature = "native")]
+
+use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, TxKind, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use sov_address::{EthereumAddress, EvmCryptoSpec};
+use sov_mock_da::MockDaSpec;
+use sov_mock_zkvm::MockZkvm;
+use sov_modules_api::capabilities::{
+    AuthenticationError, TransactionAuthenticator, UniquenessData,
+};
+use sov_modules_api::configurable_spec::ConfigurableSpec;
+use sov_modules_api::execution_mode::Native;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{Amount, CryptoSpec, FullyBakedTx, PrivateKey, RawTx, Spec};
+use sov_rollup_interface::da::DaSpec;
+use sov_state::nomt::prover_storage::NomtProverStorage;
+use sov_state::DefaultStorageSpec;
+use sov_test_utils::generate_optimistic_runtime;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use stf_starter::authentication::{Ed25519CryptoSpec, EvmAndEip712AuthenticatorInput};
+use stf_starter::{Runtime, RuntimeCall};
+
+type Hasher = <EvmCryptoSpec as CryptoSpec>::Hasher;
+type NomtStorage = NomtProverStorage<DefaultStorageSpec<Hasher>, <MockDaSpec as DaSpec>::SlotHash>;
+type TestSpec = ConfigurableSpec<
+    MockDaSpec,
+    MockZkvm,
+    MockZkvm,
+    EthereumAddress,
+    Native,
+    EvmCryptoSpec,
+    NomtStorage,
+>;
+/// [`TestSpec`] with ed25519 keys, used to sign the transactions of the `Ed25519` variant.
+type Ed25519TestSpec = ConfigurableSpec<
+    MockDaSpec,
+    MockZkvm,
+    MockZkvm,
+    EthereumAddress,
+    Native,
+    Ed25519CryptoSpec<TestSpec>,
+    NomtStorage,
+>;
+type Auth = <Runtime<TestSpec> as sov_modules_stf_blueprint::Runtime<TestSpec>>::Auth;
+type Decodable = <Auth as TransactionAuthenticator<TestSpec>>::Decodable;
+
+const CHAIN_HASH: [u8; 32] =
+    <Runtime<TestSpec> as sov_modules_stf_blueprint::Runtime<TestSpec>>::CHAIN_HASH;
+
+// Only provides the state `Auth::authenticate` reads; the transactions are never dispatched to it.
+generate_optimistic_runtime!(StateRuntime <=);
+
+fn transfer_call() -> RuntimeCall<TestSpec> {
+    RuntimeCall::Bank(sov_bank::CallMessage::Transfer {
+        coins: sov_bank::Coins {
+            amount: Amount::new(10),
+            token_id: sov_bank::config_gas_token_id(),
+        },
+        to: "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap(),
+    })
+}
+
+fn signed_tx(msg: RuntimeCall<TestSpec>) -> RawTx {
+    let utx = UnsignedTransaction::<Runtime<TestSpec>, TestSpec>::new(
+        msg,
+        config_value!("CHAIN_ID"),
+        PriorityFeeBips::ZERO,
+        Amount::new(100_000_000),
+        UniquenessData::Generation(0),
+        None,
+    );
+    let priv_key: <<TestSpec as Spec>::CryptoSpec as CryptoSpec>::PrivateKey =
+        serde_json::from_str(
+            "\"0d87c12ea7c12024b3f70a26d735874608f17c8bce2b48e6fe87389310191264\"",
+        )
+        .unwrap();
+    let tx: Transaction<Runtime<TestSpec>, TestSpec> =
+        Transaction::new_signed_tx(&priv_key, &CHAIN_HASH, utx);
+    RawTx::new(borsh::to_vec(&tx).unwrap())
+}
+
+/// Re-encodes a call of [`TestSpec`] as a call of [`Ed25519TestSpec`]. Both specs use
+/// `EthereumAddress`, so the call has the same encoding under either.
+fn ed25519_call(msg: &RuntimeCall<TestSpec>) -> RuntimeCall<Ed25519TestSpec> {
+    borsh::from_slice(&borsh::to_vec(msg).unwrap()).unwrap()
+}
+
+/// Runs `Auth::authenticate` on `input` against the genesis state of a test rollup and returns the
+/// authenticated call.
+fn authenticate(input: EvmAndEip712AuthenticatorInput) -> Result<Decodable, AuthenticationError> {
+    let genesis_config = HighLevelOptimisticGenesisConfig::<TestSpec>::generate();
+    let genesis = GenesisConfig::from_minimal_config(genesis_config.into());
+    let mut runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), StateRuntime::default());
+
+    let tx = FullyBakedTx::new(borsh::to_vec(&input).unwrap());
+    runner.query_state(|state| Auth::authenticate(&tx, state).map(|(_, _, call)| call))
+}
+
+/// Asserts `result` is a fatal authentication error and returns its message.
+fn fatal_error_message(result: Result<Decodable, AuthenticationError>) -> String {
+    match result {
+        Err(AuthenticationError::FatalError(err, _)) => err.to_string(),
+        Err(err) => panic!("expected a fatal error, got {err:?}"),
+        Ok(_) => panic!("expected the transaction to be rejected"),
+    }
+}
+
+/// Signs `msg` with an ed25519 key over `chain_hash`, for the `Ed25519` variant.
+fn ed25519_signed_tx(msg: RuntimeCall<Ed25519TestSpec>, chain_hash: &[u8; 32]) -> RawTx {
+    let utx = UnsignedTransaction::<Runtime<Ed25519TestSpec>, Ed25519TestSpec>::new(
+        msg,
+        config_value!("CHAIN_ID"),
+        PriorityFeeBips::ZERO,
+        Amount::new(100_000_000),
+        UniquenessData::Generation(0),
+        None,
+    );
+    let priv_key = <Ed25519CryptoSpec<TestSpec> as CryptoSpec>::PrivateKey::generate();
+    let tx: Transaction<Runtime<Ed25519TestSpec>, Ed25519TestSpec> =
+        Transaction::new_signed_tx(&priv_key, chain_hash, utx);
+    RawTx::new(borsh::to_vec(&tx).unwrap())
+}
+
+fn decode(input: EvmAndEip712AuthenticatorInput) -> Vec<u8> {
+    let tx = FullyBakedTx::new(borsh::to_vec(&input).unwrap());
+    match Auth::decode_serialized_tx(&tx).expect("tx should decode") {
+        EvmAndEip712AuthenticatorInput::Eip712(call)
+        | EvmAndEip712AuthenticatorInput::Standard(call)
+        | EvmAndEip712AuthenticatorInput::Ed25519(call) => borsh::to_vec(&call).unwrap(),
+        EvmAndEip712AuthenticatorInput::Evm(_) => panic!("sov tx decoded as an EVM tx"),
+    }
+}
+
+/// The same transaction bytes submitted through the EIP712 and Standard authenticators must decode
+/// to the same runtime call. Decoding doesn't check signatures, so the EIP712 variant can reuse the
+/// standard-signed transaction: the two encodings only differ in how the signature is produced.
+#[test]
+fn test_eip712_and_standard_decode_to_the_same_call() {
+    let msg = transfer_call();
+    let tx = signed_tx(msg.clone());
+
+    let via_standard = decode(EvmAndEip712AuthenticatorInput::Standard(tx.clone()));
+    let via_eip712 = decode(EvmAndEip712AuthenticatorInput::Eip712(tx));
+
+    assert_eq!(via_standard, via_eip712);
+    assert_eq!(via_standard, borsh::to_vec(&msg).unwrap());
+}
+
+/// An ed25519-signed transaction round-trips through the `Ed25519` variant: it decodes to the
+/// signed runtime call and is identified by the hash of its sov encoding.
+#[test]
+fn test_ed25519_round_trip() {
+    let msg = transfer_call();
+    let tx = ed25519_signed_tx(ed25519_call(&msg), &CHAIN_HASH);
+    let input = EvmAndEip712AuthenticatorInput::Ed25519(tx.clone());
+
+    let baked = FullyBakedTx::new(borsh::to_vec(&input).unwrap());
+    assert_eq!(
+        Auth::compute_tx_hash(&baked).unwrap(),
+        sov_modules_api::capabilities::calculate_hash::<TestSpec>(&tx.data)
+    );
+    assert_eq!(decode(input), borsh::to_vec(&msg).unwrap());
+}
+
+/// A correctly signed ed25519 transaction passes `authenticate` and yields the signed call.
+#[test]
+fn test_ed25519_authenticates() {
+    let msg = transfer_call();
+    let tx = ed25519_signed_tx(ed25519_call(&msg), &CHAIN_HASH);
+
+    match authenticate(EvmAndEip712AuthenticatorInput::Ed25519(tx)).expect("tx should authenticate")
+    {
+        EvmAndEip712AuthenticatorInput::Ed25519(call) => {
+            assert_eq!(borsh::to_vec(&call).unwrap(), borsh::to_vec(&msg).unwrap());
+        }
+        _ => panic!("ed25519 tx authenticated as another variant"),
+    }
+}
+
+/// An ed25519 signature that doesn't cover the runtime's chain hash is rejected by `authenticate`.
+#[test]
+fn test_ed25519_bad_signature_is_rejected() {
+    let tx = ed25519_signed_tx(ed25519_call(&transfer_call()), &[0; 32]);
+
+    let message = fatal_error_message(authenticate(EvmAndEip712AuthenticatorInput::Ed25519(tx)));
+    assert!(message.to_lowercase().contains("signature"), "{message}");
+}
+
+/// An EIP-1559 transaction on the rollup's chain, signed with secp256k1 and encoded as the `Evm`
+/// variant expects it.
+fn signed_evm_input() -> EvmAndEip712AuthenticatorInput {
+    let signer: PrivateKeySigner =
+        "0d87c12ea7c12024b3f70a26d735874608f17c8bce2b48e6fe87389310191264"
+            .parse()
+            .unwrap();
+    let tx = TxEip1559 {
+        chain_id: config_value!("CHAIN_ID"),
+        nonce: 0,
+        gas_limit: 1_000_000,
+        max_fee_per_gas: 10_000_000_000,
+        max_priority_fee_per_gas: 1_000_000_000,
+        to: TxKind::Call(Address::with_last_byte(1)),
+        value: U256::from(10),
+        ..Default::default()
+    };
+    let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+    let envelope = TxEnvelope::from(tx.into_signed(signature));
+    let input = EvmAndEip712AuthenticatorInput::Evm(RawTx::new(envelope.encoded_2718()));
+
+    // The variant keeps deserializing whether or not EVM authentication is enabled.
+    let decoded: EvmAndEip712AuthenticatorInput =
+        borsh::from_slice(&borsh::to_vec(&input).unwrap()).unwrap();
+    assert!(matches!(decoded, EvmAndEip712AuthenticatorInput::Evm(_)));
+    input
+}
+
+/// With `disable_evm_auth`, `authenticate` rejects even correctly signed `Evm` transactions before
+/// the EVM authenticator runs, while sov-encoded transactions still authenticate.
+#[cfg(feature = "disable_evm_auth")]
+#[test]
+fn test_evm_auth_rejected_when_disabled() {
+    let message = fatal_error_message(authenticate(signed_evm_input()));
+    assert!(message.contains("disabled"), "{message}");
+
+    let tx = ed25519_signed_tx(ed25519_call(&transfer_call()), &CHAIN_HASH);
+    authenticate(EvmAndEip712AuthenticatorInput::Ed25519(tx)).expect("tx should authenticate");
+}
+
+/// By default `authenticate` hands `Evm` transactions to the EVM authenticator, which accepts a
+/// correctly signed one.
+#[cfg(not(feature = "disable_evm_auth"))]
+#[test]
+fn test_evm_auth_accepted_by_default() {
+    match authenticate(signed_evm_input()).expect("EVM tx should authenticate") {
+        EvmAndEip712AuthenticatorInput::Evm(_) => {}
+        _ => panic!("EVM tx authenticated as another variant"),
+    }
+}
@@ -0,0 +1,312 @@
+//! Manager-only call handlers.
+
+use sov_modules_api::{EventEmitter, Spec, TxState};
+
+use crate::{
+    CallMessage, Event, Session, SessionRegistry, SessionRegistryError, MAX_SESSION_METADATA_BYTES,
+};
+
+/// Expiry written by `ForceExpire`.
+///
+/// Any non-zero value in the past works; `0` is reserved for deletion.
+const FORCE_EXPIRED_TS: i64 = 1;
+
+/// Handle a call message reserved for the manager.
+///
+/// Rejects the call with [`SessionRegistryError::UnauthorizedManager`] unless
+/// `sender` is the configured manager. Like the signers' session batches,
+/// `SetSessionSignersBatch`, `SetSessionSignerBatch` and `ImportSessions` fail with
+/// [`SessionRegistryError::BatchTooLarge`] past `max_batch_size` entries.
+pub(crate) fn execute<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    msg: CallMessage<S>,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if !module.is_manager(sender, state)? {
+        return Err(SessionRegistryError::UnauthorizedManager.into());
+    }
+
+    match msg {
+        CallMessage::SetSessionSigner {
+            signer,
+            allowed,
+            expires_at,
+        } => {
+            if module.signers_frozen.get(state)?.unwrap_or(false) {
+                return Err(SessionRegistryError::SignersFrozen.into());
+            }
+
+            module.set_session_signer(&signer, allowed, expires_at, state)?;
+
+            module.emit_event(
+                state,
+                Event::SessionSignerSet {
+                    signer,
+                    allowed,
+                    expires_at,
+                },
+            );
+
+            Ok(())
+        }
+        CallMessage::SetSessionSignersBatch { signers } => {
+            if module.signers_frozen.get(state)?.unwrap_or(false) {
+                return Err(SessionRegistryError::SignersFrozen.into());
+            }
+            if signers.is_empty() {
+                return Err(SessionRegistryError::EmptyBatch.into());
+            }
+            check_batch_size(module, signers.len(), state)?;
+
+            set_session_signers(module, signers, state)
+        }
+        CallMessage::SetSessionSignerBatch { signers, allowed } => {
+            if module.signers_frozen.get(state)?.unwrap_or(false) {
+                return Err(SessionRegistryError::SignersFrozen.into());
+            }
+            if signers.len() != allowed.len() {
+                return Err(SessionRegistryError::InvalidBatchLengths.into());
+            }
+            if signers.is_empty() {
+                return Err(SessionRegistryError::EmptyBatch.into());
+            }
+            check_batch_size(module, signers.len(), state)?;
+
+            set_session_signers(module, signers.into_iter().zip(allowed), state)
+        }
+        CallMessage::SetBypass { wallet, bypass } => {
+            let maybe_session = module.sessions.get(&wallet, state)?;
+
+            match maybe_session {
+                None => {
+                    if !bypass {
+                        return Ok(());
+                    }
+
+                    let session = Session {
+                        expiry_ts: 0,
+                        bypass: true,
+                        renewals: 0,
+                        created_ts: module.now_ts(state)?,
+                        metadata: None,
+                    };
+
+                    module.store_session(&wallet, Some(&session), state)?;
+                }
+                Some(mut session) => {
+                    if session.expiry_ts == 0 && !bypass {
+                        module.store_session(&wallet, None, state)?;
+                    } else {
+                        session.bypass = bypass;
+                        module.store_session(&wallet, Some(&session), state)?;
+                    }
+                }
+            }
+
+            module.emit_event(state, Event::BypassSet { wallet, bypass });
+
+            Ok(())
+        }
+        CallMessage::ForceExpire { wallet } => {
+            let mut session = module
+                .sessions
+                .get(&wallet, state)?
+                .ok_or(SessionRegistryError::SessionNotPresent)?;
+
+            session.expiry_ts = FORCE_EXPIRED_TS;
+            session.bypass = false;
+
+            module.store_session(&wallet, Some(&session), state)?;
+
+            module.emit_event(state, Event::SessionForceExpired { wallet });
+
+            Ok(())
+        }
+        CallMessage::SetWalletPolicy { wallet, policy } => {
+            match policy {
+                Some(policy) => module.wallet_policies.set(&wallet, &policy, state)?,
+                None => module.wallet_policies.remove(&wallet, state)?,
+            }
+
+            module.emit_event(state, Event::WalletPolicySet { wallet, policy });
+
+            Ok(())
+        }
+        CallMessage::SetForceEnforce { wallet, enabled } => {
+            if enabled {
+                module.force_enforce.set(&wallet, &true, state)?;
+            } else {
+                module.force_enforce.remove(&wallet, state)?;
+            }
+
+            module.emit_event(state, Event::ForceEnforceSet { wallet, enabled });
+
+            Ok(())
+        }
+        CallMessage::ImportSessions { entries } => {
+            if !module.import_allowed.get(state)?.unwrap_or(false) {
+                return Err(SessionRegistryError::ImportLocked.into());
+            }
+            // an empty import would lock the bootstrap without seeding anything
+            if entries.is_empty() {
+                return Err(SessionRegistryError::EmptyBatch.into());
+            }
+            check_batch_size(module, entries.len(), state)?;
+            for (index, (_, session)) in entries.iter().enumerate() {
+                check_imported_session(module, index, session, state)?;
+            }
+
+            for (wallet, session) in &entries {
+                // imported sessions count against no signer, like genesis sessions
+                module.release_session_origin(wallet, state)?;
+                if session.bypass || session.expiry_ts != 0 {
+                    module.store_session(wallet, Some(session), state)?;
+                } else {
+                    module.store_session(wallet, None, state)?;
+                }
+            }
+            module.import_allowed.set(&false, state)?;
+
+            module.emit_event(
+                state,
+                Event::SessionsImported {
+                    count: entries.len() as u64,
+                },
+            );
+
+            Ok(())
+        }
+        _ => anyhow::bail!("Internal error: a non-manager call was routed to the manager handler"),
+    }
+}
+
+/// Fails with [`SessionRegistryError::BatchTooLarge`] if `len` exceeds `max_batch_size`.
+fn check_batch_size<S: Spec>(
+    module: &SessionRegistry<S>,
+    len: usize,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if len > module.max_batch_size(state)? as usize {
+        return Err(SessionRegistryError::BatchTooLarge.into());
+    }
+
+    Ok(())
+}
+
+/// Applies the rules of `SetSession` to the session at `index` of an import, so an import
+/// can't write a session the signer calls couldn't.
+///
+/// Fails with [`SessionRegistryError::NegativeExpiry`], [`SessionRegistryError::MetadataTooLarge`],
+/// [`SessionRegistryError::SessionRenewalLimitReached`] past `max_renewals`, and for timed
+/// sessions [`SessionRegistryError::SessionTooLong`] or [`SessionRegistryError::ExpiryInPast`].
+fn check_imported_session<S: Spec>(
+    module: &SessionRegistry<S>,
+    index: usize,
+    session: &Session,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if session.expiry_ts < 0 {
+        let index = Some(index);
+        return Err(SessionRegistryError::NegativeExpiry { index }.into());
+    }
+    if session
+        .metadata
+        .as_ref()
+        .is_some_and(|metadata| metadata.len() > MAX_SESSION_METADATA_BYTES)
+    {
+        return Err(SessionRegistryError::MetadataTooLarge.into());
+    }
+    if let Some(max_renewals) = module.max_renewals.get(state)? {
+        if session.renewals > max_renewals {
+            return Err(SessionRegistryError::SessionRenewalLimitReached.into());
+        }
+    }
+
+    if session.expiry_ts != 0 {
+        let now_ts = module.now_ts(state)?;
+        let max_duration = module.max_session_duration.get(state)?.unwrap_or(0);
+        if max_duration > 0 && session.expiry_ts > now_ts.saturating_add(max_duration) {
+            return Err(SessionRegistryError::SessionTooLong.into());
+        }
+        if module.reject_past_expiry.get(state)?.unwrap_or(false) && session.expiry_ts <= now_ts {
+            return Err(SessionRegistryError::ExpiryInPast.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Grant or revoke never-expiring session-signer privileges for each entry in order,
+/// emitting one `SessionSignerSet` per entry.
+fn set_session_signers<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    signers: impl IntoIterator<Item = (S::Address, bool)>,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    for (signer, allowed) in signers {
+        module.set_session_signer(&signer, allowed, 0, state)?;
+
+        module.emit_event(
+            state,
+            Event::SessionSignerSet {
+                signer,
+                allowed,
+                expires_at: 0,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle `PurgeWallet`, deleting a wallet's session record including its bypass flag.
+///
+/// Open to both the owner and the manager: it fails with
+/// [`SessionRegistryError::UnauthorizedOwnerOrManager`] for anyone else.
+pub(crate) fn purge_wallet<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    wallet: S::Address,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if !module.is_owner(sender, state)? && !module.is_manager(sender, state)? {
+        return Err(SessionRegistryError::UnauthorizedOwnerOrManager.into());
+    }
+
+    module.remove_session(&wallet, state)?;
+
+    module.emit_event(state, Event::WalletPurged { wallet });
+
+    Ok(())
+}
+
+/// Handle `AcceptManager`, completing a handoff started with `ProposeManager`.
+///
+/// Unlike the other manager calls this is authorized by the proposal, not
+/// the current manager: it fails with [`SessionRegistryError::NotPendingManager`]
+/// unless `sender` is the pending manager.
+pub(crate) fn accept_manager<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if module.pending_manager.get(state)?.as_ref() != Some(sender) {
+        return Err(SessionRegistryError::NotPendingManager.into());
+    }
+
+    let old_manager = module.manager.get(state)?;
+
+    module.manager.set(sender, state)?;
+    module.pending_manager.delete(state)?;
+
+    module.emit_event(
+        state,
+        Event::ManagerSet {
+            old_manager,
+            new_manager: sender.clone(),
+        },
+    );
+
+    Ok(())
+}
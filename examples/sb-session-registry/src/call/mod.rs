@@ -0,0 +1,274 @@
+//! Call messages and execution entrypoint for the `SessionRegistry` module.
+
+mod manager;
+mod owner;
+mod signer;
+
+use schemars::JsonSchema;
+use sov_modules_api::macros::serialize;
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::{Context, Spec, TxState};
+
+use crate::{EnforcementMode, ScopeId, Session, SessionRegistry, WalletPolicy};
+
+/// Transaction-level messages supported by the `SessionRegistry`.
+///
+/// Access control is enforced in [`execute`]:
+/// - `SetManager` / `ProposeManager`: owner-only
+/// - `AcceptManager`: pending-manager-only
+/// - `SetEnforcementEnabled`: owner-only
+/// - `SetSessionSigner` / `SetSessionSignersBatch` / `SetSessionSignerBatch`: manager-only
+/// - `SetSession` / `SetSessionWithMetadata` / `SetSessionBatch` / `SetSessionBatchUniform` /
+///   `ExtendSession` / `RevokeSelfAsSigner`: session-signer-only
+/// - `SetBypass`: manager-only
+/// - `SetExpiryOffset`: owner-only
+/// - `ForceExpire`: manager-only
+/// - `SetSignersFrozen`: owner-only
+/// - `SetEnforcementScope`: owner-only
+/// - `SetPaused`: owner-only
+/// - `SetOwner`: owner-only
+/// - `SetWalletPolicy`: manager-only
+/// - `SetForceEnforce`: manager-only
+/// - `ImportSessions`: manager-only, once
+/// - `PurgeWallet`: owner-or-manager
+/// - `EnforceSessionActive` / `EnforceSessionPresent` / `CheckSessionActive`: anyone
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, UniversalWallet)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+#[schemars(bound = "S: Spec", rename = "CallMessage")]
+pub enum CallMessage<S: Spec> {
+    /// Update the manager address.
+    SetManager { new_manager: S::Address },
+
+    /// Propose a new manager, who takes over once it sends `AcceptManager`.
+    ///
+    /// Replaces any earlier proposal; the current manager stays in place until then.
+    ProposeManager { new_manager: S::Address },
+
+    /// Accept a pending `ProposeManager`, making the sender the manager.
+    AcceptManager,
+
+    /// Enable or disable global session enforcement.
+    SetEnforcementEnabled { enabled: bool },
+
+    /// Grant or revoke session-signer privileges for an address.
+    ///
+    /// A grant lapses once chain time reaches `expires_at`, in the registry's time unit;
+    /// `0` (the default) never expires. Ignored on revocation.
+    SetSessionSigner {
+        signer: S::Address,
+        allowed: bool,
+        #[serde(default)]
+        expires_at: i64,
+    },
+
+    /// Grant or revoke session-signer privileges for several addresses at once.
+    ///
+    /// Entries are applied in order, so a later entry for the same address wins. Grants never
+    /// expire.
+    SetSessionSignersBatch { signers: Vec<(S::Address, bool)> },
+
+    /// Like `SetSessionSignersBatch`, with the addresses and their flags in parallel lists.
+    ///
+    /// `signers` and `allowed` must have the same length, as in `SetSessionBatch`.
+    SetSessionSignerBatch {
+        signers: Vec<S::Address>,
+        allowed: Vec<bool>,
+    },
+
+    /// Set or delete the session for a single wallet.
+    ///
+    /// `expires_at == 0` removes the session (see `write_session`); negative expiries are
+    /// rejected.
+    SetSession { wallet: S::Address, expires_at: i64 },
+
+    /// Like `SetSession`, also replacing the session's metadata tag.
+    ///
+    /// An empty `metadata` clears the tag; it may be at most 64 bytes long. Plain
+    /// `SetSession` renewals keep the tag.
+    SetSessionWithMetadata {
+        wallet: S::Address,
+        expires_at: i64,
+        metadata: Vec<u8>,
+    },
+
+    /// Set or delete sessions for a batch of wallets.
+    ///
+    /// An `expiries` entry of `0` clears that wallet's session exactly like
+    /// `SetSession` does, so one batch can mix renewals and revocations. The
+    /// batch is atomic: if any entry fails, no session changes. A negative
+    /// entry fails the whole batch before anything is written.
+    SetSessionBatch {
+        wallets: Vec<S::Address>,
+        expiries: Vec<i64>,
+    },
+
+    /// Set or clear the bypass flag for a wallet.
+    ///
+    /// When `bypass == true`, the wallet is always treated as having
+    /// an active and present session.
+    SetBypass { wallet: S::Address, bypass: bool },
+
+    /// Assert that a wallet has an active session.
+    EnforceSessionActive { wallet: S::Address },
+
+    /// Assert that a wallet has a present (non-deleted) session.
+    EnforceSessionPresent { wallet: S::Address },
+
+    /// Report whether a wallet's session is active and present in a `SessionChecked` event.
+    ///
+    /// Never fails on the wallet's status, so clients can probe it without reverting.
+    CheckSessionActive { wallet: S::Address },
+
+    /// Set a new global expiry offset.
+    ///
+    /// Fails with `OffsetTooNegative` below the genesis `min_expiry_offset`.
+    SetExpiryOffset { new_offset: i64 },
+
+    /// Immediately expire a wallet's session while keeping its record.
+    ///
+    /// Sets `expiry_ts` to a timestamp in the past and clears `bypass`, so the
+    /// wallet reads as present but not active.
+    ForceExpire { wallet: S::Address },
+
+    /// Set sessions for a batch of wallets, all expiring `ttl_secs` from now.
+    ///
+    /// `ttl_secs` must be positive; use `SetSessionBatch` with `0` to clear.
+    SetSessionBatchUniform {
+        wallets: Vec<S::Address>,
+        ttl_secs: i64,
+    },
+
+    /// Freeze or unfreeze the set of session signers.
+    ///
+    /// While frozen, `SetSessionSigner` fails; existing signers keep their privileges.
+    SetSignersFrozen { frozen: bool },
+
+    /// Set the enforcement mode of a scope.
+    ///
+    /// `mode == None` removes the scope, which then follows the global
+    /// enforcement flag again.
+    SetEnforcementScope {
+        scope: ScopeId,
+        mode: Option<EnforcementMode>,
+    },
+
+    /// Set or clear a stricter enforcement policy for a wallet.
+    ///
+    /// `policy == None` returns the wallet to the regular active-session check.
+    SetWalletPolicy {
+        wallet: S::Address,
+        policy: Option<WalletPolicy>,
+    },
+
+    /// Push a wallet's session expiry `additional_seconds` further out.
+    ///
+    /// Keeps the `bypass` flag. A wallet without a timed session gets one
+    /// expiring `additional_seconds` from now. `additional_seconds` must not be
+    /// negative; 0 leaves the session untouched.
+    ExtendSession {
+        wallet: S::Address,
+        additional_seconds: i64,
+    },
+
+    /// Enforce or stop enforcing a wallet's unscoped checks regardless of the
+    /// global enforcement flag.
+    SetForceEnforce { wallet: S::Address, enabled: bool },
+
+    /// Pause or unpause the registry.
+    ///
+    /// While paused, every enforce check fails with `RegistryPaused`, whatever
+    /// the wallet's session and the enforcement flags.
+    SetPaused { paused: bool },
+
+    /// Delete a wallet's session record entirely, expiry and bypass alike.
+    ///
+    /// Unlike clearing with `SetSession` and `SetBypass`, this takes a single call from
+    /// either the owner or the manager.
+    PurgeWallet { wallet: S::Address },
+
+    /// Revoke the sender's own session-signer privileges.
+    ///
+    /// Lets a signer that suspects its key is compromised step down without waiting for the
+    /// manager. Works while signers are frozen.
+    RevokeSelfAsSigner,
+
+    /// Write session records verbatim, bypass flags and expiries included, to seed a new chain
+    /// with the sessions exported from an old one.
+    ///
+    /// Each entry must pass the checks of `SetSession`; an entry that is neither bypassed nor
+    /// timed clears the wallet. Imported sessions count against no signer. Only allowed once:
+    /// the first successful import locks it, after which it fails with `ImportLocked`.
+    ImportSessions { entries: Vec<(S::Address, Session)> },
+
+    /// Add an address to the owner set, or remove one from it.
+    ///
+    /// Removing the only remaining owner fails with `LastOwner`, so the registry always keeps
+    /// one.
+    SetOwner { owner: S::Address, allowed: bool },
+}
+
+/// Route a CallMessage to the corresponding `SessionRegistry` logic.
+///
+/// This is the main entrypoint used by the runtime: each message is
+/// dispatched to the handler for its authorization class (`owner`,
+/// `manager` or `signer`), which applies access control based on
+/// `context.sender()` before updating module state.
+pub fn execute<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    msg: CallMessage<S>,
+    context: &Context<S>,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    execute_as(module, msg, context.sender(), state)
+}
+
+/// Like [`execute`], with access control based on `sender` alone.
+///
+/// Handlers only depend on the sender of the context, which lets the
+/// simulate endpoint dry-run a call on behalf of a claimed sender.
+pub(crate) fn execute_as<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    msg: CallMessage<S>,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    match msg {
+        CallMessage::SetManager { .. }
+        | CallMessage::ProposeManager { .. }
+        | CallMessage::SetEnforcementEnabled { .. }
+        | CallMessage::SetExpiryOffset { .. }
+        | CallMessage::SetSignersFrozen { .. }
+        | CallMessage::SetEnforcementScope { .. }
+        | CallMessage::SetPaused { .. }
+        | CallMessage::SetOwner { .. } => owner::execute(module, msg, sender, state),
+
+        CallMessage::SetSessionSigner { .. }
+        | CallMessage::SetSessionSignersBatch { .. }
+        | CallMessage::SetSessionSignerBatch { .. }
+        | CallMessage::SetBypass { .. }
+        | CallMessage::ForceExpire { .. }
+        | CallMessage::SetWalletPolicy { .. }
+        | CallMessage::SetForceEnforce { .. }
+        | CallMessage::ImportSessions { .. } => manager::execute(module, msg, sender, state),
+
+        CallMessage::AcceptManager => manager::accept_manager(module, sender, state),
+        CallMessage::PurgeWallet { wallet } => manager::purge_wallet(module, wallet, sender, state),
+
+        CallMessage::SetSession { .. }
+        | CallMessage::SetSessionWithMetadata { .. }
+        | CallMessage::SetSessionBatch { .. }
+        | CallMessage::SetSessionBatchUniform { .. }
+        | CallMessage::ExtendSession { .. }
+        | CallMessage::RevokeSelfAsSigner => signer::execute(module, msg, sender, state),
+
+        // --- Endpoints for direct session checks via transactions ---
+        CallMessage::EnforceSessionActive { wallet } => {
+            module.enforce_session_active(&wallet, state)
+        }
+        CallMessage::EnforceSessionPresent { wallet } => {
+            module.enforce_session_present(&wallet, state)
+        }
+        CallMessage::CheckSessionActive { wallet } => module.check_session_active(&wallet, state),
+    }
+}
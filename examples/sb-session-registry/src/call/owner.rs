@@ -0,0 +1,153 @@
+//! Owner-only call handlers.
+
+use sov_modules_api::{EventEmitter, Spec, TxState};
+
+use crate::{CallMessage, Event, ExpiryOffsetChange, SessionRegistry, SessionRegistryError};
+
+/// Handle a call message reserved for the owner.
+///
+/// Rejects the call with [`SessionRegistryError::UnauthorizedOwner`] unless
+/// `sender` is one of the owners. With `require_funded_manager` set, both
+/// `SetManager` and `ProposeManager` reject unfunded addresses.
+pub(crate) fn execute<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    msg: CallMessage<S>,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if !module.is_owner(sender, state)? {
+        return Err(SessionRegistryError::UnauthorizedOwner.into());
+    }
+
+    match msg {
+        CallMessage::SetManager { new_manager } => {
+            ensure_manager_funded(module, &new_manager, state)?;
+
+            let old_manager = module.manager.get(state)?;
+
+            module.manager.set(&new_manager, state)?;
+            // a direct handoff supersedes any pending proposal
+            module.pending_manager.delete(state)?;
+
+            module.emit_event(
+                state,
+                Event::ManagerSet {
+                    old_manager,
+                    new_manager,
+                },
+            );
+
+            Ok(())
+        }
+        CallMessage::ProposeManager { new_manager } => {
+            ensure_manager_funded(module, &new_manager, state)?;
+
+            module.pending_manager.set(&new_manager, state)?;
+
+            module.emit_event(state, Event::ManagerProposed { new_manager });
+
+            Ok(())
+        }
+        CallMessage::SetEnforcementEnabled { enabled } => {
+            // compare the raw flag, so an uninitialized flag can still be set
+            if module.enforcement_enabled.get(state)? == Some(enabled) {
+                module.emit_event(state, Event::EnforcementUnchanged { enabled });
+
+                return Ok(());
+            }
+
+            module.enforcement_enabled.set(&enabled, state)?;
+
+            module.emit_event(state, Event::EnforcementEnabledSet { enabled });
+
+            Ok(())
+        }
+        CallMessage::SetExpiryOffset { new_offset } => {
+            let min_offset = module.min_expiry_offset.get(state)?.unwrap_or(i64::MIN);
+            if new_offset < min_offset {
+                return Err(SessionRegistryError::OffsetTooNegative.into());
+            }
+
+            let change = ExpiryOffsetChange {
+                old_offset: module.expiry_offset.get(state)?,
+                new_offset,
+                updated_by: sender.clone(),
+                updated_at_ts: module.now_ts(state)?,
+            };
+
+            module.expiry_offset.set(&new_offset, state)?;
+            module.record_offset_change(&change, state)?;
+
+            module.emit_event(
+                state,
+                Event::ExpiryOffsetUpdated {
+                    old_offset: change.old_offset,
+                    new_offset,
+                    updated_by: change.updated_by,
+                    updated_at_ts: change.updated_at_ts,
+                },
+            );
+
+            Ok(())
+        }
+        CallMessage::SetSignersFrozen { frozen } => {
+            module.signers_frozen.set(&frozen, state)?;
+
+            module.emit_event(state, Event::SignersFrozenSet { frozen });
+
+            Ok(())
+        }
+        CallMessage::SetEnforcementScope { scope, mode } => {
+            match mode {
+                Some(mode) => module.enforcement_scopes.set(&scope, &mode, state)?,
+                None => module.enforcement_scopes.remove(&scope, state)?,
+            }
+
+            module.emit_event(state, Event::EnforcementScopeSet { scope, mode });
+
+            Ok(())
+        }
+        CallMessage::SetPaused { paused } => {
+            module.paused.set(&paused, state)?;
+
+            module.emit_event(state, Event::PausedSet { paused });
+
+            Ok(())
+        }
+        CallMessage::SetOwner { owner, allowed } => {
+            let was_owner = module.owners.get(&owner, state)?.unwrap_or(false);
+            let owner_count = module.owner_count.get(state)?.unwrap_or(0);
+
+            if allowed && !was_owner {
+                module.owners.set(&owner, &true, state)?;
+                module.owner_count.set(&(owner_count + 1), state)?;
+            } else if !allowed && was_owner {
+                if owner_count <= 1 {
+                    return Err(SessionRegistryError::LastOwner.into());
+                }
+                module.owners.remove(&owner, state)?;
+                module.owner_count.set(&(owner_count - 1), state)?;
+            }
+
+            module.emit_event(state, Event::OwnerSet { owner, allowed });
+
+            Ok(())
+        }
+        _ => anyhow::bail!("Internal error: a non-owner call was routed to the owner handler"),
+    }
+}
+
+/// Applies the `require_funded_manager` guard to a prospective manager.
+fn ensure_manager_funded<S: Spec>(
+    module: &SessionRegistry<S>,
+    new_manager: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if module.require_funded_manager.get(state)?.unwrap_or(false)
+        && !module.is_funded_account(new_manager, state)?
+    {
+        return Err(SessionRegistryError::ManagerNotFunded.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Session-signer-only call handlers.
+
+use sov_modules_api::{EventEmitter, Spec, TxState};
+
+use crate::{
+    CallMessage, Event, SessionRegistry, SessionRegistryError, MAX_SESSION_METADATA_BYTES,
+};
+
+/// Handle a call message reserved for session signers.
+///
+/// Rejects the call with [`SessionRegistryError::UnauthorizedSessionSigner`]
+/// unless `sender` is an allowed session signer. When
+/// `signer_cannot_be_wallet` is set, entries targeting the sender itself fail
+/// with [`SessionRegistryError::SignerIsWallet`]. Batches without any wallet
+/// fail with [`SessionRegistryError::EmptyBatch`], and batches longer than
+/// `max_batch_size` with [`SessionRegistryError::BatchTooLarge`] before
+/// anything is written, as do negative expiries with
+/// [`SessionRegistryError::NegativeExpiry`] and metadata longer than
+/// [`MAX_SESSION_METADATA_BYTES`] with [`SessionRegistryError::MetadataTooLarge`].
+pub(crate) fn execute<S: Spec>(
+    module: &mut SessionRegistry<S>,
+    msg: CallMessage<S>,
+    sender: &S::Address,
+    state: &mut impl TxState<S>,
+) -> anyhow::Result<()> {
+    if !module.is_session_signer(sender, state)? {
+        return Err(SessionRegistryError::UnauthorizedSessionSigner.into());
+    }
+
+    let signer_cannot_be_wallet = module.signer_cannot_be_wallet.get(state)?.unwrap_or(false);
+    let check_target = |wallet: &S::Address| {
+        if signer_cannot_be_wallet && wallet == sender {
+            return Err(SessionRegistryError::SignerIsWallet);
+        }
+        Ok(())
+    };
+    let max_batch_size = module.max_batch_size(state)? as usize;
+    let check_batch_size = |len: usize| {
+        if len > max_batch_size {
+            return Err(SessionRegistryError::BatchTooLarge);
+        }
+        Ok(())
+    };
+
+    match msg {
+        CallMessage::SetSession { wallet, expires_at } => {
+            if expires_at < 0 {
+                return Err(SessionRegistryError::NegativeExpiry { index: None }.into());
+            }
+            check_target(&wallet)?;
+            module.write_session(&wallet, expires_at, None, sender, state)?;
+
+            Ok(())
+        }
+        CallMessage::SetSessionWithMetadata {
+            wallet,
+            expires_at,
+            metadata,
+        } => {
+            if expires_at < 0 {
+                return Err(SessionRegistryError::NegativeExpiry { index: None }.into());
+            }
+            if metadata.len() > MAX_SESSION_METADATA_BYTES {
+                return Err(SessionRegistryError::MetadataTooLarge.into());
+            }
+            check_target(&wallet)?;
+            module.write_session(&wallet, expires_at, Some(metadata), sender, state)?;
+
+            Ok(())
+        }
+        CallMessage::SetSessionBatch { wallets, expiries } => {
+            check_batch_size(wallets.len().max(expiries.len()))?;
+            if wallets.len() != expiries.len() {
+                return Err(SessionRegistryError::InvalidBatchLengths.into());
+            }
+            if wallets.is_empty() {
+                return Err(SessionRegistryError::EmptyBatch.into());
+            }
+            if let Some(index) = expiries.iter().position(|expires_at| *expires_at < 0) {
+                let index = Some(index);
+                return Err(SessionRegistryError::NegativeExpiry { index }.into());
+            }
+
+            for (wallet, expires_at) in wallets.iter().zip(expiries.iter().copied()) {
+                check_target(wallet)?;
+                module.write_session(wallet, expires_at, None, sender, state)?;
+            }
+
+            Ok(())
+        }
+        CallMessage::SetSessionBatchUniform { wallets, ttl_secs } => {
+            check_batch_size(wallets.len())?;
+            if wallets.is_empty() {
+                return Err(SessionRegistryError::EmptyBatch.into());
+            }
+            let now_ts = module.now_ts(state)?;
+            let expires_at = module
+                .time_unit(state)?
+                .from_secs(ttl_secs)
+                .and_then(|ttl| now_ts.checked_add(ttl))
+                .filter(|_| ttl_secs > 0)
+                .ok_or(SessionRegistryError::InvalidTtl)?;
+
+            for wallet in wallets.iter() {
+                check_target(wallet)?;
+                module.write_session(wallet, expires_at, None, sender, state)?;
+            }
+
+            Ok(())
+        }
+        CallMessage::ExtendSession {
+            wallet,
+            additional_seconds,
+        } => {
+            check_target(&wallet)?;
+            if additional_seconds < 0 {
+                return Err(SessionRegistryError::InvalidTtl.into());
+            }
+            // nothing to extend, so neither a renewal nor an event
+            if additional_seconds == 0 {
+                return Ok(());
+            }
+
+            let base_ts = match module.sessions.get(&wallet, state)? {
+                Some(session) if session.expiry_ts != 0 => session.expiry_ts,
+                _ => module.now_ts(state)?,
+            };
+            let expires_at = module
+                .time_unit(state)?
+                .from_secs(additional_seconds)
+                .and_then(|extension| base_ts.checked_add(extension))
+                .ok_or(SessionRegistryError::InvalidTtl)?;
+
+            module.write_session(&wallet, expires_at, None, sender, state)?;
+
+            Ok(())
+        }
+        CallMessage::RevokeSelfAsSigner => {
+            module.set_session_signer(sender, false, 0, state)?;
+
+            module.emit_event(
+                state,
+                Event::SessionSignerSet {
+                    signer: sender.clone(),
+                    allowed: false,
+                    expires_at: 0,
+                },
+            );
+
+            Ok(())
+        }
+        _ => anyhow::bail!("Internal error: a non-signer call was routed to the signer handler"),
+    }
+}
@@ -25,4 +25,154 @@ pub enum SessionRegistryError {
 
     #[error("Discrepancy in wallets/expiries lengths")]
     InvalidBatchLengths,
+
+    #[error("New manager has no gas token balance")]
+    ManagerNotFunded,
+
+    #[error("Session registry genesis has already run")]
+    GenesisAlreadyInitialized,
+
+    #[error("Session has reached the maximum number of renewals")]
+    SessionRenewalLimitReached,
+
+    #[error("Session signer cannot set its own session")]
+    SignerIsWallet,
+
+    #[error("Session TTL must be positive and must not overflow the expiry")]
+    InvalidTtl,
+
+    #[error("Session signers are frozen")]
+    SignersFrozen,
+
+    #[error("Batch contains no wallets")]
+    EmptyBatch,
+
+    #[error("Session expiry exceeds the maximum session duration")]
+    SessionTooLong,
+
+    #[error("Caller is not the pending manager")]
+    NotPendingManager,
+
+    #[error("Batch exceeds the maximum batch size")]
+    BatchTooLarge,
+
+    #[error("Session expiry is not in the future")]
+    ExpiryInPast,
+
+    #[error("Session registry is paused")]
+    RegistryPaused,
+
+    #[error("Session signer has reached its maximum number of sessions")]
+    SignerSessionLimitReached,
+
+    #[error("Enforcement flag not initialized")]
+    EnforcementFlagNotInitialized,
+
+    #[error("Caller is neither the owner nor the manager")]
+    UnauthorizedOwnerOrManager,
+
+    /// `index` is the offending entry of a batch, and `None` for single-session calls.
+    #[error("Session expiry{} is negative", at_index(.index))]
+    NegativeExpiry { index: Option<usize> },
+
+    #[error("Expiry offset is below the minimum expiry offset")]
+    OffsetTooNegative,
+
+    #[error("Session metadata exceeds the maximum size")]
+    MetadataTooLarge,
+
+    #[error("Invalid genesis config: {reason}")]
+    InvalidGenesisConfig { reason: &'static str },
+
+    #[error("Sessions have already been imported")]
+    ImportLocked,
+
+    #[error("Cannot remove the last owner")]
+    LastOwner,
+}
+
+impl SessionRegistryError {
+    /// Stable numeric code of the error, for clients that branch on the error kind.
+    ///
+    /// Codes are never reused or renumbered; new variants take the next free code.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::OwnerNotInitialized => 1,
+            Self::ManagerNotInitialized => 2,
+            Self::UnauthorizedOwner => 3,
+            Self::UnauthorizedManager => 4,
+            Self::UnauthorizedSessionSigner => 5,
+            Self::SessionNotActive => 6,
+            Self::SessionNotPresent => 7,
+            Self::InvalidBatchLengths => 8,
+            Self::ManagerNotFunded => 9,
+            Self::GenesisAlreadyInitialized => 10,
+            Self::SessionRenewalLimitReached => 11,
+            Self::SignerIsWallet => 12,
+            Self::InvalidTtl => 13,
+            Self::SignersFrozen => 14,
+            Self::EmptyBatch => 15,
+            Self::SessionTooLong => 16,
+            Self::NotPendingManager => 17,
+            Self::BatchTooLarge => 18,
+            Self::ExpiryInPast => 19,
+            Self::RegistryPaused => 20,
+            Self::SignerSessionLimitReached => 21,
+            Self::EnforcementFlagNotInitialized => 22,
+            Self::UnauthorizedOwnerOrManager => 23,
+            Self::NegativeExpiry { .. } => 24,
+            Self::OffsetTooNegative => 25,
+            Self::MetadataTooLarge => 26,
+            Self::InvalidGenesisConfig { .. } => 27,
+            Self::ImportLocked => 28,
+            Self::LastOwner => 29,
+        }
+    }
+}
+
+/// Locates a batch entry in an error message, e.g. ` at index 3`.
+fn at_index(index: &Option<usize>) -> String {
+    index
+        .map(|index| format!(" at index {index}"))
+        .unwrap_or_default()
+}
+
+/// A [`SessionRegistryError`] together with its [`code`](SessionRegistryError::code), as
+/// returned from the module's `call`. Displays as the underlying error followed by
+/// `(code N)`, so the code reaches transaction receipts and API errors next to the message.
+///
+/// Errors returned from `call` must be downcast to this type rather than to
+/// [`SessionRegistryError`]; it dereferences to the underlying error.
+#[derive(Debug, Error)]
+#[error("{error} (code {code})")]
+pub struct CodedSessionRegistryError {
+    /// Code of `error`.
+    pub code: u32,
+    /// The underlying registry error.
+    pub error: SessionRegistryError,
+}
+
+impl From<SessionRegistryError> for CodedSessionRegistryError {
+    fn from(error: SessionRegistryError) -> Self {
+        Self {
+            code: error.code(),
+            error,
+        }
+    }
+}
+
+impl std::ops::Deref for CodedSessionRegistryError {
+    type Target = SessionRegistryError;
+
+    fn deref(&self) -> &Self::Target {
+        &self.error
+    }
+}
+
+/// Tags `error` with its code if it is a [`SessionRegistryError`], leaving other errors as is.
+pub(crate) fn with_error_code(error: anyhow::Error) -> anyhow::Error {
+    match error.downcast::<SessionRegistryError>() {
+        Ok(error) => CodedSessionRegistryError::from(error).into(),
+        Err(error) => error,
+    }
 }
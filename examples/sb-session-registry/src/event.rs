@@ -2,6 +2,8 @@ use schemars::JsonSchema;
 use sov_modules_api::macros::serialize;
 use sov_modules_api::Spec;
 
+use crate::{ActiveVia, EnforceKind, EnforcementMode, ScopeId, WalletPolicy};
+
 #[derive(Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serialize(Borsh, Serde)]
 #[serde(bound = "S: Spec", rename_all = "snake_case")]
@@ -19,6 +21,9 @@ pub enum Event<S: Spec> {
     SessionSignerSet {
         signer: S::Address,
         allowed: bool,
+        /// When the authorization lapses; `0` for never, and always `0` on revocation.
+        #[serde(default)]
+        expires_at: i64,
     },
 
     SessionSet {
@@ -34,5 +39,75 @@ pub enum Event<S: Spec> {
     ExpiryOffsetUpdated {
         old_offset: Option<i64>,
         new_offset: i64,
+        updated_by: S::Address,
+        updated_at_ts: i64,
+    },
+
+    EnforcementPassed {
+        wallet: S::Address,
+        via: ActiveVia,
+    },
+
+    SessionForceExpired {
+        wallet: S::Address,
+    },
+
+    EnforcementUnchanged {
+        enabled: bool,
+    },
+
+    SignersFrozenSet {
+        frozen: bool,
+    },
+
+    EnforcementScopeSet {
+        scope: ScopeId,
+        mode: Option<EnforcementMode>,
+    },
+
+    WalletPolicySet {
+        wallet: S::Address,
+        policy: Option<WalletPolicy>,
+    },
+
+    SessionCleared {
+        wallet: S::Address,
+    },
+
+    ManagerProposed {
+        new_manager: S::Address,
+    },
+
+    ForceEnforceSet {
+        wallet: S::Address,
+        enabled: bool,
+    },
+
+    PausedSet {
+        paused: bool,
+    },
+
+    WalletPurged {
+        wallet: S::Address,
+    },
+
+    EnforcementBypassed {
+        wallet: S::Address,
+        check: EnforceKind,
+    },
+
+    SessionChecked {
+        wallet: S::Address,
+        active: bool,
+        present: bool,
+    },
+
+    SessionsImported {
+        count: u64,
+    },
+
+    OwnerSet {
+        owner: S::Address,
+        allowed: bool,
     },
 }
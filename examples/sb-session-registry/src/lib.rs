@@ -12,27 +12,66 @@
 mod call;
 mod error;
 mod event;
+#[cfg(feature = "native")]
+mod rest;
 mod types;
 
 pub use call::CallMessage;
-pub use error::SessionRegistryError;
+pub use error::{CodedSessionRegistryError, SessionRegistryError};
 pub use event::Event;
-pub use types::{RegistryConfig, Session};
+#[cfg(feature = "native")]
+pub use rest::{
+    encode_session_response, verify_session_proof, EncodedSession, ExpiringSessionsQuery,
+    ExpiringSessionsResponse, ExpiryOffsetHistoryResponse, HistoricalSessionStatus,
+    HistoricalStatusQuery, ResponseFormat, SessionCountResponse, SessionProof, SessionQuery,
+    SessionQueryError, SessionResponse, SessionStatus, SessionsResponse, SignersResponse,
+    SimulateRequest, SimulateResponse, VersionResponse, BORSH_CONTENT_TYPE, COMPILED_FEATURES,
+};
+pub use types::{
+    ActivationReason, ActiveVia, EnforceKind, EnforcementMode, ExpiryOffsetChange, RegistryConfig,
+    ScopeId, Session, SignerAuthorization, TimeUnit, WalletPolicy, DEFAULT_MAX_BATCH_SIZE,
+    MAX_EXPIRING_SESSIONS, MAX_GENESIS_NEGATIVE_OFFSET_SECS, MAX_OFFSET_HISTORY,
+    MAX_SESSION_METADATA_BYTES,
+};
+
+/// Semantic version of the session registry's call, event and REST interface.
+///
+/// Bump the minor version when adding messages, events, routes or config fields, and the major
+/// version on breaking changes. Reported by `GET /modules/session-registry/version`.
+pub const MODULE_VERSION: &str = "0.2.0";
 
 use sov_modules_api::da::Time;
 use sov_modules_api::{
-    Context, EventEmitter, GenesisState, Module, ModuleId, ModuleInfo, ModuleRestApi, Spec,
-    StateMap, StateValue, TxState,
+    Amount, Context, EventEmitter, GenesisState, Module, ModuleId, ModuleInfo, ModuleRestApi, Spec,
+    StateMap, StateReader, StateValue, StateVec, TxState,
 };
+use sov_state::User;
 
 /// Session registry module definition.
 ///
 /// This struct declares all on-chain state used by the registry:
-/// - `owner`: address with ultimate control (can change the manager and toggle enforcement),
+/// - `owner`: genesis owner, the bootstrap member of `owners`,
+/// - `owners` / `owner_count`: addresses with ultimate control (can change the manager and toggle
+///   enforcement), and how many there are,
 /// - `manager`: operational address that controls signers and bypass,
+/// - `pending_manager`: proposed manager of an unfinished two-step handoff,
 /// - `enforcement_enabled`: global flag to toggle enforcement checks,
 /// - `sessions`: per-wallet session records,
-/// - `session_signers`: addresses allowed to set/remove sessions.
+/// - `session_signers`: addresses allowed to set/remove sessions, each until an optional expiry,
+/// - `session_signer_list`: enumerable copy of the allowed session signers,
+/// - `require_funded_manager`: opt-in guard against handing the manager role to an unfunded address,
+/// - `max_renewals`: optional cap on how often a single session can be renewed,
+/// - `session_origins` / `sessions_per_signer`: which signer created each session, and how many
+///   each signer holds open, capped by `max_sessions_per_signer`,
+/// - `signer_cannot_be_wallet`: opt-in policy keeping signers from setting their own sessions,
+/// - `signers_frozen`: incident switch locking the set of session signers,
+/// - `enforcement_scopes`: per-operation-type enforcement modes overriding `enforcement_enabled`,
+/// - `paused`: emergency switch failing every enforce check,
+/// - `active_session_count`: number of present sessions, for dashboards,
+/// - `session_wallets` / `session_wallet_index`: enumerable list of the wallets with a present
+///   session, and each wallet's position in it,
+/// - `expiry_offset_history`: audit log of the latest `expiry_offset` changes,
+/// - `import_allowed`: whether the one-time `ImportSessions` bootstrap is still open.
 #[derive(Clone, ModuleInfo, ModuleRestApi)]
 pub struct SessionRegistry<S: Spec> {
     /// Unique identifier of this module in the runtime.
@@ -43,12 +82,27 @@ pub struct SessionRegistry<S: Spec> {
     #[module]
     pub chain_state: sov_chain_state::ChainState<S>,
 
-    /// Address with ultimate ownership of the registry.
+    /// Reference to the bank module (for checking gas token balances).
+    #[module]
+    pub bank: sov_bank::Bank<S>,
+
+    /// Owner configured at genesis.
     ///
-    /// Can change the manager and toggle enforcement.
+    /// Seeds `owners`, which is what owner checks consult; kept as a record of the bootstrap
+    /// owner, even once `SetOwner` removes it from the set.
     #[state]
     pub owner: StateValue<S::Address>,
 
+    /// Addresses with ultimate ownership of the registry.
+    ///
+    /// Each can change the manager, toggle enforcement and add or remove owners.
+    #[state]
+    pub owners: StateMap<S::Address, bool>,
+
+    /// Number of addresses in `owners`, which can't be enumerated.
+    #[state]
+    pub owner_count: StateValue<u64>,
+
     /// Operational address responsible for day-to-day configuration.
     ///
     /// The manager can set session signers and
@@ -56,6 +110,10 @@ pub struct SessionRegistry<S: Spec> {
     #[state]
     pub manager: StateValue<S::Address>,
 
+    /// Manager proposed by the owner, awaiting its `AcceptManager`.
+    #[state]
+    pub pending_manager: StateValue<S::Address>,
+
     /// Global flag controlling whether session enforcement is active.
     #[state]
     pub enforcement_enabled: StateValue<bool>,
@@ -64,14 +122,129 @@ pub struct SessionRegistry<S: Spec> {
     #[state]
     pub sessions: StateMap<S::Address, Session>,
 
-    /// Mapping from address to whether it is allowed to act as a session signer.
+    /// Mapping from address to its session-signer authorization.
+    #[state]
+    pub session_signers: StateMap<S::Address, SignerAuthorization>,
+
+    /// Addresses granted session-signer privileges, in no particular order.
+    ///
+    /// Mirrors the `allowed` entries of `session_signers`, which can't be enumerated, including
+    /// authorizations that have since expired.
     #[state]
-    pub session_signers: StateMap<S::Address, bool>,
+    pub session_signer_list: StateVec<S::Address>,
 
     /// Offset to extend all session expiries by a fixed amount.
     /// Used in emergencies if backend services are down and need to extend sessions.
     #[state]
     pub expiry_offset: StateValue<i64>,
+
+    /// Lowest offset `SetExpiryOffset` accepts. Unset means unrestricted.
+    #[state]
+    pub min_expiry_offset: StateValue<i64>,
+
+    /// When `true`, `SetManager` only accepts addresses holding a nonzero gas token balance.
+    ///
+    /// An unfunded manager can never submit a transaction, which would silently
+    /// brick operational control.
+    #[state]
+    pub require_funded_manager: StateValue<bool>,
+
+    /// When `true`, successful active-session enforcement emits an audit event.
+    #[state]
+    pub emit_enforcement_events: StateValue<bool>,
+
+    /// When `true`, enforce checks skipped because enforcement is off emit an audit event.
+    #[state]
+    pub emit_bypass_events: StateValue<bool>,
+
+    /// Maximum number of renewals per session. Unset means unlimited.
+    #[state]
+    pub max_renewals: StateValue<u32>,
+
+    /// Signer that created each wallet's current session.
+    ///
+    /// Kept next to `sessions` so the `Session` layout stays address-free. Sessions seeded at
+    /// genesis or created by `SetBypass` have no origin.
+    #[state]
+    pub session_origins: StateMap<S::Address, S::Address>,
+
+    /// Number of sessions each signer has created and not yet cleared.
+    #[state]
+    pub sessions_per_signer: StateMap<S::Address, u32>,
+
+    /// Maximum value of `sessions_per_signer` for any signer. Unset means unlimited.
+    #[state]
+    pub max_sessions_per_signer: StateValue<u32>,
+
+    /// When `true`, `SetSession`/`SetSessionBatch` reject entries targeting the signer itself.
+    #[state]
+    pub signer_cannot_be_wallet: StateValue<bool>,
+
+    /// When `true`, `SetSessionSigner` is rejected so the signer set can't change.
+    ///
+    /// Toggled by the owner during incidents; doesn't affect enforcement or sessions.
+    #[state]
+    pub signers_frozen: StateValue<bool>,
+
+    /// Enforcement mode of each named scope.
+    ///
+    /// A scope without an entry falls back to `enforcement_enabled`.
+    #[state]
+    pub enforcement_scopes: StateMap<ScopeId, EnforcementMode>,
+
+    /// Stricter active-session checks for individual wallets, set by the manager.
+    #[state]
+    pub wallet_policies: StateMap<S::Address, WalletPolicy>,
+
+    /// Wallets whose checks are enforced even while `enforcement_enabled` is off.
+    #[state]
+    pub force_enforce: StateMap<S::Address, bool>,
+
+    /// Unit of session expiries and the expiry offset, set at genesis.
+    #[state]
+    pub time_unit: StateValue<TimeUnit>,
+
+    /// Longest a session may last from the time it is written. `0` or unset means no cap.
+    #[state]
+    pub max_session_duration: StateValue<i64>,
+
+    /// Most entries per batch call. Unset means [`DEFAULT_MAX_BATCH_SIZE`].
+    #[state]
+    pub max_batch_size: StateValue<u32>,
+
+    /// When `true`, sessions can't be written with an expiry that has already passed.
+    #[state]
+    pub reject_past_expiry: StateValue<bool>,
+
+    /// Emergency kill switch: while `true`, every enforce check fails.
+    ///
+    /// The counterpart of `expiry_offset`, toggled by the owner.
+    #[state]
+    pub paused: StateValue<bool>,
+
+    /// Number of wallets with a present session (see [`SessionRegistry::is_session_present`]).
+    ///
+    /// Counts presence rather than time-based activeness, which changes without a transaction.
+    #[state]
+    pub active_session_count: StateValue<u64>,
+
+    /// Wallets with a present session, in no particular order.
+    ///
+    /// Mirrors the present entries of `sessions`, which can't be enumerated.
+    #[state]
+    pub session_wallets: StateVec<S::Address>,
+
+    /// Position of each wallet in `session_wallets`, for constant-time removal.
+    #[state]
+    pub session_wallet_index: StateMap<S::Address, u64>,
+
+    /// The latest `SetExpiryOffset` changes, oldest first, capped at [`MAX_OFFSET_HISTORY`].
+    #[state]
+    pub expiry_offset_history: StateVec<ExpiryOffsetChange<S>>,
+
+    /// Whether `ImportSessions` may still run. Set at genesis and cleared by the first import.
+    #[state]
+    pub import_allowed: StateValue<bool>,
 }
 
 impl<S: Spec> Module for SessionRegistry<S> {
@@ -88,18 +261,90 @@ impl<S: Spec> Module for SessionRegistry<S> {
     /// Initialize module state at genesis.
     ///
     /// Values are taken from the [`RegistryConfig`] provided in the
-    /// rollup’s genesis configuration.
+    /// rollup’s genesis configuration. Fails with
+    /// [`SessionRegistryError::GenesisAlreadyInitialized`] if the registry
+    /// already has an owner, rather than silently overwriting live state.
     fn genesis(
         &mut self,
         _header: &<S::Da as sov_modules_api::DaSpec>::BlockHeader,
         config: &Self::Config,
         state: &mut impl GenesisState<S>,
     ) -> anyhow::Result<()> {
+        config.validate()?;
+        if self.owner.get(state)?.is_some() {
+            return Err(SessionRegistryError::GenesisAlreadyInitialized.into());
+        }
         self.owner.set(&config.owner, state)?;
+        let mut owner_count = 0u64;
+        for owner in std::iter::once(&config.owner).chain(&config.owners) {
+            // an owner listed twice counts once
+            if self.owners.get(owner, state)?.is_none() {
+                self.owners.set(owner, &true, state)?;
+                owner_count += 1;
+            }
+        }
+        self.owner_count.set(&owner_count, state)?;
         self.manager.set(&config.manager, state)?;
         self.enforcement_enabled
             .set(&config.enforcement_enabled, state)?;
         self.expiry_offset.set(&config.expiry_offset, state)?;
+        self.min_expiry_offset
+            .set(&config.min_expiry_offset, state)?;
+        self.require_funded_manager
+            .set(&config.require_funded_manager, state)?;
+        self.emit_enforcement_events
+            .set(&config.emit_enforcement_events, state)?;
+        self.emit_bypass_events
+            .set(&config.emit_bypass_events, state)?;
+        if let Some(max_renewals) = config.max_renewals {
+            self.max_renewals.set(&max_renewals, state)?;
+        }
+        if let Some(max_sessions) = config.max_sessions_per_signer {
+            self.max_sessions_per_signer.set(&max_sessions, state)?;
+        }
+        self.signer_cannot_be_wallet
+            .set(&config.signer_cannot_be_wallet, state)?;
+        self.signers_frozen.set(&config.signers_frozen, state)?;
+        for signer in &config.session_signers {
+            let was_allowed = self
+                .session_signers
+                .get(signer, state)?
+                .is_some_and(|authorization| authorization.allowed);
+            self.session_signers
+                .set(signer, &SignerAuthorization::permanent(true), state)?;
+            if !was_allowed {
+                self.session_signer_list.push(signer, state)?;
+            }
+        }
+        let mut present = 0u64;
+        for (wallet, session) in &config.sessions {
+            // a wallet listed twice keeps its last entry
+            match (
+                is_present(self.sessions.get(wallet, state)?.as_ref()),
+                is_present(Some(session)),
+            ) {
+                (false, true) => present += 1,
+                (true, false) => present -= 1,
+                _ => {}
+            }
+            self.sessions.set(wallet, session, state)?;
+        }
+        self.active_session_count.set(&present, state)?;
+        for (wallet, _) in &config.sessions {
+            let indexed = self.session_wallet_index.get(wallet, state)?.is_some();
+            if !indexed && is_present(self.sessions.get(wallet, state)?.as_ref()) {
+                let len = self.session_wallets.len(state)?;
+                self.session_wallet_index.set(wallet, &len, state)?;
+                self.session_wallets.push(wallet, state)?;
+            }
+        }
+        self.time_unit.set(&config.time_unit, state)?;
+        self.max_session_duration
+            .set(&config.max_session_duration, state)?;
+        self.max_batch_size.set(&config.max_batch_size, state)?;
+        self.reject_past_expiry
+            .set(&config.reject_past_expiry, state)?;
+        self.import_allowed.set(&true, state)?;
         Ok(())
     }
 
@@ -107,13 +352,16 @@ impl<S: Spec> Module for SessionRegistry<S> {
     ///
     /// This delegates to the `call::execute` function, which implements
     /// the routing and access control for all [`CallMessage`] variants.
+    /// Registry errors are returned as [`CodedSessionRegistryError`], so their code reaches
+    /// the transaction receipt next to the message. Downcast a failed call's error to that type,
+    /// not to [`SessionRegistryError`].
     fn call(
         &mut self,
         msg: Self::CallMessage,
         context: &Context<Self::Spec>,
         state: &mut impl TxState<S>,
     ) -> Result<(), Self::Error> {
-        call::execute(self, msg, context, state)
+        call::execute(self, msg, context, state).map_err(error::with_error_code)
     }
 }
 
@@ -131,42 +379,183 @@ impl<S: Spec> SessionRegistry<S> {
         wallet: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<bool> {
+        self.is_session_active_ro(wallet, state)
+    }
+
+    /// Read-only variant of [`is_session_active`] for callers without a
+    /// transaction, such as REST handlers.
+    ///
+    /// Reads still take `&mut` since state access is metered.
+    pub fn is_session_active_ro<Reader>(
+        &self,
+        wallet: &S::Address,
+        state: &mut Reader,
+    ) -> anyhow::Result<bool>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(self.active_via_ro(wallet, state)?.is_some())
+    }
+
+    /// Returns how the wallet's session is active, or `None` if it is not.
+    ///
+    /// See [`is_session_active`] for the activeness rules.
+    pub fn active_via(
+        &self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<Option<ActiveVia>> {
+        self.active_via_ro(wallet, state)
+    }
+
+    fn active_via_ro<Reader>(
+        &self,
+        wallet: &S::Address,
+        state: &mut Reader,
+    ) -> anyhow::Result<Option<ActiveVia>>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
         if let Some(session) = self.sessions.get(wallet, state)? {
             if session.bypass {
-                return Ok(true);
+                return Ok(Some(ActiveVia::Bypass));
             }
 
-            let effective_expiry_ts =
-                session.expiry_ts + self.expiry_offset.get(state)?.unwrap_or(0);
-
-            let now: Time = self.chain_state.get_time(state)?;
-            let now_ts = now.secs();
-
-            if effective_expiry_ts > now_ts {
-                return Ok(true);
+            if self.is_timed_active(&session, state)? {
+                return Ok(Some(ActiveVia::Timed));
             }
         }
 
-        Ok(false)
+        Ok(None)
+    }
+
+    /// Returns `true` if the session's effective expiry is in the future, ignoring `bypass`.
+    fn is_timed_active<Reader>(&self, session: &Session, state: &mut Reader) -> anyhow::Result<bool>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        let effective_expiry_ts = self.effective_expiry_ts(session, state)?;
+        let now_ts = self.now_ts(state)?;
+
+        Ok(effective_expiry_ts > now_ts)
     }
 
     /// Require that the wallet has an active session.
     ///
     /// Returns `Ok(())` if the session is active according to
-    /// [`is_session_active`], or an error otherwise.
+    /// [`is_session_active`], or an error otherwise. If
+    /// `emit_enforcement_events` is enabled, a successful check emits
+    /// [`Event::EnforcementPassed`]. Skipped while enforcement is off, unless
+    /// the wallet is force-enforced, emitting [`Event::EnforcementBypassed`] if
+    /// `emit_bypass_events` is enabled. Always fails while the registry is paused.
     pub fn enforce_session_active(
         &self,
         wallet: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<()> {
-        if !self.enforcement_enabled.get(state)?.unwrap_or(true) {
-            return Ok(());
+        self.enforce_session_active_with_reason(wallet, state)
+            .map(|_| ())
+    }
+
+    /// Like [`enforce_session_active`], returning why the check passed so the
+    /// caller can log it: the wallet's bypass flag, its timed session, or
+    /// enforcement being off for it.
+    pub fn enforce_session_active_with_reason(
+        &self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<ActivationReason> {
+        self.ensure_not_paused(state)?;
+
+        if !self.enforced_for(wallet, state)? {
+            self.skip_enforcement(wallet, EnforceKind::Active, state)?;
+            return Ok(ActivationReason::EnforcementDisabled);
         }
 
-        if self.is_session_active(wallet, state)? {
-            Ok(())
+        Ok(self.require_active(wallet, state)?.into())
+    }
+
+    /// Returns the enforcement mode of `scope`.
+    ///
+    /// Scopes without an explicit mode use the global `enforcement_enabled` flag.
+    pub fn enforcement_mode(
+        &self,
+        scope: &ScopeId,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<EnforcementMode> {
+        if let Some(mode) = self.enforcement_scopes.get(scope, state)? {
+            return Ok(mode);
+        }
+
+        Ok(if self.enforcement_on(state)? {
+            EnforcementMode::Enforced
         } else {
-            Err(SessionRegistryError::SessionNotActive.into())
+            EnforcementMode::Disabled
+        })
+    }
+
+    /// Require that the wallet has an active session if `scope` is enforced.
+    ///
+    /// Like [`enforce_session_active`], but gated by the scope's
+    /// [`EnforcementMode`] instead of the global flag. Scopes without an
+    /// explicit mode follow the global flag and the wallet's force-enforce
+    /// override, like the unscoped checks.
+    pub fn enforce_session_active_for_scope(
+        &self,
+        wallet: &S::Address,
+        scope: &ScopeId,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        self.ensure_not_paused(state)?;
+
+        let enforced = match self.enforcement_scopes.get(scope, state)? {
+            Some(mode) => mode == EnforcementMode::Enforced,
+            None => self.enforced_for(wallet, state)?,
+        };
+        if !enforced {
+            return self.skip_enforcement(wallet, EnforceKind::Active, state);
+        }
+
+        self.require_active(wallet, state).map(|_| ())
+    }
+
+    /// Fails unless the wallet has an active session, emitting
+    /// [`Event::EnforcementPassed`] on success if enabled. Returns how the
+    /// session is active.
+    ///
+    /// A wallet under [`WalletPolicy::StrictTimed`] needs a timed session; its
+    /// `bypass` flag is not honored.
+    fn require_active(
+        &self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<ActiveVia> {
+        let active_via = match self.wallet_policies.get(wallet, state)? {
+            Some(WalletPolicy::StrictTimed) => match self.sessions.get(wallet, state)? {
+                Some(session) if self.is_timed_active(&session, state)? => Some(ActiveVia::Timed),
+                _ => None,
+            },
+            None => self.active_via(wallet, state)?,
+        };
+
+        match active_via {
+            Some(via) => {
+                if self.emit_enforcement_events.get(state)?.unwrap_or(false) {
+                    self.emit_event(
+                        state,
+                        Event::EnforcementPassed {
+                            wallet: wallet.clone(),
+                            via,
+                        },
+                    );
+                }
+
+                Ok(via)
+            }
+            None => Err(SessionRegistryError::SessionNotActive.into()),
         }
     }
 
@@ -180,25 +569,94 @@ impl<S: Spec> SessionRegistry<S> {
         wallet: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<bool> {
-        let session_opt = self.sessions.get(wallet, state)?;
+        self.is_session_present_ro(wallet, state)
+    }
 
-        Ok(match session_opt {
-            Some(session) => session.bypass || session.expiry_ts != 0,
-            None => false,
-        })
+    /// Read-only variant of [`is_session_present`] for callers without a
+    /// transaction.
+    pub fn is_session_present_ro<Reader>(
+        &self,
+        wallet: &S::Address,
+        state: &mut Reader,
+    ) -> anyhow::Result<bool>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(is_present(self.sessions.get(wallet, state)?.as_ref()))
+    }
+
+    /// Returns the number of wallets with a present session.
+    ///
+    /// Maintained on every session write, so it is cheap to read; see
+    /// [`is_session_present`] for what counts as present.
+    pub fn active_session_count<Reader>(&self, state: &mut Reader) -> anyhow::Result<u64>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(self.active_session_count.get(state)?.unwrap_or(0))
+    }
+
+    /// Returns the wallets whose effective expiry falls within `[now, now + window_secs]`, so
+    /// backends can renew them before they lapse.
+    ///
+    /// Bypassed sessions never lapse and are left out. This walks every present session, so it
+    /// costs O(n) reads in the number of sessions; at most [`MAX_EXPIRING_SESSIONS`] wallets
+    /// are returned. Fails with [`SessionRegistryError::InvalidTtl`] for a negative window.
+    pub fn sessions_expiring_within(
+        &self,
+        window_secs: i64,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<Vec<S::Address>> {
+        let now_ts = self.now_ts(state)?;
+        let horizon_ts = self
+            .time_unit(state)?
+            .from_secs(window_secs)
+            .filter(|_| window_secs >= 0)
+            .map(|window| now_ts.saturating_add(window))
+            .ok_or(SessionRegistryError::InvalidTtl)?;
+
+        let mut wallets = Vec::new();
+        let len = self.session_wallets.len(state)?;
+        for index in 0..len {
+            if wallets.len() == MAX_EXPIRING_SESSIONS {
+                break;
+            }
+            let Some(wallet) = self.session_wallets.get(index, state)? else {
+                continue;
+            };
+            let Some(session) = self.sessions.get(&wallet, state)? else {
+                continue;
+            };
+            if session.bypass {
+                continue;
+            }
+            let effective_expiry_ts = self.effective_expiry_ts(&session, state)?;
+            if (now_ts..=horizon_ts).contains(&effective_expiry_ts) {
+                wallets.push(wallet);
+            }
+        }
+
+        Ok(wallets)
     }
 
     /// Require that a session is present (i.e. not deleted) for a wallet.
     ///
     /// Returns `Ok(())` if a session is present according to
-    /// [`is_session_present`], or an error otherwise.
+    /// [`is_session_present`], or an error otherwise. Skipped while
+    /// enforcement is off, unless the wallet is force-enforced, emitting
+    /// [`Event::EnforcementBypassed`] if `emit_bypass_events` is enabled.
+    /// Always fails while the registry is paused.
     pub fn enforce_session_present(
         &self,
         wallet: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<()> {
-        if !self.enforcement_enabled.get(state)?.unwrap_or(true) {
-            return Ok(());
+        self.ensure_not_paused(state)?;
+
+        if !self.enforced_for(wallet, state)? {
+            return self.skip_enforcement(wallet, EnforceKind::Present, state);
         }
 
         if self.is_session_present(wallet, state)? {
@@ -208,8 +666,201 @@ impl<S: Spec> SessionRegistry<S> {
         }
     }
 
+    /// Emits [`Event::SessionChecked`] with whether the wallet's session is
+    /// active and present, without failing either way.
+    ///
+    /// Unlike the enforce checks, the result ignores the enforcement flags and
+    /// pause, so a client can probe a wallet's status with a transaction.
+    pub fn check_session_active(
+        &self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        let active = self.is_session_active(wallet, state)?;
+        let present = self.is_session_present(wallet, state)?;
+
+        self.emit_event(
+            state,
+            Event::SessionChecked {
+                wallet: wallet.clone(),
+                active,
+                present,
+            },
+        );
+
+        Ok(())
+    }
+
     /// --- Helpers ---
 
+    /// Returns the global expiry offset, `0` if it was never set.
+    pub fn offset<Reader>(&self, state: &mut Reader) -> anyhow::Result<i64>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(self.expiry_offset.get(state)?.unwrap_or(0))
+    }
+
+    /// Returns the recorded expiry offset changes, oldest first.
+    ///
+    /// Only the latest [`MAX_OFFSET_HISTORY`] changes are kept.
+    pub fn offset_history<Reader>(
+        &self,
+        state: &mut Reader,
+    ) -> anyhow::Result<Vec<ExpiryOffsetChange<S>>>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        let len = self.expiry_offset_history.len(state)?;
+        let mut changes = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            if let Some(change) = self.expiry_offset_history.get(index, state)? {
+                changes.push(change);
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Appends `change` to `expiry_offset_history`, dropping the oldest change once the
+    /// history holds [`MAX_OFFSET_HISTORY`] of them.
+    pub(crate) fn record_offset_change(
+        &mut self,
+        change: &ExpiryOffsetChange<S>,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        let len = self.expiry_offset_history.len(state)?;
+        if len < MAX_OFFSET_HISTORY {
+            self.expiry_offset_history.push(change, state)?;
+            return Ok(());
+        }
+
+        // shift every change down one slot, overwriting the oldest
+        for index in 1..len {
+            if let Some(newer) = self.expiry_offset_history.get(index, state)? {
+                self.expiry_offset_history.set(index - 1, &newer, state)?;
+            }
+        }
+        self.expiry_offset_history.set(len - 1, change, state)?;
+
+        Ok(())
+    }
+
+    /// Returns the wallet's session expiry extended by the global offset, or `None` if the
+    /// wallet has no session.
+    ///
+    /// This is the expiry [`is_session_active`] compares against chain time; `bypass` is
+    /// ignored.
+    pub fn effective_expiry<Reader>(
+        &self,
+        wallet: &S::Address,
+        state: &mut Reader,
+    ) -> anyhow::Result<Option<i64>>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        match self.sessions.get(wallet, state)? {
+            Some(session) => Ok(Some(self.effective_expiry_ts(&session, state)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the session's expiry extended by the global offset, as used for activeness.
+    ///
+    /// Saturates instead of wrapping, so a large emergency offset can't turn a far-future
+    /// expiry into a past one.
+    pub(crate) fn effective_expiry_ts<Reader>(
+        &self,
+        session: &Session,
+        state: &mut Reader,
+    ) -> anyhow::Result<i64>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(session.expiry_ts.saturating_add(self.offset(state)?))
+    }
+
+    /// Returns the unit of session expiries, seconds if it was never set.
+    pub fn time_unit<Reader>(&self, state: &mut Reader) -> anyhow::Result<TimeUnit>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        Ok(self.time_unit.get(state)?.unwrap_or_default())
+    }
+
+    /// Returns the current chain time in the registry's [`TimeUnit`].
+    pub fn now_ts<Reader>(&self, state: &mut Reader) -> anyhow::Result<i64>
+    where
+        Reader: StateReader<User>,
+        anyhow::Error: From<Reader::Error>,
+    {
+        let now: Time = self.chain_state.get_time(state)?;
+        Ok(self.time_unit(state)?.from_time(&now))
+    }
+
+    /// Returns the most wallets a session batch may contain.
+    pub fn max_batch_size(&self, state: &mut impl TxState<S>) -> anyhow::Result<u32> {
+        Ok(self
+            .max_batch_size
+            .get(state)?
+            .unwrap_or(DEFAULT_MAX_BATCH_SIZE))
+    }
+
+    /// Returns whether global enforcement is on.
+    ///
+    /// Genesis always sets the flag, so an unset flag is a misconfiguration and fails with
+    /// [`SessionRegistryError::EnforcementFlagNotInitialized`] instead of picking a default.
+    pub fn enforcement_on(&self, state: &mut impl TxState<S>) -> anyhow::Result<bool> {
+        self.enforcement_enabled
+            .get(state)?
+            .ok_or_else(|| SessionRegistryError::EnforcementFlagNotInitialized.into())
+    }
+
+    /// Fails with [`SessionRegistryError::RegistryPaused`] while the registry is paused.
+    fn ensure_not_paused(&self, state: &mut impl TxState<S>) -> anyhow::Result<()> {
+        if self.paused.get(state)?.unwrap_or(false) {
+            return Err(SessionRegistryError::RegistryPaused.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether unscoped checks are enforced for `wallet`: either globally, or because
+    /// the wallet is force-enforced.
+    fn enforced_for(
+        &self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<bool> {
+        Ok(self.enforcement_on(state)? || self.force_enforce.get(wallet, state)?.unwrap_or(false))
+    }
+
+    /// Passes a `check` skipped because enforcement is off for `wallet`, emitting
+    /// [`Event::EnforcementBypassed`] if enabled.
+    fn skip_enforcement(
+        &self,
+        wallet: &S::Address,
+        check: EnforceKind,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        if self.emit_bypass_events.get(state)?.unwrap_or(false) {
+            self.emit_event(
+                state,
+                Event::EnforcementBypassed {
+                    wallet: wallet.clone(),
+                    check,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     /// Returns `true` if the given sender is the configured manager.
     ///
     /// # Errors
@@ -224,21 +875,36 @@ impl<S: Spec> SessionRegistry<S> {
         Ok(sender == &manager)
     }
 
-    /// Returns `true` if the given sender is the configured owner.
+    /// Returns `true` if the given sender is one of the `owners`.
     ///
     /// # Errors
     ///
     /// - Returns an error if the owner has not been initialized in state.
     fn is_owner(&self, sender: &S::Address, state: &mut impl TxState<S>) -> anyhow::Result<bool> {
-        let owner = self
-            .owner
-            .get(state)?
-            .ok_or(SessionRegistryError::OwnerNotInitialized)?;
+        if self.owner.get(state)?.is_none() {
+            return Err(SessionRegistryError::OwnerNotInitialized.into());
+        }
+
+        Ok(self.owners.get(sender, state)?.unwrap_or(false))
+    }
+
+    /// Returns `true` if the given address holds a nonzero balance of the gas token.
+    ///
+    /// Used as a proxy for "this is a known account that can pay for transactions".
+    fn is_funded_account(
+        &self,
+        address: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<bool> {
+        let balance = self
+            .bank
+            .get_balance_of(address, sov_bank::config_gas_token_id(), state)?;
 
-        Ok(sender == &owner)
+        Ok(balance.is_some_and(|balance| balance > Amount::ZERO))
     }
 
-    /// Returns `true` if the given address is configured as a session signer.
+    /// Returns `true` if the given address is configured as a session signer and its
+    /// authorization hasn't expired at the current chain time.
     ///
     /// Absence in the map is treated as `false`.
     fn is_session_signer(
@@ -246,7 +912,181 @@ impl<S: Spec> SessionRegistry<S> {
         signer: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<bool> {
-        Ok(self.session_signers.get(signer, state)?.unwrap_or(false))
+        match self.session_signers.get(signer, state)? {
+            Some(authorization) => Ok(authorization.is_valid_at(self.now_ts(state)?)),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns every address currently allowed as a session signer, in no particular order.
+    ///
+    /// Signers whose authorization has expired are left out.
+    pub fn list_session_signers(
+        &self,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<Vec<S::Address>> {
+        let len = self.session_signer_list.len(state)?;
+        let mut signers = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            if let Some(signer) = self.session_signer_list.get(index, state)? {
+                if self.is_session_signer(&signer, state)? {
+                    signers.push(signer);
+                }
+            }
+        }
+
+        Ok(signers)
+    }
+
+    /// Returns every present session with its wallet, in no particular order.
+    ///
+    /// This walks `session_wallets`, so it costs O(n) reads in the number of sessions.
+    pub fn list_sessions(
+        &self,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<Vec<(S::Address, Session)>> {
+        let len = self.session_wallets.len(state)?;
+        let mut sessions = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            let Some(wallet) = self.session_wallets.get(index, state)? else {
+                continue;
+            };
+            if let Some(session) = self.sessions.get(&wallet, state)? {
+                sessions.push((wallet, session));
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    /// Grant or revoke session-signer privileges, keeping `session_signer_list` in sync.
+    ///
+    /// A grant lapses at `expires_at`, or never if it is `0`.
+    fn set_session_signer(
+        &mut self,
+        signer: &S::Address,
+        allowed: bool,
+        expires_at: i64,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        let was_allowed = self
+            .session_signers
+            .get(signer, state)?
+            .is_some_and(|authorization| authorization.allowed);
+        let authorization = SignerAuthorization {
+            allowed,
+            expires_at: if allowed { expires_at } else { 0 },
+        };
+        self.session_signers.set(signer, &authorization, state)?;
+
+        if allowed && !was_allowed {
+            self.session_signer_list.push(signer, state)?;
+        } else if !allowed && was_allowed {
+            // swap-remove: move the last signer into the revoked one's slot
+            let len = self.session_signer_list.len(state)?;
+            for index in 0..len {
+                if self.session_signer_list.get(index, state)?.as_ref() != Some(signer) {
+                    continue;
+                }
+                let last = self
+                    .session_signer_list
+                    .pop(state)?
+                    .expect("Signer list is non-empty while it contains the signer");
+                if index + 1 < len {
+                    self.session_signer_list.set(index, &last, state)?;
+                }
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `session` as the wallet's record, or removes the record if `None`, keeping
+    /// `active_session_count` and `session_wallets` in sync when the wallet's session appears
+    /// or disappears.
+    pub(crate) fn store_session(
+        &mut self,
+        wallet: &S::Address,
+        session: Option<&Session>,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        let was_present = is_present(self.sessions.get(wallet, state)?.as_ref());
+        match session {
+            Some(session) => self.sessions.set(wallet, session, state)?,
+            None => self.sessions.remove(wallet, state)?,
+        }
+
+        let count = self.active_session_count(state)?;
+        match (was_present, is_present(session)) {
+            (false, true) => {
+                self.active_session_count.set(&(count + 1), state)?;
+                let len = self.session_wallets.len(state)?;
+                self.session_wallet_index.set(wallet, &len, state)?;
+                self.session_wallets.push(wallet, state)?;
+            }
+            (true, false) => {
+                self.active_session_count
+                    .set(&count.saturating_sub(1), state)?;
+                self.unindex_session_wallet(wallet, state)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Drops `wallet` from `session_wallets`, moving the last wallet into its slot.
+    ///
+    /// Sessions written before the index existed aren't listed, and are skipped.
+    fn unindex_session_wallet(
+        &mut self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        let Some(index) = self.session_wallet_index.get(wallet, state)? else {
+            return Ok(());
+        };
+        self.session_wallet_index.remove(wallet, state)?;
+
+        let last = self
+            .session_wallets
+            .pop(state)?
+            .expect("Session wallet list is non-empty while it indexes the wallet");
+        if &last != wallet {
+            self.session_wallets.set(index, &last, state)?;
+            self.session_wallet_index.set(&last, &index, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete a wallet's session record, bypass included, releasing it from its signer's
+    /// `max_sessions_per_signer` quota.
+    pub(crate) fn remove_session(
+        &mut self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        self.store_session(wallet, None, state)?;
+        self.release_session_origin(wallet, state)
+    }
+
+    /// Detach a wallet's session from the signer that created it, releasing it from that
+    /// signer's `max_sessions_per_signer` quota.
+    pub(crate) fn release_session_origin(
+        &mut self,
+        wallet: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> anyhow::Result<()> {
+        if let Some(origin) = self.session_origins.get(wallet, state)? {
+            self.session_origins.remove(wallet, state)?;
+            let held = self.sessions_per_signer.get(&origin, state)?.unwrap_or(0);
+            self.sessions_per_signer
+                .set(&origin, &held.saturating_sub(1), state)?;
+        }
+
+        Ok(())
     }
 
     /// Create, update, or delete the session for a wallet.
@@ -255,33 +1095,90 @@ impl<S: Spec> SessionRegistry<S> {
     /// - Otherwise, a new `Session` is written with expiry_ts = expires_at
     ///   and `bypass` either retained from any existing session or set to
     ///   `false` if none exists.
+    /// - Updating an existing timed session counts as a renewal and fails with
+    ///   [`SessionRegistryError::SessionRenewalLimitReached`] once `max_renewals`
+    ///   is reached.
+    /// - With a nonzero `max_session_duration`, an expiry past
+    ///   `now + max_session_duration` fails with
+    ///   [`SessionRegistryError::SessionTooLong`].
+    /// - With `reject_past_expiry` set, an expiry at or before the current
+    ///   chain time fails with [`SessionRegistryError::ExpiryInPast`].
+    /// - A session without an origin is attributed to `signer`, failing with
+    ///   [`SessionRegistryError::SignerSessionLimitReached`] once the signer
+    ///   holds `max_sessions_per_signer` sessions. Clearing a session releases
+    ///   it from its origin's count.
+    /// - `metadata` replaces the session's metadata, an empty tag clearing it;
+    ///   `None` keeps any existing metadata.
     fn write_session(
         &mut self,
         wallet: &S::Address,
         expires_at: i64,
+        metadata: Option<Vec<u8>>,
+        signer: &S::Address,
         state: &mut impl TxState<S>,
     ) -> anyhow::Result<()> {
         if expires_at == 0 {
-            self.sessions.remove(wallet, state)?;
+            self.remove_session(wallet, state)?;
 
             self.emit_event(
                 state,
-                Event::SessionSet {
+                Event::SessionCleared {
                     wallet: wallet.clone(),
-                    expiry_ts: 0,
                 },
             );
         } else {
-            // retain existing bypass flag if any
+            let now_ts = self.now_ts(state)?;
+            let max_duration = self.max_session_duration.get(state)?.unwrap_or(0);
+            if max_duration > 0 && expires_at > now_ts.saturating_add(max_duration) {
+                return Err(SessionRegistryError::SessionTooLong.into());
+            }
+            if self.reject_past_expiry.get(state)?.unwrap_or(false) && expires_at <= now_ts {
+                return Err(SessionRegistryError::ExpiryInPast.into());
+            }
+
+            // retain existing bypass flag, creation time and metadata if any
             let existing = self.sessions.get(wallet, state)?;
-            let bypass = existing.map(|s| s.bypass).unwrap_or(false);
+            let bypass = existing.as_ref().map(|s| s.bypass).unwrap_or(false);
+            let created_ts = existing.as_ref().map(|s| s.created_ts).unwrap_or(now_ts);
+            let metadata = match metadata {
+                Some(metadata) => Some(metadata).filter(|metadata| !metadata.is_empty()),
+                None => existing.as_ref().and_then(|s| s.metadata.clone()),
+            };
+
+            // a bypass-only record has no timed session to renew
+            let renewals = match existing.filter(|s| s.expiry_ts != 0) {
+                Some(existing) => {
+                    if let Some(max_renewals) = self.max_renewals.get(state)? {
+                        if existing.renewals >= max_renewals {
+                            return Err(SessionRegistryError::SessionRenewalLimitReached.into());
+                        }
+                    }
+                    existing.renewals + 1
+                }
+                None => 0,
+            };
+
+            // renewals keep the original signer; only new sessions count against a signer
+            if self.session_origins.get(wallet, state)?.is_none() {
+                let held = self.sessions_per_signer.get(signer, state)?.unwrap_or(0);
+                if let Some(max_sessions) = self.max_sessions_per_signer.get(state)? {
+                    if held >= max_sessions {
+                        return Err(SessionRegistryError::SignerSessionLimitReached.into());
+                    }
+                }
+                self.sessions_per_signer.set(signer, &(held + 1), state)?;
+                self.session_origins.set(wallet, signer, state)?;
+            }
 
             let session = Session {
                 expiry_ts: expires_at,
                 bypass,
+                renewals,
+                created_ts,
+                metadata,
             };
 
-            self.sessions.set(wallet, &session, state)?;
+            self.store_session(wallet, Some(&session), state)?;
 
             self.emit_event(
                 state,
@@ -295,3 +1192,8 @@ impl<S: Spec> SessionRegistry<S> {
         Ok(())
     }
 }
+
+/// Whether `session` is present: it exists and is either bypassed or has an expiry.
+fn is_present(session: Option<&Session>) -> bool {
+    session.is_some_and(|session| session.bypass || session.expiry_ts != 0)
+}
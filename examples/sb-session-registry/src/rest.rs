@@ -0,0 +1,810 @@
+//! REST API for the `SessionRegistry` module.
+//!
+//! Routes are mounted under `/modules/session-registry`.
+
+use axum::extract::{Json, Path, Query};
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::rest::{ApiState, HasCustomRestApi};
+use sov_modules_api::{ApiStateAccessor, Spec};
+use sov_rest_utils::{errors, ApiResult, ResponseObject};
+use sov_state::{SlotKey, Storage, StorageProof};
+
+use crate::{
+    CallMessage, ExpiryOffsetChange, Session, SessionRegistry, SessionRegistryError, MODULE_VERSION,
+};
+
+/// When a compiled feature is reported as enabled by `GET /version`.
+#[derive(Debug, Clone, Copy)]
+enum FeatureSwitch {
+    /// On in every deployment.
+    Always,
+    /// `max_renewals` is set.
+    MaxRenewals,
+    /// `signer_cannot_be_wallet` is on.
+    SignerCannotBeWallet,
+    /// `require_funded_manager` is on.
+    FundedManager,
+    /// `emit_enforcement_events` is on.
+    EnforcementEvents,
+    /// The session signers are frozen.
+    SignersFrozen,
+    /// `max_session_duration` is positive.
+    MaxSessionDuration,
+    /// `reject_past_expiry` is on.
+    RejectPastExpiry,
+    /// The registry is paused.
+    Paused,
+    /// `max_sessions_per_signer` is set.
+    MaxSessionsPerSigner,
+    /// `emit_bypass_events` is on.
+    BypassEvents,
+    /// `min_expiry_offset` is above `i64::MIN`.
+    MinExpiryOffset,
+    /// `ImportSessions` is still allowed.
+    ImportAllowed,
+}
+
+/// Optional behaviors every build of this module supports, with what switches each on.
+///
+/// Every change that bumps [`MODULE_VERSION`] for a new behavior adds it here.
+const FEATURES: &[(&str, FeatureSwitch)] = &[
+    ("session_proofs", FeatureSwitch::Always),
+    ("force_expire", FeatureSwitch::Always),
+    ("uniform_ttl_batch", FeatureSwitch::Always),
+    ("renewal_limits", FeatureSwitch::MaxRenewals),
+    (
+        "signer_cannot_be_wallet",
+        FeatureSwitch::SignerCannotBeWallet,
+    ),
+    ("funded_manager_guard", FeatureSwitch::FundedManager),
+    ("enforcement_events", FeatureSwitch::EnforcementEvents),
+    ("signer_freeze", FeatureSwitch::SignersFrozen),
+    ("enforcement_scopes", FeatureSwitch::Always),
+    ("session_duration_cap", FeatureSwitch::MaxSessionDuration),
+    ("past_expiry_guard", FeatureSwitch::RejectPastExpiry),
+    ("emergency_pause", FeatureSwitch::Paused),
+    ("signer_session_limit", FeatureSwitch::MaxSessionsPerSigner),
+    ("bypass_events", FeatureSwitch::BypassEvents),
+    ("offset_floor", FeatureSwitch::MinExpiryOffset),
+    ("borsh_responses", FeatureSwitch::Always),
+    ("wallet_policies", FeatureSwitch::Always),
+    ("time_units", FeatureSwitch::Always),
+    ("simulate", FeatureSwitch::Always),
+    ("signer_batches", FeatureSwitch::Always),
+    ("session_status", FeatureSwitch::Always),
+    ("manager_handoff", FeatureSwitch::Always),
+    ("batch_size_cap", FeatureSwitch::Always),
+    ("signer_list", FeatureSwitch::Always),
+    ("force_enforce", FeatureSwitch::Always),
+    ("extend_session", FeatureSwitch::Always),
+    ("session_created_ts", FeatureSwitch::Always),
+    ("session_count", FeatureSwitch::Always),
+    ("expiring_signers", FeatureSwitch::Always),
+    ("purge_wallet", FeatureSwitch::Always),
+    ("error_codes", FeatureSwitch::Always),
+    ("historical_status", FeatureSwitch::Always),
+    ("expiring_sessions", FeatureSwitch::Always),
+    ("session_metadata", FeatureSwitch::Always),
+    ("check_session_active", FeatureSwitch::Always),
+    ("offset_history", FeatureSwitch::Always),
+    ("self_revoke", FeatureSwitch::Always),
+    ("genesis_validation", FeatureSwitch::Always),
+    ("owner_set", FeatureSwitch::Always),
+    ("session_import", FeatureSwitch::ImportAllowed),
+];
+
+/// Names of [`FEATURES`], in order.
+const fn feature_names<const N: usize>() -> [&'static str; N] {
+    let mut names = [""; N];
+    let mut i = 0;
+    while i < N {
+        names[i] = FEATURES[i].0;
+        i += 1;
+    }
+    names
+}
+
+/// Optional behaviors every build of this module supports, reported by `GET /version`.
+pub const COMPILED_FEATURES: &[&str] = &feature_names::<{ FEATURES.len() }>();
+
+/// Storage proof for a single `sessions` entry.
+pub type SessionProof<S> = StorageProof<<<S as Spec>::Storage as Storage>::Proof>;
+
+/// Content type of borsh-encoded responses.
+pub const BORSH_CONTENT_TYPE: &str = "application/x-borsh";
+
+/// Encoding of the `GET /sessions/{wallet}` response body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// A [`SessionResponse`] as JSON.
+    #[default]
+    Json,
+    /// The borsh-encoded [`Session`] alone.
+    Borsh,
+}
+
+impl ResponseFormat {
+    /// Picks the format from `?format=`, falling back to the `Accept` header and then JSON.
+    pub fn negotiate(requested: Option<ResponseFormat>, accept: Option<&str>) -> Self {
+        if let Some(format) = requested {
+            return format;
+        }
+        let accepts_borsh = accept.is_some_and(|accept| {
+            accept
+                .split(',')
+                .any(|media| media.split(';').next().unwrap_or("").trim() == BORSH_CONTENT_TYPE)
+        });
+        if accepts_borsh {
+            ResponseFormat::Borsh
+        } else {
+            ResponseFormat::Json
+        }
+    }
+}
+
+/// Query parameters of `GET /sessions/{wallet}`.
+#[derive(Debug, Default, Deserialize)]
+pub struct SessionQuery {
+    /// Also return a storage proof of the session against the current state root.
+    #[serde(default)]
+    pub with_proof: bool,
+    /// Response encoding. Overrides the `Accept` header when set.
+    #[serde(default)]
+    pub format: Option<ResponseFormat>,
+}
+
+/// Response of `GET /sessions/{wallet}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SessionResponse<S: Spec> {
+    pub wallet: S::Address,
+    /// The raw session record, or `None` if the wallet has none.
+    pub session: Option<Session>,
+    /// Present when requested with `?with_proof=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<SessionProof<S>>,
+    /// The session's own `expiry_ts`, or `None` if the wallet has no session.
+    #[serde(default)]
+    pub nominal_expiry_ts: Option<i64>,
+    /// `nominal_expiry_ts` extended by the global `expiry_offset`; this is what activeness uses.
+    #[serde(default)]
+    pub effective_expiry_ts: Option<i64>,
+    /// `true` if the session has expired nominally and is only active because of the offset.
+    #[serde(default)]
+    pub active_only_due_to_offset: bool,
+}
+
+/// Response of `GET /wallets/{wallet}/status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SessionStatus<S: Spec> {
+    pub wallet: S::Address,
+    /// Whether the wallet passes `is_session_active` at `now_ts`.
+    pub active: bool,
+    /// Whether the wallet passes `is_session_present`.
+    pub present: bool,
+    /// The session's raw `expiry_ts`, or `None` if the wallet has no session.
+    pub expiry_ts: Option<i64>,
+    /// `expiry_ts` extended by the global `expiry_offset`.
+    pub effective_expiry_ts: Option<i64>,
+    /// The session's bypass flag, `false` if the wallet has no session.
+    pub bypass: bool,
+    /// The chain time the status was computed at, in the registry's time unit.
+    pub now_ts: i64,
+}
+
+/// Query parameters of `GET /wallets/{wallet}/historical-status`.
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoricalStatusQuery {
+    /// Slot whose state the status is computed at; the latest slot when unset.
+    ///
+    /// Read by the node's [`ApiStateAccessor`] extractor, which serves the archival state of
+    /// that slot.
+    #[serde(default)]
+    pub slot_number: Option<u64>,
+}
+
+/// Response of `GET /wallets/{wallet}/historical-status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HistoricalSessionStatus<S: Spec> {
+    /// The requested slot, or `None` for the latest one.
+    pub slot_number: Option<u64>,
+    /// The wallet's status at that slot, `now_ts` being that slot's chain time.
+    #[serde(flatten)]
+    pub status: SessionStatus<S>,
+}
+
+/// Why `GET /sessions/{wallet}` could not return a session.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionQueryError {
+    /// The path segment does not parse as an address. Served as 400.
+    #[error("{input:?} is not a valid address: {reason}")]
+    InvalidAddress { input: String, reason: String },
+    /// The address is well-formed but has no session. Served as 404.
+    #[error("No session for {wallet}")]
+    NotFound { wallet: String },
+    /// Proofs are only served as JSON. Served as 400.
+    #[error("Storage proofs are only available in the JSON format")]
+    ProofNotEncodable,
+    /// The registry state needed for the response could not be read. Served as 500.
+    #[error("Failed to read the registry state: {reason}")]
+    StateUnavailable { reason: String },
+}
+
+/// Body of `POST /simulate`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SimulateRequest<S: Spec> {
+    /// The address the call is simulated as. Not authenticated.
+    pub sender: S::Address,
+    pub call: CallMessage<S>,
+}
+
+/// Response of `POST /simulate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateResponse {
+    /// Whether the call would succeed against the current state.
+    pub success: bool,
+    /// The error the call would fail with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The [`SessionRegistryError::code`] of `error`, if it is a registry error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u32>,
+}
+
+/// Response of `GET /sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SessionsResponse<S: Spec> {
+    /// Every present session as a `[wallet, session]` pair, in no particular order.
+    pub sessions: Vec<(S::Address, Session)>,
+}
+
+/// Response of `GET /signers`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SignersResponse<S: Spec> {
+    /// Every address currently allowed as a session signer, in no particular order.
+    pub signers: Vec<S::Address>,
+}
+
+/// Response of `GET /session-count`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCountResponse {
+    /// Number of wallets with a present session, see [`SessionRegistry::active_session_count`].
+    pub active_session_count: u64,
+}
+
+/// Query parameters of `GET /expiring-sessions`.
+#[derive(Debug, Deserialize)]
+pub struct ExpiringSessionsQuery {
+    /// Width of the window after now, in seconds.
+    pub within_secs: i64,
+}
+
+/// Response of `GET /expiring-sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ExpiringSessionsResponse<S: Spec> {
+    /// Wallets whose effective expiry falls within the window, in no particular order, see
+    /// [`SessionRegistry::sessions_expiring_within`].
+    pub wallets: Vec<S::Address>,
+}
+
+/// Response of `GET /expiry-offset/history`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ExpiryOffsetHistoryResponse<S: Spec> {
+    /// The latest expiry offset changes, oldest first, see
+    /// [`SessionRegistry::offset_history`].
+    pub changes: Vec<ExpiryOffsetChange<S>>,
+}
+
+/// Response of `GET /version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionResponse {
+    /// Semantic version of the module, see [`MODULE_VERSION`].
+    pub version: String,
+    /// Optional behaviors this build supports, see [`COMPILED_FEATURES`].
+    pub features: Vec<String>,
+    /// The subset of `features` that are switched on in this deployment's state.
+    pub enabled: Vec<String>,
+}
+
+impl<S: Spec> SessionRegistry<S> {
+    /// Describes the module version and which optional behaviors are compiled in and enabled.
+    pub fn version_info(&self, state: &mut ApiStateAccessor<S>) -> VersionResponse {
+        let enabled = FEATURES
+            .iter()
+            .filter(|(_, switch)| self.is_switched_on(*switch, state))
+            .map(|(feature, _)| feature.to_string())
+            .collect();
+
+        VersionResponse {
+            version: MODULE_VERSION.to_string(),
+            features: COMPILED_FEATURES.iter().map(|f| f.to_string()).collect(),
+            enabled,
+        }
+    }
+
+    /// Whether `switch` is on in the current state.
+    fn is_switched_on(&self, switch: FeatureSwitch, state: &mut ApiStateAccessor<S>) -> bool {
+        match switch {
+            FeatureSwitch::Always => true,
+            FeatureSwitch::MaxRenewals => {
+                self.max_renewals.get(state).unwrap_infallible().is_some()
+            }
+            FeatureSwitch::SignerCannotBeWallet => self
+                .signer_cannot_be_wallet
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::FundedManager => self
+                .require_funded_manager
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::EnforcementEvents => self
+                .emit_enforcement_events
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::SignersFrozen => self
+                .signers_frozen
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::MaxSessionDuration => self
+                .max_session_duration
+                .get(state)
+                .unwrap_infallible()
+                .is_some_and(|max| max > 0),
+            FeatureSwitch::RejectPastExpiry => self
+                .reject_past_expiry
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::Paused => self.paused.get(state).unwrap_infallible().unwrap_or(false),
+            FeatureSwitch::MaxSessionsPerSigner => self
+                .max_sessions_per_signer
+                .get(state)
+                .unwrap_infallible()
+                .is_some(),
+            FeatureSwitch::BypassEvents => self
+                .emit_bypass_events
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            FeatureSwitch::MinExpiryOffset => self
+                .min_expiry_offset
+                .get(state)
+                .unwrap_infallible()
+                .is_some_and(|min| min > i64::MIN),
+            FeatureSwitch::ImportAllowed => self
+                .import_allowed
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+        }
+    }
+
+    /// Dry-runs `call` as `sender` against the current state.
+    ///
+    /// Runs the same authorization and validation as a transaction. The accessor never commits,
+    /// so any writes and events are discarded with it.
+    pub fn simulate_call(
+        &self,
+        sender: &S::Address,
+        call: CallMessage<S>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> SimulateResponse {
+        let mut module = self.clone();
+        match crate::call::execute_as(&mut module, call, sender, state) {
+            Ok(()) => SimulateResponse {
+                success: true,
+                error: None,
+                code: None,
+            },
+            Err(e) => SimulateResponse {
+                success: false,
+                error: Some(e.to_string()),
+                code: e
+                    .downcast_ref::<SessionRegistryError>()
+                    .map(SessionRegistryError::code),
+            },
+        }
+    }
+
+    /// Reads the wallet's session together with a storage proof of the entry.
+    pub fn session_with_proof(
+        &self,
+        wallet: &S::Address,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<SessionResponse<S>, SessionQueryError> {
+        let session = self.sessions.get(wallet, state).unwrap_infallible();
+        let proof = self.sessions.get_with_proof(wallet, state);
+
+        self.session_response(wallet.clone(), session, Some(proof), state)
+    }
+
+    /// Builds the response for `session`, including how the global offset affects its expiry.
+    fn session_response(
+        &self,
+        wallet: S::Address,
+        session: Option<Session>,
+        proof: Option<SessionProof<S>>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<SessionResponse<S>, SessionQueryError> {
+        let state_unavailable = |e: anyhow::Error| SessionQueryError::StateUnavailable {
+            reason: e.to_string(),
+        };
+
+        let (nominal_expiry_ts, effective_expiry_ts, active_only_due_to_offset) = match &session {
+            Some(session) => {
+                let effective = self
+                    .effective_expiry_ts(session, state)
+                    .map_err(state_unavailable)?;
+                let now_ts = self.now_ts(state).map_err(state_unavailable)?;
+                let propped_up =
+                    !session.bypass && session.expiry_ts <= now_ts && effective > now_ts;
+                (Some(session.expiry_ts), Some(effective), propped_up)
+            }
+            None => (None, None, false),
+        };
+
+        Ok(SessionResponse {
+            wallet,
+            session,
+            proof,
+            nominal_expiry_ts,
+            effective_expiry_ts,
+            active_only_due_to_offset,
+        })
+    }
+
+    /// Looks up the session of the wallet at `wallet`, as served by `GET /sessions/{wallet}`.
+    ///
+    /// A missing session is [`SessionQueryError::NotFound`] unless a proof is requested, in which
+    /// case the response proves its absence.
+    pub fn query_session(
+        &self,
+        wallet: &str,
+        query: &SessionQuery,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<SessionResponse<S>, SessionQueryError> {
+        let wallet =
+            wallet
+                .parse::<S::Address>()
+                .map_err(|e| SessionQueryError::InvalidAddress {
+                    input: wallet.to_string(),
+                    reason: e.to_string(),
+                })?;
+
+        if query.with_proof {
+            return self.session_with_proof(&wallet, state);
+        }
+
+        match self.sessions.get(&wallet, state).unwrap_infallible() {
+            Some(session) => self.session_response(wallet, Some(session), None, state),
+            None => Err(SessionQueryError::NotFound {
+                wallet: wallet.to_string(),
+            }),
+        }
+    }
+
+    /// Computes the session status of the wallet at `wallet`, as served by
+    /// `GET /wallets/{wallet}/status`.
+    ///
+    /// Chain time is read once, so `active` is consistent with `effective_expiry_ts` and
+    /// `now_ts`. A wallet without a session is reported as neither active nor present.
+    pub fn wallet_status(
+        &self,
+        wallet: &str,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<SessionStatus<S>, SessionQueryError> {
+        let wallet =
+            wallet
+                .parse::<S::Address>()
+                .map_err(|e| SessionQueryError::InvalidAddress {
+                    input: wallet.to_string(),
+                    reason: e.to_string(),
+                })?;
+        let state_unavailable = |e: anyhow::Error| SessionQueryError::StateUnavailable {
+            reason: e.to_string(),
+        };
+
+        let session = self.sessions.get(&wallet, state).unwrap_infallible();
+        let now_ts = self.now_ts(state).map_err(state_unavailable)?;
+        let effective_expiry_ts = session
+            .as_ref()
+            .map(|session| self.effective_expiry_ts(session, state))
+            .transpose()
+            .map_err(state_unavailable)?;
+
+        let (active, present, bypass) = match &session {
+            Some(session) => (
+                session.bypass || effective_expiry_ts.is_some_and(|ts| ts > now_ts),
+                session.bypass || session.expiry_ts != 0,
+                session.bypass,
+            ),
+            None => (false, false, false),
+        };
+
+        Ok(SessionStatus {
+            wallet,
+            active,
+            present,
+            expiry_ts: session.map(|session| session.expiry_ts),
+            effective_expiry_ts,
+            bypass,
+            now_ts,
+        })
+    }
+
+    /// Computes the session status of the wallet at `wallet` at a past slot, as served by
+    /// `GET /wallets/{wallet}/historical-status?slot_number=..`.
+    ///
+    /// `state` must be the archival state of `slot_number`, chain time included, so
+    /// `active` and `now_ts` are what the chain saw back then. The route gets it from the
+    /// node's [`ApiStateAccessor`] extractor, which turns down slots the node has no state for
+    /// before the handler runs; every state it does serve includes the registry's genesis.
+    pub fn historical_wallet_status(
+        &self,
+        wallet: &str,
+        slot_number: Option<u64>,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Result<HistoricalSessionStatus<S>, SessionQueryError> {
+        Ok(HistoricalSessionStatus {
+            slot_number,
+            status: self.wallet_status(wallet, state)?,
+        })
+    }
+
+    async fn route_historical_status(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(wallet): Path<String>,
+        Query(query): Query<HistoricalStatusQuery>,
+    ) -> Response {
+        match state.historical_wallet_status(&wallet, query.slot_number, &mut accessor) {
+            Ok(status) => ResponseObject::from(status).into_response(),
+            Err(e @ SessionQueryError::StateUnavailable { .. }) => {
+                errors::internal_server_error_response_500(e).into_response()
+            }
+            Err(e) => errors::bad_request_400("Invalid wallet address", e).into_response(),
+        }
+    }
+
+    async fn route_wallet_status(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(wallet): Path<String>,
+    ) -> Response {
+        match state.wallet_status(&wallet, &mut accessor) {
+            Ok(status) => ResponseObject::from(status).into_response(),
+            Err(e @ SessionQueryError::StateUnavailable { .. }) => {
+                errors::internal_server_error_response_500(e).into_response()
+            }
+            Err(e) => errors::bad_request_400("Invalid wallet address", e).into_response(),
+        }
+    }
+
+    async fn route_session(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(wallet): Path<String>,
+        Query(query): Query<SessionQuery>,
+        headers: HeaderMap,
+    ) -> Response {
+        let accept = headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok());
+        let format = ResponseFormat::negotiate(query.format, accept);
+
+        let encoded = state
+            .query_session(&wallet, &query, &mut accessor)
+            .and_then(|response| encode_session_response(response, format));
+        match encoded {
+            Ok(EncodedSession::Json(response)) => ResponseObject::from(response).into_response(),
+            Ok(EncodedSession::Borsh(bytes)) => {
+                ([(header::CONTENT_TYPE, BORSH_CONTENT_TYPE)], bytes).into_response()
+            }
+            Err(e @ SessionQueryError::InvalidAddress { .. }) => {
+                errors::bad_request_400("Invalid wallet address", e).into_response()
+            }
+            Err(e @ SessionQueryError::ProofNotEncodable) => {
+                errors::bad_request_400("Unsupported response format", e).into_response()
+            }
+            Err(SessionQueryError::NotFound { wallet }) => {
+                errors::not_found_404("Session", wallet).into_response()
+            }
+            Err(e @ SessionQueryError::StateUnavailable { .. }) => {
+                errors::internal_server_error_response_500(e).into_response()
+            }
+        }
+    }
+
+    async fn route_simulate(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Json(request): Json<SimulateRequest<S>>,
+    ) -> ApiResult<SimulateResponse> {
+        Ok(state
+            .simulate_call(&request.sender, request.call, &mut accessor)
+            .into())
+    }
+
+    async fn route_sessions(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<SessionsResponse<S>> {
+        let sessions = state
+            .list_sessions(&mut accessor)
+            .map_err(errors::internal_server_error_response_500)?;
+
+        Ok(SessionsResponse { sessions }.into())
+    }
+
+    async fn route_signers(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<SignersResponse<S>> {
+        let signers = state
+            .list_session_signers(&mut accessor)
+            .map_err(errors::internal_server_error_response_500)?;
+
+        Ok(SignersResponse { signers }.into())
+    }
+
+    async fn route_session_count(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<SessionCountResponse> {
+        let active_session_count = state
+            .active_session_count(&mut accessor)
+            .map_err(errors::internal_server_error_response_500)?;
+
+        Ok(SessionCountResponse {
+            active_session_count,
+        }
+        .into())
+    }
+
+    async fn route_expiring_sessions(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Query(query): Query<ExpiringSessionsQuery>,
+    ) -> ApiResult<ExpiringSessionsResponse<S>> {
+        let wallets = state
+            .sessions_expiring_within(query.within_secs, &mut accessor)
+            .map_err(|e| {
+                if matches!(e.downcast_ref(), Some(SessionRegistryError::InvalidTtl)) {
+                    errors::bad_request_400("Invalid window", e)
+                } else {
+                    errors::internal_server_error_response_500(e)
+                }
+            })?;
+
+        Ok(ExpiringSessionsResponse { wallets }.into())
+    }
+
+    async fn route_offset_history(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<ExpiryOffsetHistoryResponse<S>> {
+        let changes = state
+            .offset_history(&mut accessor)
+            .map_err(errors::internal_server_error_response_500)?;
+
+        Ok(ExpiryOffsetHistoryResponse { changes }.into())
+    }
+
+    async fn route_version(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<VersionResponse> {
+        Ok(state.version_info(&mut accessor).into())
+    }
+}
+
+/// A `GET /sessions/{wallet}` response in its negotiated [`ResponseFormat`].
+#[derive(Debug, Clone)]
+pub enum EncodedSession<S: Spec> {
+    Json(SessionResponse<S>),
+    Borsh(Vec<u8>),
+}
+
+/// Encodes a session response for `format`.
+///
+/// The borsh encoding carries only the [`Session`], so responses with a proof or without a
+/// session can't be encoded as borsh.
+pub fn encode_session_response<S: Spec>(
+    response: SessionResponse<S>,
+    format: ResponseFormat,
+) -> Result<EncodedSession<S>, SessionQueryError> {
+    match format {
+        ResponseFormat::Json => Ok(EncodedSession::Json(response)),
+        ResponseFormat::Borsh => {
+            if response.proof.is_some() {
+                return Err(SessionQueryError::ProofNotEncodable);
+            }
+            let session = response
+                .session
+                .ok_or_else(|| SessionQueryError::NotFound {
+                    wallet: response.wallet.to_string(),
+                })?;
+            Ok(EncodedSession::Borsh(
+                borsh::to_vec(&session).expect("Serializing a session to a vector never fails"),
+            ))
+        }
+    }
+}
+
+/// Checks that a [`SessionResponse`] is backed by `state_root` instead of trusting the node.
+///
+/// The proof must open against `state_root`, be for the wallet's key in the `sessions` map, and
+/// prove exactly the returned session (including its absence).
+pub fn verify_session_proof<S: Spec>(
+    response: &SessionResponse<S>,
+    state_root: &<S::Storage as Storage>::Root,
+) -> anyhow::Result<()> {
+    let proof = response
+        .proof
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Response does not contain a proof"))?;
+
+    let registry = SessionRegistry::<S>::default();
+    let expected_key = SlotKey::new(
+        registry.sessions.prefix(),
+        &response.wallet,
+        registry.sessions.codec(),
+    );
+
+    let (key, value) = S::Storage::open_proof(state_root.clone(), proof)?;
+    anyhow::ensure!(
+        key == expected_key,
+        "Proof is not for the session of {}",
+        response.wallet
+    );
+
+    let proven: Option<Session> = value
+        .map(|value| borsh::from_slice(value.value()))
+        .transpose()?;
+    anyhow::ensure!(
+        proven == response.session,
+        "Proven session {:?} does not match the returned session {:?}",
+        proven,
+        response.session
+    );
+
+    Ok(())
+}
+
+impl<S: Spec> HasCustomRestApi for SessionRegistry<S> {
+    type Spec = S;
+
+    fn custom_rest_api(&self, state: ApiState<S>) -> axum::Router<()> {
+        axum::Router::new()
+            .route("/sessions", get(Self::route_sessions))
+            .route("/sessions/:wallet", get(Self::route_session))
+            .route("/wallets/:wallet/status", get(Self::route_wallet_status))
+            .route(
+                "/wallets/:wallet/historical-status",
+                get(Self::route_historical_status),
+            )
+            .route("/signers", get(Self::route_signers))
+            .route("/session-count", get(Self::route_session_count))
+            .route("/expiring-sessions", get(Self::route_expiring_sessions))
+            .route("/expiry-offset/history", get(Self::route_offset_history))
+            .route("/version", get(Self::route_version))
+            .route("/simulate", post(Self::route_simulate))
+            .with_state(state.with(self.clone()))
+    }
+}
@@ -1,9 +1,29 @@
 //! Types used by the `SessionRegistry` module.
 
 use schemars::JsonSchema;
+use sov_modules_api::da::Time;
 use sov_modules_api::macros::serialize;
 use sov_modules_api::Spec;
 
+use crate::SessionRegistryError;
+
+/// Default for [`RegistryConfig::max_batch_size`].
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 256;
+
+/// Most wallets [`sessions_expiring_within`](crate::SessionRegistry::sessions_expiring_within)
+/// returns.
+pub const MAX_EXPIRING_SESSIONS: usize = 1000;
+
+/// Largest [`Session::metadata`] a signer may attach, in bytes.
+pub const MAX_SESSION_METADATA_BYTES: usize = 64;
+
+/// Most changes the registry keeps in its expiry offset history; older ones are dropped.
+pub const MAX_OFFSET_HISTORY: u64 = 32;
+
+/// Furthest a genesis [`RegistryConfig::expiry_offset`] may pull session expiries backward, in
+/// seconds. Anything beyond a year is a misconfiguration rather than an emergency measure.
+pub const MAX_GENESIS_NEGATIVE_OFFSET_SECS: i64 = 365 * 24 * 60 * 60;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[serialize(Serde)]
 #[serde(rename_all = "snake_case")]
@@ -11,6 +31,10 @@ pub struct RegistryConfig<S: Spec> {
     /// Has authority for changing `manager` and toggling enforcement.
     pub owner: S::Address,
 
+    /// Further addresses sharing the owner's authority from genesis on, see `SetOwner`.
+    #[serde(default)]
+    pub owners: Vec<S::Address>,
+
     /// Can set session signers, and manage per-wallet bypass behavior.
     pub manager: S::Address,
 
@@ -21,13 +45,158 @@ pub struct RegistryConfig<S: Spec> {
     /// Offset to extend all active session expiries by a fixed amount.
     /// Used in emergencies if backend services are down and need to extend sessions.
     pub expiry_offset: i64,
+
+    /// When `true`, `SetManager` rejects addresses with no gas token balance.
+    /// Opt-in, so genesis configs and tests using fresh addresses keep working.
+    #[serde(default)]
+    pub require_funded_manager: bool,
+
+    /// When `true`, successful active-session enforcement emits `EnforcementPassed`.
+    /// Off by default since it adds an event to every gated operation.
+    #[serde(default)]
+    pub emit_enforcement_events: bool,
+
+    /// Maximum number of times a session can be renewed before a signer has to
+    /// clear and recreate it. `None` allows unlimited renewals.
+    #[serde(default)]
+    pub max_renewals: Option<u32>,
+
+    /// Maximum number of sessions a single signer may have created and not yet cleared.
+    /// Contains the damage of a compromised signer. `None` means unlimited.
+    #[serde(default)]
+    pub max_sessions_per_signer: Option<u32>,
+
+    /// When `true`, a session signer can't set a session for its own address.
+    /// Keeps signers from self-authorizing arbitrarily long sessions.
+    #[serde(default)]
+    pub signer_cannot_be_wallet: bool,
+
+    /// Initial value of the signer freeze, see `SetSignersFrozen`.
+    #[serde(default)]
+    pub signers_frozen: bool,
+
+    /// Addresses allowed to act as session signers from genesis on, without expiry.
+    #[serde(default)]
+    pub session_signers: Vec<S::Address>,
+
+    /// Session records to seed at genesis, e.g. when restoring a snapshot of a live registry.
+    #[serde(default)]
+    pub sessions: Vec<(S::Address, Session)>,
+
+    /// Unit of `expiry_ts`, `expires_at` and `expiry_offset`. Fixed at genesis.
+    #[serde(default)]
+    pub time_unit: TimeUnit,
+
+    /// Longest a new or renewed session may last from now, in `time_unit`. `0` means no cap.
+    /// Limits how long-lived a session a compromised signer can hand out.
+    #[serde(default)]
+    pub max_session_duration: i64,
+
+    /// Most entries a single batch call (`SetSessionBatch`, `SetSessionBatchUniform`,
+    /// `SetSessionSignersBatch` or `ImportSessions`) may contain.
+    /// Bounds the gas and state writes of one transaction.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: u32,
+
+    /// When `true`, writing a session whose expiry is at or before the current chain time fails.
+    /// Off by default, so signers can still write already-expired sessions.
+    #[serde(default)]
+    pub reject_past_expiry: bool,
+
+    /// When `true`, an enforce check skipped because enforcement is off emits
+    /// `EnforcementBypassed`. Off by default to avoid an event on every gated operation.
+    #[serde(default)]
+    pub emit_bypass_events: bool,
+
+    /// Lowest offset `SetExpiryOffset` accepts, in `time_unit`. Caps how far the emergency
+    /// lever can pull sessions backward. `i64::MIN`, the default, means unrestricted.
+    #[serde(default = "default_min_expiry_offset")]
+    pub min_expiry_offset: i64,
+
+    /// When `true`, genesis fails if `manager` is also `owner` or one of `owners`.
+    /// Off by default, so single-key test deployments keep working.
+    #[serde(default)]
+    pub require_distinct_manager: bool,
+}
+
+impl<S: Spec> RegistryConfig<S> {
+    /// Checks the config for values that would silently deploy a broken registry.
+    ///
+    /// Fails with [`SessionRegistryError::InvalidGenesisConfig`] if `expiry_offset` is below
+    /// `min_expiry_offset` or pulls expiries back further than
+    /// [`MAX_GENESIS_NEGATIVE_OFFSET_SECS`], or if `require_distinct_manager` is set and the
+    /// manager is also an owner.
+    pub fn validate(&self) -> Result<(), SessionRegistryError> {
+        let invalid = |reason| Err(SessionRegistryError::InvalidGenesisConfig { reason });
+
+        let offset_floor = self
+            .time_unit
+            .from_secs(-MAX_GENESIS_NEGATIVE_OFFSET_SECS)
+            .unwrap_or(i64::MIN);
+        if self.expiry_offset < offset_floor {
+            return invalid("expiry_offset pulls sessions back by more than a year");
+        }
+        if self.expiry_offset < self.min_expiry_offset {
+            return invalid("expiry_offset is below min_expiry_offset");
+        }
+        if self.require_distinct_manager && self.owner == self.manager {
+            return invalid("owner and manager are the same address");
+        }
+        if self.require_distinct_manager && self.owners.contains(&self.manager) {
+            return invalid("manager is listed in owners");
+        }
+        Ok(())
+    }
+}
+
+fn default_max_batch_size() -> u32 {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
+fn default_min_expiry_offset() -> i64 {
+    i64::MIN
+}
+
+/// Unit in which session expiries are stored and compared against chain time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    /// Whole seconds since the epoch.
+    #[default]
+    Seconds,
+
+    /// Milliseconds since the epoch, using the sub-second precision of the DA time.
+    Milliseconds,
+}
+
+impl TimeUnit {
+    /// Expresses `time` in this unit.
+    pub fn from_time(self, time: &Time) -> i64 {
+        match self {
+            TimeUnit::Seconds => time.secs(),
+            TimeUnit::Milliseconds => time
+                .secs()
+                .saturating_mul(1000)
+                .saturating_add(i64::from(time.subsec_nanos() / 1_000_000)),
+        }
+    }
+
+    /// Converts a duration in seconds to this unit, or `None` on overflow.
+    pub fn from_secs(self, secs: i64) -> Option<i64> {
+        match self {
+            TimeUnit::Seconds => Some(secs),
+            TimeUnit::Milliseconds => secs.checked_mul(1000),
+        }
+    }
 }
 
 /// Per-wallet session state.
 #[derive(Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serialize(Borsh, Serde)]
 pub struct Session {
-    /// Session expiry timestamp (seconds since epoch, as provided by DA time).
+    /// Session expiry timestamp since the epoch, as provided by DA time, in the registry's
+    /// [`TimeUnit`] (seconds by default).
     pub expiry_ts: i64,
 
     /// If `true`, this wallet bypasses normal session expiry checks.
@@ -35,4 +204,157 @@ pub struct Session {
     /// A bypassed wallet is treated as always having an active
     /// and present session.
     pub bypass: bool,
+
+    /// Number of times the expiry has been updated since the session was created.
+    ///
+    /// Reset to `0` when the session is cleared and created again. `0` for records seeded
+    /// without it.
+    #[serde(default)]
+    pub renewals: u32,
+
+    /// Chain time at which the record was first written, in the registry's [`TimeUnit`].
+    ///
+    /// Kept across renewals and bypass changes; a session that is cleared and created again
+    /// gets a new one. `0` for records seeded without it.
+    #[serde(default)]
+    pub created_ts: i64,
+
+    /// Opaque tag attached by the signer for offchain correlation, e.g. a device id hash.
+    ///
+    /// At most [`MAX_SESSION_METADATA_BYTES`] long. Kept across `SetSession` renewals and
+    /// dropped with the record.
+    #[serde(default)]
+    pub metadata: Option<Vec<u8>>,
+}
+
+/// One `SetExpiryOffset` change, as recorded in the expiry offset history.
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(bound = "S: Spec")]
+#[schemars(bound = "S: Spec")]
+pub struct ExpiryOffsetChange<S: Spec> {
+    /// The offset before the change, `None` if it was never set.
+    pub old_offset: Option<i64>,
+
+    /// The offset after the change.
+    pub new_offset: i64,
+
+    /// The owner that made the change.
+    pub updated_by: S::Address,
+
+    /// Chain time of the change, in the registry's [`TimeUnit`].
+    pub updated_at_ts: i64,
+}
+
+/// Session-signer authorization of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+pub struct SignerAuthorization {
+    /// Whether the address was granted session-signer privileges.
+    pub allowed: bool,
+
+    /// Chain time after which the authorization lapses, in the registry's [`TimeUnit`].
+    /// `0` means it never expires.
+    pub expires_at: i64,
+}
+
+impl SignerAuthorization {
+    /// Authorization that never expires.
+    pub fn permanent(allowed: bool) -> Self {
+        Self {
+            allowed,
+            expires_at: 0,
+        }
+    }
+
+    /// Whether the authorization grants privileges at chain time `now_ts`.
+    pub fn is_valid_at(&self, now_ts: i64) -> bool {
+        self.allowed && (self.expires_at == 0 || self.expires_at > now_ts)
+    }
+}
+
+/// Name of an enforcement scope, e.g. `trade` or `withdraw`.
+///
+/// Dependent modules pick a scope per operation type and pass it to
+/// `enforce_session_active_for_scope`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
+#[serialize(Borsh, Serde)]
+pub struct ScopeId(pub String);
+
+impl ScopeId {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+/// Whether operations in a scope require an active session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum EnforcementMode {
+    /// Operations in the scope are not gated.
+    Disabled,
+
+    /// Operations in the scope require an active session.
+    Enforced,
+}
+
+/// Per-wallet tightening of the active-session check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletPolicy {
+    /// Only a timed session satisfies the check; `bypass` is ignored.
+    StrictTimed,
+}
+
+/// Which enforce check was skipped, see `Event::EnforcementBypassed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum EnforceKind {
+    /// `enforce_session_active`.
+    Active,
+
+    /// `enforce_session_present`.
+    Present,
+}
+
+/// How a wallet satisfied an active-session check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveVia {
+    /// The wallet has `bypass == true`.
+    Bypass,
+
+    /// The wallet's effective expiry is in the future.
+    Timed,
+}
+
+/// Why an [`enforce_session_active_with_reason`] check passed.
+///
+/// [`enforce_session_active_with_reason`]:
+///     crate::SessionRegistry::enforce_session_active_with_reason
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serialize(Borsh, Serde)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivationReason {
+    /// The wallet has `bypass == true`.
+    Bypass,
+
+    /// The wallet's effective expiry is in the future.
+    TimeValid,
+
+    /// Enforcement is off for the wallet, so its session wasn't checked.
+    EnforcementDisabled,
+}
+
+impl From<ActiveVia> for ActivationReason {
+    fn from(via: ActiveVia) -> Self {
+        match via {
+            ActiveVia::Bypass => Self::Bypass,
+            ActiveVia::Timed => Self::TimeValid,
+        }
+    }
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 // Mock DEX module used for testing session enforcement APIs.
 
 mod test_dex {
@@ -8,7 +10,7 @@ mod test_dex {
         Context, GenesisState, Module, ModuleId, ModuleInfo, ModuleRestApi, Spec, TxState,
     };
 
-    use sb_session_registry::SessionRegistry;
+    use sb_session_registry::{ScopeId, SessionRegistry};
 
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[serialize(Serde)]
@@ -21,6 +23,7 @@ mod test_dex {
     pub enum DexCallMessage<S: Spec> {
         EnforceSessionActive { wallet: S::Address },
         EnforceSessionPresent { wallet: S::Address },
+        EnforceSessionActiveForScope { wallet: S::Address, scope: ScopeId },
     }
 
     #[derive(Clone, ModuleInfo, ModuleRestApi)]
@@ -61,9 +64,294 @@ mod test_dex {
                 DexCallMessage::EnforceSessionPresent { wallet } => self
                     .session_registry
                     .enforce_session_present(&wallet, state),
+                DexCallMessage::EnforceSessionActiveForScope { wallet, scope } => self
+                    .session_registry
+                    .enforce_session_active_for_scope(&wallet, &scope, state),
             }
         }
     }
 }
 
 pub use test_dex::{DexCallMessage, DexConfig, TestDex};
+
+// Shared test runtime and setup for the session registry tests.
+
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::Spec;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{
+    generate_optimistic_runtime, AsUser, TestSpec, TestUser, TransactionTestCase,
+};
+
+use sb_session_registry::{
+    CallMessage, Event, RegistryConfig, Session, SessionRegistry, TimeUnit, DEFAULT_MAX_BATCH_SIZE,
+};
+
+pub type S = TestSpec;
+
+generate_optimistic_runtime!(
+    TestRuntime <=
+    session_registry: SessionRegistry<S>,
+    test_dex: TestDex<S>
+);
+
+pub struct TestData<S: Spec> {
+    pub owner: TestUser<S>,
+    pub manager: TestUser<S>,
+    pub signer: TestUser<S>,
+    pub wallet: TestUser<S>,
+    pub wallet2: TestUser<S>,
+}
+
+pub fn setup() -> (TestData<S>, TestRunner<TestRuntime<S>, S>) {
+    setup_with_config(|_| {})
+}
+
+/// Same as [`setup`], but lets the caller adjust the registry genesis config.
+pub fn setup_with_config(
+    configure: impl FnOnce(&mut RegistryConfig<S>),
+) -> (TestData<S>, TestRunner<TestRuntime<S>, S>) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(5);
+
+    let mut users = genesis_config.additional_accounts().to_vec();
+    let wallet2 = users.pop().expect("second wallet user");
+    let wallet = users.pop().expect("wallet user");
+    let signer = users.pop().expect("signer user");
+    let manager = users.pop().expect("manager user");
+    let owner = users.pop().expect("owner user");
+
+    let test_data = TestData {
+        owner,
+        manager,
+        signer,
+        wallet,
+        wallet2,
+    };
+
+    let mut registry_config = RegistryConfig::<S> {
+        owner: test_data.owner.address(),
+        owners: vec![],
+        manager: test_data.manager.address(),
+        enforcement_enabled: true,
+        expiry_offset: 0,
+        require_funded_manager: false,
+        emit_enforcement_events: false,
+        max_renewals: None,
+        max_sessions_per_signer: None,
+        signer_cannot_be_wallet: false,
+        signers_frozen: false,
+        session_signers: vec![],
+        sessions: vec![],
+        time_unit: TimeUnit::Seconds,
+        max_session_duration: 0,
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        reject_past_expiry: false,
+        emit_bypass_events: false,
+        min_expiry_offset: i64::MIN,
+        require_distinct_manager: false,
+    };
+    configure(&mut registry_config);
+
+    let dex_config = DexConfig {};
+
+    let genesis =
+        GenesisConfig::from_minimal_config(genesis_config.into(), registry_config, dex_config);
+
+    let runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), TestRuntime::default());
+
+    (test_data, runner)
+}
+
+/// Reads a wallet's raw session record from the latest state.
+pub fn read_session(
+    runner: &mut TestRunner<TestRuntime<S>, S>,
+    wallet: &<S as Spec>::Address,
+) -> Option<Session> {
+    runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .sessions
+            .get(wallet, state)
+            .unwrap_infallible()
+    })
+}
+
+/// Captures the registry state of `runner` as a genesis config that recreates it.
+///
+/// State maps can't be iterated, so sessions and signers are only captured for `addresses`.
+pub fn snapshot_registry(
+    runner: &mut TestRunner<TestRuntime<S>, S>,
+    addresses: &[<S as Spec>::Address],
+) -> RegistryConfig<S> {
+    runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let flag = |value: Option<bool>| value.unwrap_or(false);
+
+        let owner = registry.owner.get(state).unwrap_infallible().unwrap();
+
+        RegistryConfig {
+            owners: addresses
+                .iter()
+                .filter(|address| {
+                    **address != owner
+                        && flag(registry.owners.get(*address, state).unwrap_infallible())
+                })
+                .cloned()
+                .collect(),
+            owner,
+            manager: registry.manager.get(state).unwrap_infallible().unwrap(),
+            enforcement_enabled: registry.enforcement_on(state).unwrap(),
+            expiry_offset: registry.offset(state).unwrap(),
+            require_funded_manager: flag(
+                registry
+                    .require_funded_manager
+                    .get(state)
+                    .unwrap_infallible(),
+            ),
+            emit_enforcement_events: flag(
+                registry
+                    .emit_enforcement_events
+                    .get(state)
+                    .unwrap_infallible(),
+            ),
+            max_renewals: registry.max_renewals.get(state).unwrap_infallible(),
+            max_sessions_per_signer: registry
+                .max_sessions_per_signer
+                .get(state)
+                .unwrap_infallible(),
+            signer_cannot_be_wallet: flag(
+                registry
+                    .signer_cannot_be_wallet
+                    .get(state)
+                    .unwrap_infallible(),
+            ),
+            signers_frozen: flag(registry.signers_frozen.get(state).unwrap_infallible()),
+            session_signers: addresses
+                .iter()
+                .filter(|address| {
+                    registry
+                        .session_signers
+                        .get(*address, state)
+                        .unwrap_infallible()
+                        .is_some_and(|authorization| authorization.allowed)
+                })
+                .cloned()
+                .collect(),
+            sessions: addresses
+                .iter()
+                .filter_map(|address| {
+                    let session = registry.sessions.get(address, state).unwrap_infallible()?;
+                    Some((address.clone(), session))
+                })
+                .collect(),
+            time_unit: registry.time_unit(state).unwrap(),
+            max_session_duration: registry
+                .max_session_duration
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(0),
+            max_batch_size: registry.max_batch_size(state).unwrap(),
+            reject_past_expiry: registry
+                .reject_past_expiry
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(false),
+            emit_bypass_events: flag(registry.emit_bypass_events.get(state).unwrap_infallible()),
+            min_expiry_offset: registry
+                .min_expiry_offset
+                .get(state)
+                .unwrap_infallible()
+                .unwrap_or(i64::MIN),
+            // Only checked at genesis, so not kept in state
+            require_distinct_manager: false,
+        }
+    })
+}
+
+/// Asserts that the listed session signers are `expected`, in any order.
+pub fn assert_session_signers(
+    runner: &mut TestRunner<TestRuntime<S>, S>,
+    expected: &[<S as Spec>::Address],
+) {
+    let signers = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .list_session_signers(state)
+            .unwrap()
+    });
+    assert_eq!(
+        signers.len(),
+        expected.len(),
+        "Listed signers: {:?}",
+        signers
+    );
+    for signer in expected {
+        assert!(signers.contains(signer), "{} should be listed", signer);
+    }
+}
+
+/// Starts a fresh runner whose registry genesis is `snapshot`, after a JSON round-trip to make
+/// sure the snapshot survives serialization.
+pub fn restore_registry(snapshot: &RegistryConfig<S>) -> TestRunner<TestRuntime<S>, S> {
+    let json = serde_json::to_string(snapshot).expect("snapshot serializes");
+    let snapshot: RegistryConfig<S> = serde_json::from_str(&json).expect("snapshot deserializes");
+
+    let (_, runner) = setup_with_config(|config| *config = snapshot);
+    runner
+}
+
+/// Extracts the session registry events from a transaction's runtime events.
+pub fn registry_events(events: &[TestRuntimeEvent<S>]) -> Vec<Event<S>> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TestRuntimeEvent::SessionRegistry(event) => Some(event.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sends `msg` to the session registry as `sender` and asserts on the outcome.
+pub fn execute_registry_call(
+    runner: &mut TestRunner<TestRuntime<S>, S>,
+    sender: &TestUser<S>,
+    msg: CallMessage<S>,
+    expect_success: bool,
+    description: &str,
+) {
+    let description = description.to_string();
+    runner.execute_transaction(TransactionTestCase {
+        input: sender.create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(msg),
+        assert: Box::new(move |result, _| {
+            assert_eq!(
+                result.tx_receipt.is_successful(),
+                expect_success,
+                "{}",
+                description
+            );
+        }),
+    });
+}
+
+/// Sends `msg` to the mock DEX as `sender` and asserts on the outcome.
+pub fn execute_dex_call(
+    runner: &mut TestRunner<TestRuntime<S>, S>,
+    sender: &TestUser<S>,
+    msg: DexCallMessage<S>,
+    expect_success: bool,
+    description: &str,
+) {
+    let description = description.to_string();
+    runner.execute_transaction(TransactionTestCase {
+        input: sender.create_plain_message::<TestRuntime<S>, TestDex<S>>(msg),
+        assert: Box::new(move |result, _| {
+            assert_eq!(
+                result.tx_receipt.is_successful(),
+                expect_success,
+                "{}",
+                description
+            );
+        }),
+    });
+}
@@ -0,0 +1,188 @@
+#![cfg(test)]
+
+use sb_session_registry::{CallMessage, ScopeId, WalletPolicy};
+
+mod common;
+use common::{execute_registry_call, setup};
+
+//
+// Owner calls – SetManager / ProposeManager / SetEnforcementEnabled / SetExpiryOffset /
+// SetSignersFrozen / SetEnforcementScope / SetPaused
+//
+// - Manager, signer and an arbitrary wallet attempt every owner call (should fail)
+// - Owner performs every owner call (should succeed)
+//
+#[test]
+fn test_owner_calls_authorization() {
+    let (test_data, mut runner) = setup();
+
+    let owner_calls = || {
+        vec![
+            CallMessage::SetManager {
+                new_manager: test_data.manager.address(),
+            },
+            CallMessage::ProposeManager {
+                new_manager: test_data.manager.address(),
+            },
+            CallMessage::SetEnforcementEnabled { enabled: true },
+            CallMessage::SetExpiryOffset { new_offset: 0 },
+            CallMessage::SetSignersFrozen { frozen: false },
+            CallMessage::SetEnforcementScope {
+                scope: ScopeId::new("trade"),
+                mode: None,
+            },
+            CallMessage::SetPaused { paused: false },
+        ]
+    };
+
+    for sender in [&test_data.manager, &test_data.signer, &test_data.wallet] {
+        for msg in owner_calls() {
+            execute_registry_call(
+                &mut runner,
+                sender,
+                msg,
+                false,
+                "Owner calls should be rejected for non-owners",
+            );
+        }
+    }
+
+    for msg in owner_calls() {
+        execute_registry_call(
+            &mut runner,
+            &test_data.owner,
+            msg,
+            true,
+            "Owner calls should succeed for the owner",
+        );
+    }
+}
+
+//
+// Manager calls – SetSessionSigner / SetSessionSignersBatch / SetSessionSignerBatch / SetBypass /
+// SetWalletPolicy / SetForceEnforce
+//
+// - Owner, signer and an arbitrary wallet attempt every manager call (should fail)
+// - Manager performs every manager call (should succeed)
+//
+#[test]
+fn test_manager_calls_authorization() {
+    let (test_data, mut runner) = setup();
+
+    let manager_calls = || {
+        vec![
+            CallMessage::SetSessionSigner {
+                signer: test_data.signer.address(),
+                allowed: true,
+                expires_at: 0,
+            },
+            CallMessage::SetSessionSignersBatch {
+                signers: vec![(test_data.wallet2.address(), false)],
+            },
+            CallMessage::SetSessionSignerBatch {
+                signers: vec![test_data.wallet2.address()],
+                allowed: vec![false],
+            },
+            CallMessage::SetBypass {
+                wallet: test_data.wallet.address(),
+                bypass: true,
+            },
+            CallMessage::SetWalletPolicy {
+                wallet: test_data.wallet2.address(),
+                policy: Some(WalletPolicy::StrictTimed),
+            },
+            CallMessage::SetForceEnforce {
+                wallet: test_data.wallet2.address(),
+                enabled: true,
+            },
+        ]
+    };
+
+    for sender in [&test_data.owner, &test_data.signer, &test_data.wallet] {
+        for msg in manager_calls() {
+            execute_registry_call(
+                &mut runner,
+                sender,
+                msg,
+                false,
+                "Manager calls should be rejected for non-managers",
+            );
+        }
+    }
+
+    for msg in manager_calls() {
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            msg,
+            true,
+            "Manager calls should succeed for the manager",
+        );
+    }
+}
+
+//
+// Signer calls – SetSession / SetSessionBatch / SetSessionBatchUniform / ExtendSession
+//
+// - Owner, manager and a not-yet-authorized signer attempt every signer call (should fail)
+// - Manager authorizes the signer, which then performs every signer call (should succeed)
+//
+#[test]
+fn test_signer_calls_authorization() {
+    let (test_data, mut runner) = setup();
+
+    let signer_calls = || {
+        vec![
+            CallMessage::SetSession {
+                wallet: test_data.wallet.address(),
+                expires_at: 2764177788,
+            },
+            CallMessage::SetSessionBatch {
+                wallets: vec![test_data.wallet2.address()],
+                expiries: vec![2764177788],
+            },
+            CallMessage::SetSessionBatchUniform {
+                wallets: vec![test_data.wallet2.address()],
+                ttl_secs: 3600,
+            },
+            CallMessage::ExtendSession {
+                wallet: test_data.wallet.address(),
+                additional_seconds: 60,
+            },
+        ]
+    };
+
+    for sender in [&test_data.owner, &test_data.manager, &test_data.signer] {
+        for msg in signer_calls() {
+            execute_registry_call(
+                &mut runner,
+                sender,
+                msg,
+                false,
+                "Signer calls should be rejected for non-signers",
+            );
+        }
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    for msg in signer_calls() {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            msg,
+            true,
+            "Signer calls should succeed for an authorized signer",
+        );
+    }
+}
@@ -1,69 +1,21 @@
 #![cfg(test)]
 
-use sov_modules_api::Spec;
-use sov_test_utils::{generate_optimistic_runtime, TestSpec};
-
-use sb_session_registry::{CallMessage, RegistryConfig, SessionRegistry};
-
-mod common;
-use common::{DexCallMessage, DexConfig, TestDex};
-
-type S = TestSpec;
-
-generate_optimistic_runtime!(
-    TestRuntime <=
-    session_registry: SessionRegistry<S>,
-    test_dex: TestDex<S>
-);
-
-use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
-use sov_test_utils::runtime::TestRunner;
+use sov_mock_da::MockBlockHeader;
+use sov_modules_api::{Module, Spec};
 use sov_test_utils::{AsUser, TestUser, TransactionTestCase};
 
-pub struct TestData<S: Spec> {
-    pub owner: TestUser<S>,
-    pub manager: TestUser<S>,
-    pub signer: TestUser<S>,
-    pub wallet: TestUser<S>,
-    pub wallet2: TestUser<S>,
-}
-
-pub fn setup() -> (TestData<S>, TestRunner<TestRuntime<S>, S>) {
-    let genesis_config =
-        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(5);
-
-    let mut users = genesis_config.additional_accounts().to_vec();
-    let wallet2 = users.pop().expect("second wallet user");
-    let wallet = users.pop().expect("wallet user");
-    let signer = users.pop().expect("signer user");
-    let manager = users.pop().expect("manager user");
-    let owner = users.pop().expect("owner user");
-
-    let test_data = TestData {
-        owner,
-        manager,
-        signer,
-        wallet,
-        wallet2,
-    };
-
-    let registry_config = RegistryConfig::<S> {
-        owner: test_data.owner.address(),
-        manager: test_data.manager.address(),
-        enforcement_enabled: true,
-        expiry_offset: 0,
-    };
-
-    let dex_config = DexConfig {};
+use sb_session_registry::{
+    ActiveVia, CallMessage, EnforceKind, EnforcementMode, Event, RegistryConfig, ScopeId,
+    SessionRegistry, SessionRegistryError, SignerAuthorization, TimeUnit, WalletPolicy,
+    DEFAULT_MAX_BATCH_SIZE,
+};
 
-    let genesis =
-        GenesisConfig::from_minimal_config(genesis_config.into(), registry_config, dex_config);
-
-    let runner =
-        TestRunner::new_with_genesis(genesis.into_genesis_params(), TestRuntime::default());
-
-    (test_data, runner)
-}
+mod common;
+use common::{
+    assert_session_signers, execute_dex_call, execute_registry_call, read_session, registry_events,
+    restore_registry, setup, setup_with_config, snapshot_registry, DexCallMessage, TestDex,
+    TestRuntime, S,
+};
 
 //
 // TEST 1 – basic signer / session lifecycle
@@ -110,6 +62,7 @@ fn test_1() {
             CallMessage::SetSessionSigner {
                 signer: signer_addr.clone(),
                 allowed: true,
+                expires_at: 0,
             },
         ),
         assert: Box::new(|result, _state| {
@@ -218,6 +171,7 @@ fn test_1() {
             CallMessage::SetSessionSigner {
                 signer: signer_addr.clone(),
                 allowed: true,
+                expires_at: 0,
             },
         ),
         assert: Box::new(|result, _state| {
@@ -274,6 +228,7 @@ fn test_2() {
             CallMessage::SetSessionSigner {
                 signer: signer_addr.clone(),
                 allowed: true,
+                expires_at: 0,
             },
         ),
         assert: Box::new(|result, _state| {
@@ -604,6 +559,7 @@ fn test_3() {
             CallMessage::SetSessionSigner {
                 signer: signer_addr.clone(),
                 allowed: true,
+                expires_at: 0,
             },
         ),
         assert: Box::new(|result, _| {
@@ -614,3 +570,5533 @@ fn test_3() {
         }),
     });
 }
+
+//
+// TEST – funded-manager guard on SetManager
+//
+// - Policy off: owner hands the manager role to an unknown address (should succeed)
+// - Policy on: owner hands the manager role to an unknown address (should fail)
+// - Policy on: owner hands the manager role to a funded genesis account (should succeed)
+//
+#[test]
+fn test_set_manager_funded_guard() {
+    let unknown_addr = TestUser::<S>::generate_with_default_balance().address();
+
+    let (test_data, mut runner) = setup();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::SetManager {
+                new_manager: unknown_addr.clone(),
+            }),
+        assert: Box::new(|result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetManager to an unknown address should succeed when the policy is off"
+            );
+        }),
+    });
+
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.require_funded_manager = true;
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::SetManager {
+                new_manager: unknown_addr.clone(),
+            }),
+        assert: Box::new(|result, _| {
+            assert!(
+                !result.tx_receipt.is_successful(),
+                "SetManager to an unfunded address should fail when the policy is on"
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::SetManager {
+                new_manager: test_data.wallet.address(),
+            }),
+        assert: Box::new(|result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetManager to a funded account should succeed when the policy is on"
+            );
+        }),
+    });
+}
+
+//
+// TEST – two-step manager handoff
+//
+// - Owner proposes wallet: ManagerProposed, manager unchanged
+// - wallet2 accepts (should fail: not the pending manager)
+// - Owner overwrites the proposal with wallet2; wallet can no longer accept
+// - wallet2 accepts: ManagerSet, and wallet2 acts as manager
+// - Accepting again fails since no proposal is pending
+//
+#[test]
+fn test_two_step_manager_handoff() {
+    let (test_data, mut runner) = setup();
+    let manager_addr = test_data.manager.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    let expected = Event::ManagerProposed {
+        new_manager: wallet_addr.clone(),
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::ProposeManager {
+                    new_manager: wallet_addr.clone(),
+                },
+            ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "ProposeManager should succeed for owner"
+            );
+            assert_eq!(registry_events(&result.events), vec![expected]);
+        }),
+    });
+
+    let (manager, pending) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.manager.get(state).unwrap(),
+            registry.pending_manager.get(state).unwrap(),
+        )
+    });
+    assert_eq!(manager, Some(manager_addr.clone()));
+    assert_eq!(pending, Some(wallet_addr.clone()));
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.wallet2,
+        CallMessage::AcceptManager,
+        false,
+        "AcceptManager should fail for an address that isn't pending",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::ProposeManager {
+            new_manager: wallet2_addr.clone(),
+        },
+        true,
+        "ProposeManager should replace the pending proposal",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.wallet,
+        CallMessage::AcceptManager,
+        false,
+        "AcceptManager should fail for a replaced proposal",
+    );
+
+    let expected = Event::ManagerSet {
+        old_manager: Some(manager_addr),
+        new_manager: wallet2_addr.clone(),
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .wallet2
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::AcceptManager),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "AcceptManager should succeed for the pending manager"
+            );
+            assert_eq!(registry_events(&result.events), vec![expected]);
+        }),
+    });
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.wallet2,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for the new manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.wallet2,
+        CallMessage::AcceptManager,
+        false,
+        "AcceptManager should fail once no proposal is pending",
+    );
+}
+
+//
+// TEST – opt-in EnforcementPassed audit events
+//
+// - Opt-in off: DEX enforces an active session (should succeed, no EnforcementPassed event)
+// - Opt-in on: DEX enforces a timed session (should emit EnforcementPassed via Timed)
+// - Opt-in on: DEX enforces a bypassed wallet (should emit EnforcementPassed via Bypass)
+//
+#[test]
+fn test_enforcement_passed_events() {
+    for emit_enforcement_events in [false, true] {
+        let (test_data, mut runner) = setup_with_config(|config| {
+            config.emit_enforcement_events = emit_enforcement_events;
+        });
+
+        let wallet_addr = test_data.wallet.address();
+        let wallet2_addr = test_data.wallet2.address();
+
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetSessionSigner {
+                signer: test_data.signer.address(),
+                allowed: true,
+                expires_at: 0,
+            },
+            true,
+            "SetSessionSigner should succeed for manager",
+        );
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at: 2764177788,
+            },
+            true,
+            "SetSession should succeed for authorized session signer",
+        );
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetBypass {
+                wallet: wallet2_addr.clone(),
+                bypass: true,
+            },
+            true,
+            "SetBypass should succeed for manager",
+        );
+
+        for (target, via) in [
+            (wallet_addr.clone(), ActiveVia::Timed),
+            (wallet2_addr.clone(), ActiveVia::Bypass),
+        ] {
+            let expected = Event::EnforcementPassed {
+                wallet: target.clone(),
+                via,
+            };
+
+            runner.execute_transaction(TransactionTestCase {
+                input: test_data
+                    .wallet
+                    .create_plain_message::<TestRuntime<S>, TestDex<S>>(
+                        DexCallMessage::EnforceSessionActive { wallet: target },
+                    ),
+                assert: Box::new(move |result, _| {
+                    assert!(
+                        result.tx_receipt.is_successful(),
+                        "EnforceSessionActive should succeed for an active session"
+                    );
+
+                    let emitted = registry_events(&result.events).contains(&expected);
+                    assert_eq!(
+                        emitted, emit_enforcement_events,
+                        "EnforcementPassed should be emitted only when the opt-in is enabled"
+                    );
+                }),
+            });
+        }
+    }
+}
+
+//
+// TEST – ForceExpire keeps the record but makes it inactive
+//
+// - Signer sets sessions for wallet and wallet2, manager bypasses wallet
+// - Signer attempts ForceExpire (should fail: manager-only)
+// - Manager force-expires wallet, signer clears wallet2
+// - wallet: present but not active; wallet2: neither present nor active
+// - Manager force-expires wallet2 (should fail: no session to expire)
+//
+#[test]
+fn test_force_expire() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![2764177788, 2764177788],
+        },
+        true,
+        "SetSessionBatch should succeed for authorized signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::ForceExpire {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "ForceExpire should fail when called by a non-manager",
+    );
+
+    let expected = Event::SessionForceExpired {
+        wallet: wallet_addr.clone(),
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::ForceExpire {
+                wallet: wallet_addr.clone(),
+            }),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "ForceExpire should succeed for manager"
+            );
+            assert!(
+                registry_events(&result.events).contains(&expected),
+                "ForceExpire should emit SessionForceExpired"
+            );
+        }),
+    });
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet2_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "SetSession with ttl=0 should clear wallet2's session",
+    );
+
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "A force-expired session should not be active, even with bypass previously set",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionPresent {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "A force-expired session should remain present",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet2_addr.clone(),
+        },
+        false,
+        "A cleared session should not be active",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionPresent {
+            wallet: wallet2_addr.clone(),
+        },
+        false,
+        "A cleared session should not be present",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ForceExpire {
+            wallet: wallet2_addr.clone(),
+        },
+        false,
+        "ForceExpire should fail for a wallet without a session",
+    );
+}
+
+//
+// TEST – every clear path removes the session record
+//
+// - SetSession with expiry 0 removes a timed session
+// - SetSessionBatch with expiry 0 removes a timed session
+// - SetBypass(false) removes a pure-bypass session
+// - SetBypass(false) on a timed session keeps the record
+//
+#[test]
+fn test_clear_paths_remove_session_records() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    // Single clear
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    assert!(read_session(&mut runner, &wallet_addr).is_some());
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "SetSession with ttl=0 should succeed",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr),
+        None,
+        "SetSession with ttl=0 should remove the session record"
+    );
+
+    // Batch clear
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![2764177788, 2764177788],
+        },
+        true,
+        "SetSessionBatch should succeed for authorized signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone()],
+            expiries: vec![0],
+        },
+        true,
+        "SetSessionBatch with ttl=0 should succeed",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr),
+        None,
+        "SetSessionBatch with ttl=0 should remove the session record"
+    );
+    assert!(
+        read_session(&mut runner, &wallet2_addr).is_some(),
+        "Clearing one wallet must not touch another wallet's record"
+    );
+
+    // Bypass clear
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass(true) should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: false,
+        },
+        true,
+        "SetBypass(false) should succeed for manager",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr),
+        None,
+        "SetBypass(false) should remove a pure-bypass session record"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet2_addr.clone(),
+            bypass: false,
+        },
+        true,
+        "SetBypass(false) should succeed for manager",
+    );
+    assert!(
+        read_session(&mut runner, &wallet2_addr).is_some(),
+        "SetBypass(false) should keep a timed session record"
+    );
+}
+
+//
+// TEST – genesis refuses to run twice
+//
+// - Genesis has already run through the test runner
+// - Running genesis again with a different owner fails with GenesisAlreadyInitialized
+// - The original owner is left untouched
+//
+#[test]
+fn test_genesis_twice_is_rejected() {
+    let (test_data, mut runner) = setup();
+
+    let config = RegistryConfig::<S> {
+        owner: test_data.wallet.address(),
+        owners: vec![],
+        manager: test_data.wallet.address(),
+        enforcement_enabled: false,
+        expiry_offset: 0,
+        require_funded_manager: false,
+        emit_enforcement_events: false,
+        max_renewals: None,
+        max_sessions_per_signer: None,
+        signer_cannot_be_wallet: false,
+        signers_frozen: false,
+        session_signers: vec![],
+        sessions: vec![],
+        time_unit: TimeUnit::Seconds,
+        max_session_duration: 0,
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        reject_past_expiry: false,
+        emit_bypass_events: false,
+        min_expiry_offset: i64::MIN,
+        require_distinct_manager: false,
+    };
+
+    runner.query_state(|state| {
+        let mut registry = SessionRegistry::<S>::default();
+        let err = registry
+            .genesis(&MockBlockHeader::from_height(0), &config, state)
+            .expect_err("Second genesis should fail");
+        assert!(matches!(
+            err.downcast_ref::<SessionRegistryError>(),
+            Some(SessionRegistryError::GenesisAlreadyInitialized)
+        ));
+
+        assert_eq!(
+            registry.owner.get(state).unwrap(),
+            Some(test_data.owner.address()),
+            "Second genesis must not overwrite the owner"
+        );
+    });
+}
+
+//
+// TEST – renewals are capped by max_renewals
+//
+// - Registry configured with max_renewals = 2
+// - Signer creates a session and renews it twice (renewals = 2)
+// - A third renewal fails with the cap reached
+// - Signer clears the session and creates it again (renewals = 0)
+// - Renewing succeeds again
+//
+#[test]
+fn test_max_renewals() {
+    let (test_data, mut runner) = setup_with_config(|config| config.max_renewals = Some(2));
+
+    let wallet_addr = test_data.wallet.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    let set_session = |expires_at: i64| CallMessage::SetSession {
+        wallet: wallet_addr.clone(),
+        expires_at,
+    };
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(2764177788),
+        true,
+        "Creating the session should succeed",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr).unwrap().renewals, 0);
+
+    for (renewal, expires_at) in [(1, 2764177789), (2, 2764177790)] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            set_session(expires_at),
+            true,
+            "Renewing below the cap should succeed",
+        );
+        assert_eq!(
+            read_session(&mut runner, &wallet_addr).unwrap().renewals,
+            renewal
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(2764177791),
+        false,
+        "Renewing past the cap should fail",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).unwrap().expiry_ts,
+        2764177790,
+        "A rejected renewal must not change the expiry"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(0),
+        true,
+        "Clearing the session should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(2764177788),
+        true,
+        "Recreating the session should succeed",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr).unwrap().renewals, 0);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(2764177789),
+        true,
+        "Renewing a recreated session should succeed",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr).unwrap().renewals, 1);
+}
+
+//
+// TEST – signer_cannot_be_wallet policy
+//
+// - With the policy off, a signer can set its own session
+// - With the policy on, SetSession and SetSessionBatch targeting the signer fail
+// - With the policy on, the signer can still set other wallets' sessions
+//
+#[test]
+fn test_signer_cannot_be_wallet() {
+    for policy in [false, true] {
+        let (test_data, mut runner) =
+            setup_with_config(|config| config.signer_cannot_be_wallet = policy);
+
+        let signer_addr = test_data.signer.address();
+        let wallet_addr = test_data.wallet.address();
+
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetSessionSigner {
+                signer: signer_addr.clone(),
+                allowed: true,
+                expires_at: 0,
+            },
+            true,
+            "SetSessionSigner should succeed for manager",
+        );
+
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: signer_addr.clone(),
+                expires_at: 2764177788,
+            },
+            !policy,
+            "SetSession targeting the signer should only succeed with the policy off",
+        );
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSessionBatch {
+                wallets: vec![wallet_addr.clone(), signer_addr.clone()],
+                expiries: vec![2764177788, 2764177788],
+            },
+            !policy,
+            "SetSessionBatch containing the signer should only succeed with the policy off",
+        );
+        assert_eq!(
+            read_session(&mut runner, &wallet_addr).is_some(),
+            !policy,
+            "A rejected batch must not write any entry"
+        );
+
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at: 2764177789,
+            },
+            true,
+            "SetSession for another wallet should always succeed",
+        );
+    }
+}
+
+//
+// TEST – session proofs verify against the state root
+//
+// - Signer sets a session for wallet
+// - The proven session of wallet verifies against the current state root
+// - The proven absence of wallet2's session verifies too
+// - Tampering with the returned session makes verification fail
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_session_proof_verifies_against_state_root() {
+    use sb_session_registry::verify_session_proof;
+
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+
+    let (present, absent) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.session_with_proof(&wallet_addr, state).unwrap(),
+            registry.session_with_proof(&wallet2_addr, state).unwrap(),
+        )
+    });
+    let state_root = runner.state_root().clone();
+
+    assert_eq!(present.session.as_ref().unwrap().expiry_ts, 2764177788);
+    verify_session_proof(&present, &state_root).expect("present session should verify");
+
+    assert_eq!(absent.session, None);
+    verify_session_proof(&absent, &state_root).expect("absent session should verify");
+
+    let mut tampered = present.clone();
+    tampered.session.as_mut().unwrap().expiry_ts += 1;
+    assert!(verify_session_proof(&tampered, &state_root).is_err());
+
+    let mut wrong_wallet = present;
+    wrong_wallet.wallet = wallet2_addr;
+    assert!(verify_session_proof(&wrong_wallet, &state_root).is_err());
+}
+
+//
+// TEST – setting enforcement to its current value is a no-op
+//
+// - Owner sets enforcement to true (already true): EnforcementUnchanged, no EnforcementEnabledSet
+// - Owner sets enforcement to false: EnforcementEnabledSet
+// - Owner sets enforcement to false again: EnforcementUnchanged
+//
+#[test]
+fn test_set_enforcement_enabled_unchanged() {
+    let (test_data, mut runner) = setup();
+
+    for (enabled, changed) in [(true, false), (false, true), (false, false)] {
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .owner
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                    CallMessage::SetEnforcementEnabled { enabled },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "SetEnforcementEnabled should succeed for owner"
+                );
+
+                let expected = if changed {
+                    Event::EnforcementEnabledSet { enabled }
+                } else {
+                    Event::EnforcementUnchanged { enabled }
+                };
+                assert_eq!(registry_events(&result.events), vec![expected]);
+            }),
+        });
+    }
+}
+
+//
+// TEST – SetSessionBatchUniform applies the same computed expiry to every wallet
+//
+// - Signer sets a uniform-TTL batch for wallet and wallet2
+// - Both wallets get the same expiry and pass EnforceSessionActive
+// - A batch with a non-positive TTL fails
+//
+#[test]
+fn test_set_session_batch_uniform() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatchUniform {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            ttl_secs: 3600,
+        },
+        true,
+        "SetSessionBatchUniform should succeed for authorized signer",
+    );
+
+    let expiry = read_session(&mut runner, &wallet_addr).unwrap().expiry_ts;
+    let expiry2 = read_session(&mut runner, &wallet2_addr).unwrap().expiry_ts;
+    assert_eq!(expiry, expiry2, "Every wallet should get the same expiry");
+
+    for wallet in [wallet_addr.clone(), wallet2_addr.clone()] {
+        execute_dex_call(
+            &mut runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionActive { wallet },
+            true,
+            "EnforceSessionActive should succeed after SetSessionBatchUniform",
+        );
+    }
+
+    for ttl_secs in [0, -1] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSessionBatchUniform {
+                wallets: vec![wallet_addr.clone()],
+                ttl_secs,
+            },
+            false,
+            "SetSessionBatchUniform should fail for a non-positive TTL",
+        );
+    }
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).unwrap().expiry_ts,
+        expiry
+    );
+}
+
+//
+// TEST – the version endpoint reports the module version and feature flags
+//
+// - Registry configured with max_renewals and signer_cannot_be_wallet
+// - version_info lists every compiled feature and exactly the enabled ones, including the
+//   still-open session import
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_version_info() {
+    use sb_session_registry::{COMPILED_FEATURES, MODULE_VERSION};
+
+    let (_, mut runner) = setup_with_config(|config| {
+        config.max_renewals = Some(3);
+        config.signer_cannot_be_wallet = true;
+    });
+
+    let info = runner.query_state(|state| SessionRegistry::<S>::default().version_info(state));
+
+    assert_eq!(info.version, MODULE_VERSION);
+    assert_eq!(info.features, COMPILED_FEATURES);
+    assert!(
+        info.enabled
+            .iter()
+            .all(|feature| info.features.contains(feature)),
+        "Every enabled feature should be listed as compiled"
+    );
+    assert_eq!(
+        info.enabled,
+        [
+            "session_proofs",
+            "force_expire",
+            "uniform_ttl_batch",
+            "renewal_limits",
+            "signer_cannot_be_wallet",
+            "enforcement_scopes",
+            "borsh_responses",
+            "wallet_policies",
+            "time_units",
+            "simulate",
+            "signer_batches",
+            "session_status",
+            "manager_handoff",
+            "batch_size_cap",
+            "signer_list",
+            "force_enforce",
+            "extend_session",
+            "session_created_ts",
+            "session_count",
+            "expiring_signers",
+            "purge_wallet",
+            "error_codes",
+            "historical_status",
+            "expiring_sessions",
+            "session_metadata",
+            "check_session_active",
+            "offset_history",
+            "self_revoke",
+            "genesis_validation",
+            "owner_set",
+            "session_import",
+        ]
+    );
+}
+
+//
+// TEST – frozen signers block SetSessionSigner
+//
+// - Owner freezes signers, emitting SignersFrozenSet
+// - Manager attempts to grant a signer (should fail: signers frozen)
+// - Owner unfreezes signers; the manager's grant now succeeds
+//
+#[test]
+fn test_signers_frozen() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::SetSignersFrozen { frozen: true },
+            ),
+        assert: Box::new(|result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetSignersFrozen should succeed for owner"
+            );
+            assert_eq!(
+                registry_events(&result.events),
+                vec![Event::SignersFrozenSet { frozen: true }]
+            );
+        }),
+    });
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        false,
+        "SetSessionSigner should fail while signers are frozen",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetSignersFrozen { frozen: false },
+        true,
+        "SetSignersFrozen should succeed for owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed once signers are unfrozen",
+    );
+
+    let allowed = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .session_signers
+            .get(&signer_addr, state)
+            .unwrap()
+    });
+    assert_eq!(allowed, Some(SignerAuthorization::permanent(true)));
+}
+
+//
+// TEST – toggling enforcement between DEX operations
+//
+// - Signer sets a session; DEX enforces active (should succeed)
+// - Owner disables enforcement and the signer clears the session
+// - DEX enforces active (should succeed: enforcement disabled)
+// - Owner re-enables enforcement; DEX enforces active (should fail: session cleared)
+//
+#[test]
+fn test_enforcement_toggled_mid_operation() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "EnforceSessionActive should succeed for valid session",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementEnabled { enabled: false },
+        true,
+        "SetEnforcementEnabled(false) should succeed for owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "SetSession with expires_at=0 should clear the session",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "EnforceSessionActive should succeed while enforcement is disabled",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementEnabled { enabled: true },
+        true,
+        "SetEnforcementEnabled(true) should succeed for owner",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "EnforceSessionActive should fail once enforcement is re-enabled for a cleared session",
+    );
+}
+
+//
+// TEST – session queries tell malformed addresses from absent sessions
+//
+// - A path segment that isn't an address is InvalidAddress (served as 400)
+// - A valid address without a session is NotFound (served as 404)
+// - A wallet with a session returns it
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_query_session_errors() {
+    use sb_session_registry::{SessionQuery, SessionQueryError};
+
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+
+    let query = SessionQuery::default();
+    let (malformed, absent, present) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.query_session("not-an-address", &query, state),
+            registry.query_session(&wallet2_addr.to_string(), &query, state),
+            registry.query_session(&wallet_addr.to_string(), &query, state),
+        )
+    });
+
+    assert!(matches!(
+        malformed,
+        Err(SessionQueryError::InvalidAddress { input, .. }) if input == "not-an-address"
+    ));
+    assert_eq!(
+        absent.unwrap_err(),
+        SessionQueryError::NotFound {
+            wallet: wallet2_addr.to_string()
+        }
+    );
+    assert_eq!(present.unwrap().session.unwrap().expiry_ts, 2764177788);
+}
+
+//
+// TEST – session queries can be served as JSON or borsh
+//
+// - `?format=` wins over `Accept`, which selects borsh for `application/x-borsh`
+// - Both encodings decode to the same session
+// - A proof can't be encoded as borsh
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_session_response_formats() {
+    use sb_session_registry::{
+        encode_session_response, EncodedSession, ResponseFormat, Session, SessionQuery,
+        SessionQueryError, SessionResponse, BORSH_CONTENT_TYPE,
+    };
+
+    assert_eq!(ResponseFormat::negotiate(None, None), ResponseFormat::Json);
+    assert_eq!(
+        ResponseFormat::negotiate(None, Some("application/json")),
+        ResponseFormat::Json
+    );
+    assert_eq!(
+        ResponseFormat::negotiate(None, Some("text/html, application/x-borsh;q=0.9")),
+        ResponseFormat::Borsh
+    );
+    assert_eq!(
+        ResponseFormat::negotiate(Some(ResponseFormat::Json), Some(BORSH_CONTENT_TYPE)),
+        ResponseFormat::Json
+    );
+
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+
+    let (response, proven) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let with_proof = SessionQuery {
+            with_proof: true,
+            ..Default::default()
+        };
+        (
+            registry
+                .query_session(&wallet_addr.to_string(), &SessionQuery::default(), state)
+                .unwrap(),
+            registry
+                .query_session(&wallet_addr.to_string(), &with_proof, state)
+                .unwrap(),
+        )
+    });
+    let expected = response.session.clone().unwrap();
+
+    let json = match encode_session_response(response.clone(), ResponseFormat::Json).unwrap() {
+        EncodedSession::Json(response) => serde_json::to_vec(&response).unwrap(),
+        EncodedSession::Borsh(_) => panic!("JSON format should not encode as borsh"),
+    };
+    let from_json: SessionResponse<S> = serde_json::from_slice(&json).unwrap();
+    assert_eq!(from_json.session, Some(expected.clone()));
+
+    let borsh_bytes = match encode_session_response(response, ResponseFormat::Borsh).unwrap() {
+        EncodedSession::Borsh(bytes) => bytes,
+        EncodedSession::Json(_) => panic!("Borsh format should not encode as JSON"),
+    };
+    let from_borsh: Session = borsh::from_slice(&borsh_bytes).unwrap();
+    assert_eq!(from_borsh, expected);
+
+    assert!(matches!(
+        encode_session_response(proven, ResponseFormat::Borsh),
+        Err(SessionQueryError::ProofNotEncodable)
+    ));
+}
+
+//
+// TEST – session queries show when the offset props a session up
+//
+// - A session that expired 10 seconds ago is inactive without an offset
+// - With a one-hour offset it is active, and the response says it is only active due to the offset
+// - A session that hasn't expired nominally is not flagged
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_query_session_reports_offset_effect() {
+    use sb_session_registry::SessionQuery;
+
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    let now_ts = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .chain_state
+            .get_time(state)
+            .unwrap()
+            .secs()
+    });
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![now_ts - 10, 2764177788],
+        },
+        true,
+        "SetSessionBatch should succeed for authorized session signer",
+    );
+
+    let query = SessionQuery::default();
+    let expired = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .query_session(&wallet_addr.to_string(), &query, state)
+            .unwrap()
+    });
+    assert_eq!(expired.nominal_expiry_ts, Some(now_ts - 10));
+    assert_eq!(expired.effective_expiry_ts, Some(now_ts - 10));
+    assert!(!expired.active_only_due_to_offset);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset { new_offset: 3600 },
+        true,
+        "SetExpiryOffset should succeed for owner",
+    );
+
+    let (propped_up, live) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry
+                .query_session(&wallet_addr.to_string(), &query, state)
+                .unwrap(),
+            registry
+                .query_session(&wallet2_addr.to_string(), &query, state)
+                .unwrap(),
+        )
+    });
+    assert_eq!(propped_up.nominal_expiry_ts, Some(now_ts - 10));
+    assert_eq!(propped_up.effective_expiry_ts, Some(now_ts - 10 + 3600));
+    assert!(
+        propped_up.active_only_due_to_offset,
+        "An expired session kept alive by the offset should be flagged"
+    );
+    assert_eq!(live.effective_expiry_ts, Some(2764177788 + 3600));
+    assert!(
+        !live.active_only_due_to_offset,
+        "A session that hasn't expired should not be flagged"
+    );
+}
+
+//
+// TEST – wallet status route reports the session in one object
+//
+// - A wallet with a future session is active and present, with the offset applied to its
+//   effective expiry
+// - A wallet without a session is inactive and absent
+// - A malformed address is rejected
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_wallet_status() {
+    use sb_session_registry::SessionQueryError;
+
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset { new_offset: 60 },
+        true,
+        "SetExpiryOffset should succeed for owner",
+    );
+
+    let (status, absent, malformed) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.wallet_status(&wallet_addr.to_string(), state),
+            registry.wallet_status(&wallet2_addr.to_string(), state),
+            registry.wallet_status("not-an-address", state),
+        )
+    });
+
+    let status = serde_json::to_value(status.unwrap()).unwrap();
+    assert_eq!(
+        status["wallet"],
+        serde_json::to_value(&wallet_addr).unwrap()
+    );
+    assert_eq!(status["active"], true);
+    assert_eq!(status["present"], true);
+    assert_eq!(status["expiry_ts"], 2764177788i64);
+    assert_eq!(status["effective_expiry_ts"], 2764177848i64);
+    assert_eq!(status["bypass"], false);
+    assert!(status["now_ts"].as_i64().unwrap() < 2764177788);
+
+    let absent = absent.unwrap();
+    assert!(!absent.active && !absent.present && !absent.bypass);
+    assert_eq!(absent.expiry_ts, None);
+    assert_eq!(absent.effective_expiry_ts, None);
+
+    assert!(matches!(
+        malformed,
+        Err(SessionQueryError::InvalidAddress { .. })
+    ));
+}
+
+//
+// TEST – registry state survives a snapshot/restore round-trip
+//
+// - Build up state: a signer, a renewed session, a bypass-only record, an offset and a freeze
+// - Snapshot the registry and restore it into a fresh runner
+// - The restored registry snapshots to the same state, and sessions keep their renewal counts
+//
+#[test]
+fn test_snapshot_restore_round_trip() {
+    let (test_data, mut runner) = setup_with_config(|config| config.max_renewals = Some(5));
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    for expires_at in [2764177788, 2764177799] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at,
+            },
+            true,
+            "SetSession should succeed for authorized session signer",
+        );
+    }
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet2_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset { new_offset: 60 },
+        true,
+        "SetExpiryOffset should succeed for owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetSignersFrozen { frozen: true },
+        true,
+        "SetSignersFrozen should succeed for owner",
+    );
+
+    let addresses = [
+        test_data.owner.address(),
+        test_data.manager.address(),
+        signer_addr.clone(),
+        wallet_addr.clone(),
+        wallet2_addr.clone(),
+    ];
+    let snapshot = snapshot_registry(&mut runner, &addresses);
+    assert_eq!(snapshot.session_signers, vec![signer_addr]);
+    assert_eq!(snapshot.sessions.len(), 2);
+
+    let mut restored = restore_registry(&snapshot);
+    assert_eq!(snapshot_registry(&mut restored, &addresses), snapshot);
+
+    let session = read_session(&mut restored, &wallet_addr).unwrap();
+    assert_eq!(session.expiry_ts, 2764177799);
+    assert_eq!(session.renewals, 1);
+    assert!(read_session(&mut restored, &wallet2_addr).unwrap().bypass);
+}
+
+//
+// TEST – enforcement scopes gate operations independently
+//
+// - Owner sets scope `view` to disabled and `trade` to enforced
+// - For a wallet without a session, `view` passes and `trade` fails
+// - Owner disables global enforcement: an unconfigured scope passes, `trade` still fails
+// - Owner clears `trade`, which then follows the global flag and passes
+//
+#[test]
+fn test_enforcement_scopes() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let view = ScopeId::new("view");
+    let trade = ScopeId::new("trade");
+
+    let scoped_check = |runner: &mut _, scope: &ScopeId, expect_success, description| {
+        execute_dex_call(
+            runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionActiveForScope {
+                wallet: wallet_addr.clone(),
+                scope: scope.clone(),
+            },
+            expect_success,
+            description,
+        );
+    };
+
+    for (scope, mode) in [
+        (view.clone(), EnforcementMode::Disabled),
+        (trade.clone(), EnforcementMode::Enforced),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .owner
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                    CallMessage::SetEnforcementScope {
+                        scope: scope.clone(),
+                        mode: Some(mode),
+                    },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "SetEnforcementScope should succeed for owner"
+                );
+                assert_eq!(
+                    registry_events(&result.events),
+                    vec![Event::EnforcementScopeSet {
+                        scope,
+                        mode: Some(mode)
+                    }]
+                );
+            }),
+        });
+    }
+
+    scoped_check(
+        &mut runner,
+        &view,
+        true,
+        "A disabled scope should pass without a session",
+    );
+    scoped_check(
+        &mut runner,
+        &trade,
+        false,
+        "An enforced scope should fail without a session",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementEnabled { enabled: false },
+        true,
+        "SetEnforcementEnabled(false) should succeed for owner",
+    );
+    scoped_check(
+        &mut runner,
+        &ScopeId::new("withdraw"),
+        true,
+        "An unconfigured scope should follow the disabled global flag",
+    );
+    scoped_check(
+        &mut runner,
+        &trade,
+        false,
+        "An enforced scope should ignore the global flag",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementScope {
+            scope: trade.clone(),
+            mode: None,
+        },
+        true,
+        "SetEnforcementScope should clear the scope",
+    );
+    scoped_check(
+        &mut runner,
+        &trade,
+        true,
+        "A cleared scope should follow the disabled global flag",
+    );
+}
+
+//
+// TEST – accessor defaults for uninitialized state
+//
+// - With expiry_offset unset, offset() is 0
+// - With enforcement_enabled unset, enforcement_on() and the enforce checks fail with
+//   EnforcementFlagNotInitialized instead of defaulting
+//
+#[test]
+fn test_accessor_defaults() {
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.enforcement_enabled = false;
+        config.expiry_offset = 60;
+    });
+    let wallet_addr = test_data.wallet.address();
+
+    let (configured, unset_offset, unset_flag) = runner.query_state(|state| {
+        let mut registry = SessionRegistry::<S>::default();
+        let configured = (
+            registry.offset(state).unwrap(),
+            registry.enforcement_on(state).unwrap(),
+        );
+
+        registry.expiry_offset.delete(state).unwrap();
+        registry.enforcement_enabled.delete(state).unwrap();
+        let unset_flag = [
+            registry.enforcement_on(state).map(|_| ()),
+            registry.enforce_session_active(&wallet_addr, state),
+            registry.enforce_session_present(&wallet_addr, state),
+        ]
+        .map(|result| result.unwrap_err().to_string());
+
+        (configured, registry.offset(state).unwrap(), unset_flag)
+    });
+
+    assert_eq!(configured, (60, false));
+    assert_eq!(unset_offset, 0);
+    for error in unset_flag {
+        assert_eq!(
+            error,
+            SessionRegistryError::EnforcementFlagNotInitialized.to_string()
+        );
+    }
+}
+
+//
+// TEST – empty batches are rejected
+//
+// - SetSessionBatch with no wallets and no expiries fails with EmptyBatch
+// - SetSessionBatchUniform with no wallets fails too
+// - A non-empty batch from the same signer still succeeds
+//
+#[test]
+fn test_empty_batch_is_rejected() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![],
+            expiries: vec![],
+        },
+        false,
+        "SetSessionBatch with no wallets should fail with EmptyBatch",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatchUniform {
+            wallets: vec![],
+            ttl_secs: 3600,
+        },
+        false,
+        "SetSessionBatchUniform with no wallets should fail with EmptyBatch",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone()],
+            expiries: vec![2764177788],
+        },
+        true,
+        "SetSessionBatch with a wallet should succeed",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).unwrap().expiry_ts,
+        2764177788
+    );
+}
+
+//
+// TEST – a strict wallet policy ignores bypass
+//
+// - A wallet with only bypass set passes EnforceSessionActive
+// - Under StrictTimed the same wallet fails, while another bypassed wallet still passes
+// - Giving the strict wallet a timed session makes it pass; clearing the policy restores bypass
+//
+#[test]
+fn test_strict_timed_wallet_policy() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    for wallet in [wallet_addr.clone(), wallet2_addr.clone()] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetBypass {
+                wallet,
+                bypass: true,
+            },
+            true,
+            "SetBypass should succeed for manager",
+        );
+    }
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "A bypassed wallet should pass without a policy",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetWalletPolicy {
+            wallet: wallet_addr.clone(),
+            policy: Some(WalletPolicy::StrictTimed),
+        },
+        true,
+        "SetWalletPolicy should succeed for manager",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "A strict wallet should fail on bypass alone",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet2,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet2_addr.clone(),
+        },
+        true,
+        "The policy should only affect its own wallet",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "A strict wallet should pass with a timed session",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ForceExpire {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "ForceExpire should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+    let expected = Event::WalletPolicySet {
+        wallet: wallet_addr.clone(),
+        policy: None,
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::SetWalletPolicy {
+                    wallet: wallet_addr.clone(),
+                    policy: None,
+                },
+            ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "Clearing the policy should succeed for manager"
+            );
+            assert_eq!(registry_events(&result.events), vec![expected]);
+        }),
+    });
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "Bypass should be honored again once the policy is cleared",
+    );
+}
+
+//
+// TEST – expiries compare in the configured time unit
+//
+// - In both units, now_ts is the chain time expressed in that unit
+// - A session expiring one unit in the past or exactly now is inactive
+// - A session expiring one unit in the future is active
+// - The read-only checks agree with the transactional ones
+//
+#[test]
+fn test_time_unit_expiry_boundary() {
+    use sb_session_registry::Session;
+
+    for time_unit in [TimeUnit::Seconds, TimeUnit::Milliseconds] {
+        let (test_data, mut runner) = setup_with_config(|config| config.time_unit = time_unit);
+        let wallet_addr = test_data.wallet.address();
+
+        runner.query_state(|state| {
+            let mut registry = SessionRegistry::<S>::default();
+            let now = registry.chain_state.get_time(state).unwrap();
+            let now_ts = registry.now_ts(state).unwrap();
+            assert_eq!(registry.time_unit(state).unwrap(), time_unit);
+            assert_eq!(now_ts, time_unit.from_time(&now));
+
+            let boundary = [(now_ts - 1, false), (now_ts, false), (now_ts + 1, true)];
+            for (expiry_ts, expected) in boundary {
+                let session = Session {
+                    expiry_ts,
+                    bypass: false,
+                    renewals: 0,
+                    created_ts: 0,
+                    metadata: None,
+                };
+                registry
+                    .sessions
+                    .set(&wallet_addr, &session, state)
+                    .unwrap();
+                assert_eq!(
+                    registry.is_session_active(&wallet_addr, state).unwrap(),
+                    expected,
+                    "{:?}: expiry {} vs now {}",
+                    time_unit,
+                    expiry_ts,
+                    now_ts
+                );
+                assert_eq!(
+                    registry.is_session_active_ro(&wallet_addr, state).unwrap(),
+                    expected,
+                    "Read-only check should agree with is_session_active"
+                );
+                assert!(registry.is_session_present_ro(&wallet_addr, state).unwrap());
+            }
+        });
+    }
+
+    assert_eq!(TimeUnit::Seconds.from_secs(60), Some(60));
+    assert_eq!(TimeUnit::Milliseconds.from_secs(60), Some(60_000));
+    assert_eq!(TimeUnit::Milliseconds.from_secs(i64::MAX), None);
+}
+
+//
+// TEST – simulated calls report the outcome without changing state
+//
+// - Simulating SetSessionSigner as a wallet fails with the authorization error
+// - Simulating it as the manager succeeds
+// - Neither simulation grants the signer privileges
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_simulate_call() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let grant = CallMessage::SetSessionSigner {
+        signer: signer_addr.clone(),
+        allowed: true,
+        expires_at: 0,
+    };
+
+    let (unauthorized, authorized) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.simulate_call(&test_data.wallet.address(), grant.clone(), state),
+            registry.simulate_call(&test_data.manager.address(), grant.clone(), state),
+        )
+    });
+
+    assert!(!unauthorized.success);
+    assert_eq!(
+        unauthorized.error.as_deref(),
+        Some(
+            SessionRegistryError::UnauthorizedManager
+                .to_string()
+                .as_str()
+        )
+    );
+    assert_eq!(
+        unauthorized.code,
+        Some(SessionRegistryError::UnauthorizedManager.code())
+    );
+    assert!(authorized.success, "{:?}", authorized.error);
+    assert_eq!(authorized.error, None);
+    assert_eq!(authorized.code, None);
+
+    let is_signer = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .session_signers
+            .get(&signer_addr, state)
+            .unwrap()
+    });
+    assert_eq!(is_signer, None, "Simulation must not write state");
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: test_data.wallet.address(),
+            expires_at: 2764177788,
+        },
+        false,
+        "The simulated grant must not authorize the signer",
+    );
+}
+
+//
+// TEST – SetSessionSignersBatch grants and revokes signers in one call
+//
+// - The owner address starts out as a signer
+// - One batch grants signer, wallet and wallet2 and revokes the owner address
+// - Every entry lands in the map and emits its own SessionSignerSet, in order
+// - While signers are frozen the batch fails
+//
+#[test]
+fn test_set_session_signers_batch() {
+    let (test_data, mut runner) = setup();
+
+    let owner_addr = test_data.owner.address();
+    let granted = [
+        test_data.signer.address(),
+        test_data.wallet.address(),
+        test_data.wallet2.address(),
+    ];
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: owner_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    let mut signers: Vec<_> = granted.iter().map(|a| (a.clone(), true)).collect();
+    signers.push((owner_addr.clone(), false));
+    let expected: Vec<_> = signers
+        .iter()
+        .map(|(signer, allowed)| Event::SessionSignerSet {
+            signer: signer.clone(),
+            allowed: *allowed,
+            expires_at: 0,
+        })
+        .collect();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::SetSessionSignersBatch {
+                    signers: signers.clone(),
+                },
+            ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetSessionSignersBatch should succeed for manager"
+            );
+            assert_eq!(registry_events(&result.events), expected);
+        }),
+    });
+
+    let entries = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        signers
+            .iter()
+            .map(|(signer, _)| {
+                registry
+                    .session_signers
+                    .get(signer, state)
+                    .unwrap()
+                    .map(|authorization| authorization.allowed)
+            })
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        entries,
+        vec![Some(true), Some(true), Some(true), Some(false)]
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetSignersFrozen { frozen: true },
+        true,
+        "SetSignersFrozen should succeed for owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSignersBatch {
+            signers: vec![(owner_addr, true)],
+        },
+        false,
+        "SetSessionSignersBatch should fail while signers are frozen",
+    );
+}
+
+//
+// TEST – max_session_duration caps how far out sessions can expire
+//
+// - With a one-hour cap, a session expiring a day from now fails with SessionTooLong
+// - A session expiring exactly at the cap is accepted
+// - Clearing a session is not capped
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_max_session_duration() {
+    let (test_data, mut runner) = setup_with_config(|config| config.max_session_duration = 3600);
+
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    // Simulating against a single state view pins `now` for the boundary.
+    let (too_long, at_cap) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let now_ts = registry.now_ts(state).unwrap();
+        let set_session = |expires_at| CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at,
+        };
+        let signer_addr = test_data.signer.address();
+
+        (
+            registry.simulate_call(&signer_addr, set_session(now_ts + 86400), state),
+            registry.simulate_call(&signer_addr, set_session(now_ts + 3600), state),
+        )
+    });
+    assert_eq!(
+        too_long.error,
+        Some(SessionRegistryError::SessionTooLong.to_string())
+    );
+    assert!(
+        at_cap.success,
+        "A session exactly at the cap should succeed"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        false,
+        "SetSession far past the cap should fail",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr,
+            expires_at: 0,
+        },
+        true,
+        "Clearing a session should not be capped",
+    );
+}
+
+//
+// TEST – clearing a session emits SessionCleared instead of SessionSet
+//
+// - Setting a non-zero expiry emits SessionSet
+// - Clearing the session with expiry 0 emits SessionCleared
+// - A batch emits SessionSet or SessionCleared per entry, in order
+//
+#[test]
+fn test_session_cleared_event() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    let cases = [
+        (
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at: 2764177788,
+            },
+            vec![Event::SessionSet {
+                wallet: wallet_addr.clone(),
+                expiry_ts: 2764177788,
+            }],
+        ),
+        (
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at: 0,
+            },
+            vec![Event::SessionCleared {
+                wallet: wallet_addr.clone(),
+            }],
+        ),
+        (
+            CallMessage::SetSessionBatch {
+                wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+                expiries: vec![2764177788, 0],
+            },
+            vec![
+                Event::SessionSet {
+                    wallet: wallet_addr.clone(),
+                    expiry_ts: 2764177788,
+                },
+                Event::SessionCleared {
+                    wallet: wallet2_addr.clone(),
+                },
+            ],
+        ),
+    ];
+
+    for (msg, expected) in cases {
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .signer
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(msg),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "Session call should succeed for authorized session signer"
+                );
+                assert_eq!(registry_events(&result.events), expected);
+            }),
+        });
+    }
+}
+
+//
+// TEST – a large expiry offset saturates instead of overflowing
+//
+// - Signer sets a session expiring just below i64::MAX
+// - Owner sets a large positive expiry offset
+// - The session is still active instead of wrapping into the past
+//
+#[test]
+fn test_expiry_offset_saturates() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: i64::MAX - 10,
+        },
+        true,
+        "SetSession near i64::MAX should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset {
+            new_offset: i64::MAX / 2,
+        },
+        true,
+        "SetExpiryOffset should succeed for owner",
+    );
+
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "An overflowing offset should keep the session active",
+    );
+
+    let active = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .is_session_active(&wallet_addr, state)
+            .unwrap()
+    });
+    assert!(active);
+}
+
+//
+// TEST – session batches are capped at max_batch_size
+//
+// - With a cap of 2, a 3-wallet SetSessionBatch fails and writes nothing
+// - A 3-wallet batch fails with BatchTooLarge even when its lengths also mismatch
+// - A SetSessionBatchUniform over the cap fails too
+// - A batch of exactly 2 wallets succeeds
+//
+#[test]
+fn test_max_batch_size() {
+    let (test_data, mut runner) = setup_with_config(|config| config.max_batch_size = 2);
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+    let wallets = vec![
+        wallet_addr.clone(),
+        wallet2_addr.clone(),
+        wallet3_addr.clone(),
+    ];
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: wallets.clone(),
+            expiries: vec![2764177788; 3],
+        },
+        false,
+        "SetSessionBatch over the cap should fail",
+    );
+    for wallet in &wallets {
+        assert_eq!(read_session(&mut runner, wallet), None);
+    }
+
+    #[cfg(feature = "native")]
+    {
+        let signer_addr = test_data.signer.address();
+        let mismatched = runner.query_state(|state| {
+            SessionRegistry::<S>::default().simulate_call(
+                &signer_addr,
+                CallMessage::SetSessionBatch {
+                    wallets: wallets.clone(),
+                    expiries: vec![2764177788],
+                },
+                state,
+            )
+        });
+        assert_eq!(
+            mismatched.error,
+            Some(SessionRegistryError::BatchTooLarge.to_string())
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatchUniform {
+            wallets: wallets.clone(),
+            ttl_secs: 3600,
+        },
+        false,
+        "SetSessionBatchUniform over the cap should fail",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![2764177788; 2],
+        },
+        true,
+        "SetSessionBatch at the cap should succeed",
+    );
+    assert!(read_session(&mut runner, &wallet2_addr).is_some());
+    assert_eq!(read_session(&mut runner, &wallet3_addr), None);
+}
+
+//
+// TEST – a batch can set and clear sessions at once
+//
+// - Signer sets sessions for wallet and wallet2
+// - Signer sends a batch clearing wallet and renewing wallet2
+// - DEX enforcement: wallet is neither active nor present, wallet2 is both
+//
+#[test]
+fn test_mixed_set_and_clear_batch() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![2764177788, 2764177788],
+        },
+        true,
+        "SetSessionBatch should succeed for authorized session signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![0, 2764177799],
+        },
+        true,
+        "A batch mixing clears and renewals should succeed",
+    );
+
+    for (wallet, expected) in [(&wallet_addr, false), (&wallet2_addr, true)] {
+        execute_dex_call(
+            &mut runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionActive {
+                wallet: wallet.clone(),
+            },
+            expected,
+            "Only the renewed wallet should have an active session",
+        );
+        execute_dex_call(
+            &mut runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionPresent {
+                wallet: wallet.clone(),
+            },
+            expected,
+            "Only the renewed wallet should have a present session",
+        );
+    }
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+    assert_eq!(
+        read_session(&mut runner, &wallet2_addr).unwrap().expiry_ts,
+        2764177799
+    );
+}
+
+//
+// TEST – the session signer list follows grants and revocations
+//
+// - Genesis signers are listed
+// - Granting adds a signer once, even when granted twice
+// - Revoking removes only that signer, including through SetSessionSignersBatch
+//
+#[test]
+fn test_list_session_signers() {
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.session_signers = vec![config.owner.clone()];
+    });
+    let owner_addr = test_data.owner.address();
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+
+    assert_session_signers(&mut runner, &[owner_addr.clone()]);
+
+    for _ in 0..2 {
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetSessionSigner {
+                signer: signer_addr.clone(),
+                allowed: true,
+                expires_at: 0,
+            },
+            true,
+            "SetSessionSigner should succeed for manager",
+        );
+    }
+    assert_session_signers(&mut runner, &[owner_addr.clone(), signer_addr.clone()]);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSignersBatch {
+            signers: vec![(wallet_addr.clone(), true), (owner_addr.clone(), false)],
+        },
+        true,
+        "SetSessionSignersBatch should succeed for manager",
+    );
+    assert_session_signers(&mut runner, &[signer_addr.clone(), wallet_addr.clone()]);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr,
+            allowed: false,
+            expires_at: 0,
+        },
+        true,
+        "Revoking a signer should succeed for manager",
+    );
+    assert_session_signers(&mut runner, &[wallet_addr]);
+}
+
+//
+// TEST – the session list follows writes and deletions
+//
+// - Genesis sessions are listed with their records
+// - Purging a wallet drops it from the list, leaving the other session
+//
+#[test]
+fn test_list_sessions() {
+    use sb_session_registry::Session;
+    use sov_test_utils::runtime::TestRunner;
+
+    let session = Session {
+        expiry_ts: 2764177788,
+        bypass: false,
+        renewals: 0,
+        created_ts: 0,
+        metadata: None,
+    };
+    let bypassed = Session {
+        expiry_ts: 0,
+        bypass: true,
+        ..session.clone()
+    };
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.sessions = vec![
+            (config.owner.clone(), session.clone()),
+            (config.manager.clone(), bypassed.clone()),
+        ];
+    });
+    let owner_addr = test_data.owner.address();
+    let manager_addr = test_data.manager.address();
+
+    let list = |runner: &mut TestRunner<TestRuntime<S>, S>| {
+        let mut sessions = runner.query_state(|state| {
+            SessionRegistry::<S>::default()
+                .list_sessions(state)
+                .unwrap()
+        });
+        sessions.sort_by_key(|(wallet, _)| wallet.to_string());
+        sessions
+    };
+    let mut expected = vec![
+        (owner_addr.clone(), session),
+        (manager_addr.clone(), bypassed.clone()),
+    ];
+    expected.sort_by_key(|(wallet, _)| wallet.to_string());
+    assert_eq!(list(&mut runner), expected);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::PurgeWallet { wallet: owner_addr },
+        true,
+        "PurgeWallet should succeed for owner",
+    );
+    assert_eq!(list(&mut runner), vec![(manager_addr, bypassed)]);
+}
+
+//
+// TEST – reject_past_expiry refuses sessions that are already expired
+//
+// - With the flag on, sessions expiring before or exactly at now fail with ExpiryInPast
+// - A session expiring one second from now is accepted
+// - With the flag off, a past expiry is still written
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_reject_past_expiry() {
+    let (test_data, mut runner) = setup_with_config(|config| config.reject_past_expiry = true);
+    let wallet_addr = test_data.wallet.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    let (past, now, just_future) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let now_ts = registry.now_ts(state).unwrap();
+        let set_session = |expires_at| CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at,
+        };
+
+        (
+            registry.simulate_call(&signer_addr, set_session(now_ts - 1), state),
+            registry.simulate_call(&signer_addr, set_session(now_ts), state),
+            registry.simulate_call(&signer_addr, set_session(now_ts + 1), state),
+        )
+    });
+    let expiry_in_past = Some(SessionRegistryError::ExpiryInPast.to_string());
+    assert_eq!(past.error, expiry_in_past);
+    assert_eq!(now.error, expiry_in_past);
+    assert!(
+        just_future.success,
+        "A just-future expiry should be accepted"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 1,
+        },
+        false,
+        "SetSession with a past expiry should fail when the flag is on",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+
+    let (test_data, mut runner) = setup();
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 1,
+        },
+        true,
+        "SetSession with a past expiry should succeed when the flag is off",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).unwrap().expiry_ts,
+        1
+    );
+}
+
+//
+// TEST – force-enforced wallets are checked while global enforcement is off
+//
+// - Owner disables global enforcement
+// - Manager force-enforces wallet, emitting ForceEnforceSet
+// - DEX enforcement fails for wallet (no session) but passes for wallet2
+// - After the override is lifted, wallet passes again
+//
+#[test]
+fn test_force_enforce() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementEnabled { enabled: false },
+        true,
+        "SetEnforcementEnabled should succeed for owner",
+    );
+
+    let expected = Event::ForceEnforceSet {
+        wallet: wallet_addr.clone(),
+        enabled: true,
+    };
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::SetForceEnforce {
+                    wallet: wallet_addr.clone(),
+                    enabled: true,
+                },
+            ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetForceEnforce should succeed for manager"
+            );
+            assert_eq!(registry_events(&result.events), vec![expected]);
+        }),
+    });
+
+    for (wallet, expected) in [(&wallet_addr, false), (&wallet2_addr, true)] {
+        execute_dex_call(
+            &mut runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionActive {
+                wallet: wallet.clone(),
+            },
+            expected,
+            "Only the force-enforced wallet should need an active session",
+        );
+        execute_dex_call(
+            &mut runner,
+            &test_data.wallet,
+            DexCallMessage::EnforceSessionPresent {
+                wallet: wallet.clone(),
+            },
+            expected,
+            "Only the force-enforced wallet should need a present session",
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetForceEnforce {
+            wallet: wallet_addr.clone(),
+            enabled: false,
+        },
+        true,
+        "Lifting the override should succeed for manager",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr,
+        },
+        true,
+        "Without the override, enforcement should be off for the wallet again",
+    );
+}
+
+//
+// TEST – scoped checks honor force-enforce and emit bypass events
+//
+// - With global enforcement off and bypass events on, manager force-enforces wallet
+// - A scope without a mode follows the override: the scoped check fails for wallet (no session)
+// - A scope set to disabled keeps its mode: the check passes, emitting EnforcementBypassed
+// - For wallet2, the scope without a mode passes, emitting EnforcementBypassed
+//
+#[test]
+fn test_force_enforce_on_follow_scope() {
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.enforcement_enabled = false;
+        config.emit_bypass_events = true;
+    });
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let follow = ScopeId::new("follow");
+    let view = ScopeId::new("view");
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementScope {
+            scope: view.clone(),
+            mode: Some(EnforcementMode::Disabled),
+        },
+        true,
+        "SetEnforcementScope should succeed for owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetForceEnforce {
+            wallet: wallet_addr.clone(),
+            enabled: true,
+        },
+        true,
+        "SetForceEnforce should succeed for manager",
+    );
+
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActiveForScope {
+            wallet: wallet_addr.clone(),
+            scope: follow.clone(),
+        },
+        false,
+        "A force-enforced wallet should need a session on a scope without a mode",
+    );
+
+    for (wallet, scope) in [(wallet_addr, view), (wallet2_addr, follow)] {
+        let expected = vec![Event::EnforcementBypassed {
+            wallet: wallet.clone(),
+            check: EnforceKind::Active,
+        }];
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .wallet
+                .create_plain_message::<TestRuntime<S>, TestDex<S>>(
+                    DexCallMessage::EnforceSessionActiveForScope { wallet, scope },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "Scoped checks should pass while enforcement is off for the wallet"
+                );
+                assert_eq!(registry_events(&result.events), expected);
+            }),
+        });
+    }
+}
+
+//
+// TEST – ExtendSession pushes the expiry out and keeps bypass
+//
+// - Extending an existing session adds to its expiry_ts and keeps its bypass flag
+// - Extending a wallet without a session starts from the current chain time
+// - A negative extension fails
+// - A zero extension leaves the session untouched
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_extend_session() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let signer_addr = test_data.signer.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::ExtendSession {
+            wallet: wallet_addr.clone(),
+            additional_seconds: 3600,
+        },
+        true,
+        "ExtendSession should succeed for an existing session",
+    );
+    let session = read_session(&mut runner, &wallet_addr).unwrap();
+    assert_eq!(session.expiry_ts, 2764177788 + 3600);
+    assert!(session.bypass, "ExtendSession should keep the bypass flag");
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::ExtendSession {
+            wallet: wallet_addr.clone(),
+            additional_seconds: 0,
+        },
+        true,
+        "ExtendSession by 0 should succeed",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr).unwrap(), session);
+
+    // Simulating against a single state view pins `now` for the fresh session.
+    let (now_ts, fresh, negative, zero) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let now_ts = registry.now_ts(state).unwrap();
+        let extend = |additional_seconds| CallMessage::ExtendSession {
+            wallet: wallet2_addr.clone(),
+            additional_seconds,
+        };
+
+        let negative = registry.simulate_call(&signer_addr, extend(-60), state);
+        let zero = registry.simulate_call(&signer_addr, extend(0), state);
+        let zero = (
+            zero.error,
+            registry.sessions.get(&wallet2_addr, state).unwrap(),
+        );
+        let fresh = registry.simulate_call(&signer_addr, extend(60), state);
+        (
+            now_ts,
+            registry.sessions.get(&wallet2_addr, state).unwrap(),
+            negative,
+            zero,
+        )
+    });
+    assert_eq!(
+        zero,
+        (None, None),
+        "a zero extension should not create a session"
+    );
+    assert_eq!(fresh.unwrap().expiry_ts, now_ts + 60);
+    assert_eq!(
+        negative.error,
+        Some(SessionRegistryError::InvalidTtl.to_string())
+    );
+}
+
+//
+// TEST – effective_expiry applies the global offset
+//
+// - With an offset of 60, a wallet's effective expiry is its expiry_ts plus 60
+// - Unknown wallets have no effective expiry
+//
+#[test]
+fn test_effective_expiry() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset { new_offset: 60 },
+        true,
+        "SetExpiryOffset should succeed for owner",
+    );
+
+    let (known, unknown) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.effective_expiry(&wallet_addr, state).unwrap(),
+            registry.effective_expiry(&wallet2_addr, state).unwrap(),
+        )
+    });
+    assert_eq!(known, Some(2764177788 + 60));
+    assert_eq!(unknown, None);
+}
+
+//
+// TEST – pausing the registry blocks every enforce check
+//
+// - A wallet with an active session passes DEX enforcement
+// - Owner pauses the registry, emitting PausedSet
+// - Active, present and scoped enforcement all fail for the wallet
+// - Owner unpauses; the wallet passes again
+//
+#[test]
+fn test_paused_registry() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+
+    let gated_calls = || {
+        vec![
+            DexCallMessage::EnforceSessionActive {
+                wallet: wallet_addr.clone(),
+            },
+            DexCallMessage::EnforceSessionPresent {
+                wallet: wallet_addr.clone(),
+            },
+            DexCallMessage::EnforceSessionActiveForScope {
+                wallet: wallet_addr.clone(),
+                scope: ScopeId::new("trade"),
+            },
+        ]
+    };
+
+    for paused in [false, true, false] {
+        let expected = Event::PausedSet { paused };
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .owner
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                    CallMessage::SetPaused { paused },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "SetPaused should succeed for owner"
+                );
+                assert_eq!(registry_events(&result.events), vec![expected]);
+            }),
+        });
+
+        for msg in gated_calls() {
+            execute_dex_call(
+                &mut runner,
+                &test_data.wallet,
+                msg,
+                !paused,
+                "Enforcement should fail exactly while the registry is paused",
+            );
+        }
+    }
+}
+
+//
+// TEST – created_ts records when a session was first written
+//
+// - The first SetSession stamps created_ts with the current chain time
+// - Later SetSession updates and bypass changes keep it
+// - A bypass-only record created by SetBypass is stamped too
+//
+#[test]
+fn test_session_created_ts() {
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+
+    let now_ts = runner.query_state(|state| SessionRegistry::<S>::default().now_ts(state).unwrap());
+    let created_ts = read_session(&mut runner, &wallet_addr).unwrap().created_ts;
+    assert!(created_ts > 0 && created_ts <= now_ts);
+
+    for expires_at in [2764177799, 2764177800] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at,
+            },
+            true,
+            "Updating a session should succeed for authorized session signer",
+        );
+        let session = read_session(&mut runner, &wallet_addr).unwrap();
+        assert_eq!(session.expiry_ts, expires_at);
+        assert_eq!(
+            session.created_ts, created_ts,
+            "Updates should keep created_ts"
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).unwrap().created_ts,
+        created_ts
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet2_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+    assert!(read_session(&mut runner, &wallet2_addr).unwrap().created_ts >= created_ts);
+}
+
+//
+// TEST – max_sessions_per_signer caps the sessions one signer holds open
+//
+// - With a cap of 2, the signer creates sessions for wallet and wallet2
+// - A third session fails, while renewing an existing one succeeds
+// - Clearing one session frees a slot for the third wallet
+// - Another signer is counted separately
+//
+#[test]
+fn test_max_sessions_per_signer() {
+    let (test_data, mut runner) =
+        setup_with_config(|config| config.max_sessions_per_signer = Some(2));
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSignersBatch {
+            signers: vec![
+                (test_data.signer.address(), true),
+                (test_data.owner.address(), true),
+            ],
+        },
+        true,
+        "SetSessionSignersBatch should succeed for manager",
+    );
+
+    let set_session = |wallet: &<S as Spec>::Address, expires_at| CallMessage::SetSession {
+        wallet: wallet.clone(),
+        expires_at,
+    };
+
+    for wallet in [&wallet_addr, &wallet2_addr] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            set_session(wallet, 2764177788),
+            true,
+            "Sessions up to the cap should succeed",
+        );
+    }
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(&wallet3_addr, 2764177788),
+        false,
+        "A session past the cap should fail",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(&wallet_addr, 2764177799),
+        true,
+        "Renewing a session should not count against the cap",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        set_session(&wallet3_addr, 2764177788),
+        true,
+        "Another signer should have its own count",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        set_session(&wallet3_addr, 0),
+        true,
+        "Clearing a session should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(&wallet2_addr, 0),
+        true,
+        "Clearing a session should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session(&wallet3_addr, 2764177788),
+        true,
+        "Clearing a session should free a slot",
+    );
+
+    let held = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .sessions_per_signer
+            .get(&test_data.signer.address(), state)
+            .unwrap()
+    });
+    assert_eq!(held, Some(2));
+}
+
+//
+// TEST – active session count tracks session presence
+//
+// - Genesis seeds the count from the configured sessions
+// - Creating, renewing and clearing sessions, singly and in batches, moves the count
+// - Bypass-only records count; force-expired sessions stay counted until cleared
+// - A failed batch leaves the count untouched
+//
+#[test]
+fn test_active_session_count() {
+    use sb_session_registry::Session;
+    use sov_test_utils::runtime::TestRunner;
+
+    let seeded = TestUser::<S>::generate_with_default_balance().address();
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.max_batch_size = 2;
+        config.sessions = vec![(
+            seeded.clone(),
+            Session {
+                expiry_ts: 2764177788,
+                bypass: false,
+                renewals: 0,
+                created_ts: 0,
+                metadata: None,
+            },
+        )];
+    });
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+
+    let count = |runner: &mut TestRunner<TestRuntime<S>, S>| {
+        runner.query_state(|state| {
+            SessionRegistry::<S>::default()
+                .active_session_count(state)
+                .unwrap()
+        })
+    };
+    assert_eq!(count(&mut runner), 1, "Genesis sessions should be counted");
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    assert_eq!(count(&mut runner), 2);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![2764177799, 2764177788],
+        },
+        true,
+        "A batch renewing one session and creating another should succeed",
+    );
+    assert_eq!(
+        count(&mut runner),
+        3,
+        "Renewals should not be counted twice"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![
+                wallet3_addr.clone(),
+                wallet_addr.clone(),
+                wallet2_addr.clone(),
+            ],
+            expiries: vec![2764177788, 0, 0],
+        },
+        false,
+        "A batch past max_batch_size should fail",
+    );
+    assert_eq!(
+        count(&mut runner),
+        3,
+        "A failed batch should not move the count"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet3_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+    assert_eq!(
+        count(&mut runner),
+        4,
+        "A bypass-only record should be counted"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ForceExpire {
+            wallet: wallet2_addr.clone(),
+        },
+        true,
+        "ForceExpire should succeed for manager",
+    );
+    assert_eq!(
+        count(&mut runner),
+        4,
+        "A force-expired session is still present"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet3_addr.clone(),
+            bypass: false,
+        },
+        true,
+        "Clearing the bypass of a bypass-only record should succeed",
+    );
+    assert_eq!(count(&mut runner), 3);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet_addr.clone(), wallet2_addr.clone()],
+            expiries: vec![0, 0],
+        },
+        true,
+        "A batch clearing both sessions should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "Clearing an absent session should succeed",
+    );
+    assert_eq!(
+        count(&mut runner),
+        1,
+        "Only the genesis session should be left"
+    );
+}
+
+//
+// TEST – session signer authorizations expire
+//
+// - A signer granted until the current chain time is already expired: SetSession fails and the
+//   signer isn't listed, though its authorization is still stored
+// - A signer granted until one hour from now can set sessions
+// - Renewing the expired signer without expiry restores its privileges
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_expired_session_signer_is_rejected() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let signer2 = &test_data.wallet2;
+    let signer2_addr = signer2.address();
+    let wallet_addr = test_data.wallet.address();
+
+    let now_ts = runner.query_state(|state| SessionRegistry::<S>::default().now_ts(state).unwrap());
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: now_ts,
+        },
+        true,
+        "Granting a signer until now should succeed",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer2_addr.clone(),
+            allowed: true,
+            expires_at: now_ts + 3600,
+        },
+        true,
+        "Granting a signer for an hour should succeed",
+    );
+
+    let stored = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .session_signers
+            .get(&signer_addr, state)
+            .unwrap()
+    });
+    assert_eq!(
+        stored,
+        Some(SignerAuthorization {
+            allowed: true,
+            expires_at: now_ts,
+        })
+    );
+    assert_session_signers(&mut runner, &[signer2_addr.clone()]);
+
+    let set_session = CallMessage::SetSession {
+        wallet: wallet_addr.clone(),
+        expires_at: 2764177788,
+    };
+    let rejected = runner.query_state(|state| {
+        SessionRegistry::<S>::default().simulate_call(&signer_addr, set_session.clone(), state)
+    });
+    assert_eq!(
+        rejected.error,
+        Some(SessionRegistryError::UnauthorizedSessionSigner.to_string())
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        set_session.clone(),
+        false,
+        "An expired signer must not set sessions",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+
+    execute_registry_call(
+        &mut runner,
+        signer2,
+        set_session,
+        true,
+        "A signer whose authorization hasn't expired should set sessions",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "Renewing the expired signer should succeed",
+    );
+    assert_session_signers(&mut runner, &[signer_addr.clone(), signer2_addr.clone()]);
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "A renewed signer should set sessions again",
+    );
+}
+
+//
+// TEST – PurgeWallet clears a session and its bypass in one call
+//
+// - A wallet with a future expiry and bypass=true passes both enforce checks
+// - A caller that is neither owner nor manager can't purge it
+// - The owner purges it: the record is gone, WalletPurged is emitted and both enforce checks fail
+// - The manager can purge a bypass-only wallet too
+//
+#[test]
+fn test_purge_wallet() {
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    for wallet in [&wallet_addr, &wallet2_addr] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::SetBypass {
+                wallet: wallet.clone(),
+                bypass: true,
+            },
+            true,
+            "SetBypass should succeed for manager",
+        );
+    }
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        true,
+        "A wallet with a session and bypass should be active",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::PurgeWallet {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "PurgeWallet should fail for a session signer",
+    );
+    assert!(read_session(&mut runner, &wallet_addr).is_some());
+
+    let purged = wallet_addr.clone();
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .owner
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(CallMessage::PurgeWallet {
+                wallet: wallet_addr.clone(),
+            }),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "PurgeWallet should succeed for owner"
+            );
+            assert_eq!(
+                registry_events(&result.events),
+                vec![Event::WalletPurged { wallet: purged }]
+            );
+        }),
+    });
+    assert_eq!(read_session(&mut runner, &wallet_addr), None);
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionActive {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "A purged wallet should not be active",
+    );
+    execute_dex_call(
+        &mut runner,
+        &test_data.wallet,
+        DexCallMessage::EnforceSessionPresent {
+            wallet: wallet_addr.clone(),
+        },
+        false,
+        "A purged wallet should not be present",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::PurgeWallet {
+            wallet: wallet2_addr.clone(),
+        },
+        true,
+        "PurgeWallet should succeed for manager",
+    );
+    assert_eq!(read_session(&mut runner, &wallet2_addr), None);
+}
+
+//
+// TEST – every registry error has a unique, stable code
+//
+// - Each variant maps to the code it was assigned, which clients may rely on
+// - No two variants share a code
+// - The coded error returned from `call` appends the code to the unchanged message
+// - A failed `call` downcasts to the coded error, which dereferences to the registry error
+//
+#[test]
+fn test_error_codes() {
+    use sb_session_registry::CodedSessionRegistryError;
+
+    let expected = [
+        (SessionRegistryError::OwnerNotInitialized, 1),
+        (SessionRegistryError::ManagerNotInitialized, 2),
+        (SessionRegistryError::UnauthorizedOwner, 3),
+        (SessionRegistryError::UnauthorizedManager, 4),
+        (SessionRegistryError::UnauthorizedSessionSigner, 5),
+        (SessionRegistryError::SessionNotActive, 6),
+        (SessionRegistryError::SessionNotPresent, 7),
+        (SessionRegistryError::InvalidBatchLengths, 8),
+        (SessionRegistryError::ManagerNotFunded, 9),
+        (SessionRegistryError::GenesisAlreadyInitialized, 10),
+        (SessionRegistryError::SessionRenewalLimitReached, 11),
+        (SessionRegistryError::SignerIsWallet, 12),
+        (SessionRegistryError::InvalidTtl, 13),
+        (SessionRegistryError::SignersFrozen, 14),
+        (SessionRegistryError::EmptyBatch, 15),
+        (SessionRegistryError::SessionTooLong, 16),
+        (SessionRegistryError::NotPendingManager, 17),
+        (SessionRegistryError::BatchTooLarge, 18),
+        (SessionRegistryError::ExpiryInPast, 19),
+        (SessionRegistryError::RegistryPaused, 20),
+        (SessionRegistryError::SignerSessionLimitReached, 21),
+        (SessionRegistryError::EnforcementFlagNotInitialized, 22),
+        (SessionRegistryError::UnauthorizedOwnerOrManager, 23),
+        (SessionRegistryError::NegativeExpiry { index: None }, 24),
+        (SessionRegistryError::OffsetTooNegative, 25),
+        (SessionRegistryError::MetadataTooLarge, 26),
+        (
+            SessionRegistryError::InvalidGenesisConfig { reason: "" },
+            27,
+        ),
+        (SessionRegistryError::ImportLocked, 28),
+        (SessionRegistryError::LastOwner, 29),
+    ];
+
+    let mut codes = std::collections::HashSet::new();
+    for (error, code) in &expected {
+        assert_eq!(error.code(), *code, "{error:?} changed its code");
+        assert!(codes.insert(error.code()), "{error:?} reuses code {code}");
+    }
+
+    let coded = CodedSessionRegistryError::from(SessionRegistryError::SignersFrozen);
+    assert_eq!(coded.code, 14);
+    assert_eq!(coded.to_string(), "Session signers are frozen (code 14)");
+    assert!(matches!(*coded, SessionRegistryError::SignersFrozen));
+}
+
+//
+// TEST – negative expiries are rejected before anything is written
+//
+// - A batch with a negative entry fails with NegativeExpiry naming the first offending index
+// - None of the batch's wallets get a session, including those before the bad entry
+// - A single SetSession with a negative expiry fails with NegativeExpiry without an index
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_negative_expiry_is_rejected() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    let batch = CallMessage::SetSessionBatch {
+        wallets: vec![
+            wallet_addr.clone(),
+            wallet2_addr.clone(),
+            wallet3_addr.clone(),
+        ],
+        expiries: vec![2764177788, -5, -1],
+    };
+    let single = CallMessage::SetSession {
+        wallet: wallet_addr.clone(),
+        expires_at: -1,
+    };
+    let (batch_result, single_result) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.simulate_call(&signer_addr, batch.clone(), state),
+            registry.simulate_call(&signer_addr, single.clone(), state),
+        )
+    });
+    assert_eq!(
+        batch_result.error,
+        Some(SessionRegistryError::NegativeExpiry { index: Some(1) }.to_string())
+    );
+    assert_eq!(
+        batch_result.error.as_deref(),
+        Some("Session expiry at index 1 is negative")
+    );
+    assert_eq!(
+        single_result.error,
+        Some(SessionRegistryError::NegativeExpiry { index: None }.to_string())
+    );
+    assert_eq!(
+        single_result.error.as_deref(),
+        Some("Session expiry is negative")
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        batch,
+        false,
+        "A batch with a negative expiry should fail",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        single,
+        false,
+        "SetSession with a negative expiry should fail",
+    );
+    for wallet in [&wallet_addr, &wallet2_addr, &wallet3_addr] {
+        assert_eq!(
+            read_session(&mut runner, wallet),
+            None,
+            "No session should be written"
+        );
+    }
+}
+
+//
+// TEST – historical status reads a session at a past slot
+//
+// - A session is set in slot 2 and cleared in slot 3 (the test runner gives every transaction
+//   its own slot after genesis)
+// - At the latest slot the wallet has no session
+// - Against the archival state of slot 2 it is reported active and present, with that slot's
+//   chain time
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_historical_wallet_status() {
+    use sov_modules_api::SlotNumber;
+
+    let (test_data, mut runner) = setup();
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for authorized session signer",
+    );
+    let set_at = runner.query_state(|state| SessionRegistry::<S>::default().now_ts(state).unwrap());
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "Clearing the session should succeed",
+    );
+
+    let (latest, past) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let mut archival = state.get_archival_at(SlotNumber::new(2));
+        (
+            registry
+                .historical_wallet_status(&wallet_addr.to_string(), None, state)
+                .unwrap(),
+            registry
+                .historical_wallet_status(&wallet_addr.to_string(), Some(2), &mut archival)
+                .unwrap(),
+        )
+    });
+
+    assert_eq!(latest.slot_number, None);
+    assert!(!latest.status.active && !latest.status.present);
+    assert_eq!(latest.status.expiry_ts, None);
+
+    assert_eq!(past.slot_number, Some(2));
+    assert!(past.status.active && past.status.present);
+    assert_eq!(past.status.expiry_ts, Some(2764177788));
+    assert_eq!(past.status.now_ts, set_at);
+
+    let json = serde_json::to_value(&past).unwrap();
+    assert_eq!(json["slot_number"], 2);
+    assert_eq!(json["active"], true);
+}
+
+//
+// TEST – SetSessionSignersBatch grants several signers at once
+//
+// - Only the manager may send the batch
+// - Three signers granted in one batch are listed and can each set a session
+//
+#[test]
+fn test_set_session_signers_batch_grants_several() {
+    let (test_data, mut runner) = setup();
+
+    let signers = [&test_data.signer, &test_data.wallet, &test_data.wallet2];
+    let signer_addrs: Vec<_> = signers.iter().map(|signer| signer.address()).collect();
+    let wallet_addrs: Vec<_> = (0..3)
+        .map(|_| TestUser::<S>::generate_with_default_balance().address())
+        .collect();
+
+    let batch = CallMessage::SetSessionSignersBatch {
+        signers: signer_addrs
+            .iter()
+            .map(|signer| (signer.clone(), true))
+            .collect(),
+    };
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        batch.clone(),
+        false,
+        "SetSessionSignersBatch should fail for owner",
+    );
+
+    let expected: Vec<_> = signer_addrs
+        .iter()
+        .map(|signer| Event::SessionSignerSet {
+            signer: signer.clone(),
+            allowed: true,
+            expires_at: 0,
+        })
+        .collect();
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(batch),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetSessionSignersBatch should succeed for manager"
+            );
+            assert_eq!(registry_events(&result.events), expected);
+        }),
+    });
+    assert_session_signers(&mut runner, &signer_addrs);
+
+    for (signer, wallet) in signers.into_iter().zip(&wallet_addrs) {
+        execute_registry_call(
+            &mut runner,
+            signer,
+            CallMessage::SetSession {
+                wallet: wallet.clone(),
+                expires_at: 2764177788,
+            },
+            true,
+            "A signer granted in the batch should set sessions",
+        );
+        assert!(read_session(&mut runner, wallet).is_some());
+    }
+}
+
+//
+// TEST – SetSessionSignerBatch grants several signers at once
+//
+// - Mismatched signers/allowed lengths fail with InvalidBatchLengths and grant nobody
+// - Only the manager may send the batch
+// - Three signers granted in one batch are listed and can each set a session
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_set_session_signer_batch() {
+    let (test_data, mut runner) = setup();
+
+    let signers = [&test_data.signer, &test_data.wallet, &test_data.wallet2];
+    let signer_addrs: Vec<_> = signers.iter().map(|signer| signer.address()).collect();
+    let wallet_addrs: Vec<_> = (0..3)
+        .map(|_| TestUser::<S>::generate_with_default_balance().address())
+        .collect();
+
+    let mismatched = CallMessage::SetSessionSignerBatch {
+        signers: signer_addrs.clone(),
+        allowed: vec![true, true],
+    };
+    let rejected = runner.query_state(|state| {
+        SessionRegistry::<S>::default().simulate_call(
+            &test_data.manager.address(),
+            mismatched.clone(),
+            state,
+        )
+    });
+    assert_eq!(
+        rejected.error,
+        Some(SessionRegistryError::InvalidBatchLengths.to_string())
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        mismatched,
+        false,
+        "SetSessionSignerBatch with mismatched lengths should fail",
+    );
+    assert_session_signers(&mut runner, &[]);
+
+    let batch = CallMessage::SetSessionSignerBatch {
+        signers: signer_addrs.clone(),
+        allowed: vec![true; 3],
+    };
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        batch.clone(),
+        false,
+        "SetSessionSignerBatch should fail for owner",
+    );
+
+    let expected: Vec<_> = signer_addrs
+        .iter()
+        .map(|signer| Event::SessionSignerSet {
+            signer: signer.clone(),
+            allowed: true,
+            expires_at: 0,
+        })
+        .collect();
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(batch),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetSessionSignerBatch should succeed for manager"
+            );
+            assert_eq!(registry_events(&result.events), expected);
+        }),
+    });
+    assert_session_signers(&mut runner, &signer_addrs);
+
+    for (signer, wallet) in signers.into_iter().zip(&wallet_addrs) {
+        execute_registry_call(
+            &mut runner,
+            signer,
+            CallMessage::SetSession {
+                wallet: wallet.clone(),
+                expires_at: 2764177788,
+            },
+            true,
+            "A signer granted in the batch should set sessions",
+        );
+        assert!(read_session(&mut runner, wallet).is_some());
+    }
+}
+
+//
+// TEST – EnforcementBypassed audits checks skipped while enforcement is off
+//
+// - With enforcement disabled, DEX enforce checks pass for a wallet without a session
+// - Opt-in on: each check emits EnforcementBypassed naming the wallet and the check
+// - Opt-in off: no event is emitted
+//
+#[test]
+fn test_enforcement_bypassed_events() {
+    for emit_bypass_events in [false, true] {
+        let (test_data, mut runner) = setup_with_config(|config| {
+            config.enforcement_enabled = false;
+            config.emit_bypass_events = emit_bypass_events;
+        });
+
+        let wallet_addr = test_data.wallet.address();
+
+        for (msg, check) in [
+            (
+                DexCallMessage::EnforceSessionActive {
+                    wallet: wallet_addr.clone(),
+                },
+                EnforceKind::Active,
+            ),
+            (
+                DexCallMessage::EnforceSessionPresent {
+                    wallet: wallet_addr.clone(),
+                },
+                EnforceKind::Present,
+            ),
+        ] {
+            let expected = if emit_bypass_events {
+                vec![Event::EnforcementBypassed {
+                    wallet: wallet_addr.clone(),
+                    check,
+                }]
+            } else {
+                vec![]
+            };
+
+            runner.execute_transaction(TransactionTestCase {
+                input: test_data
+                    .wallet
+                    .create_plain_message::<TestRuntime<S>, TestDex<S>>(msg),
+                assert: Box::new(move |result, _| {
+                    assert!(
+                        result.tx_receipt.is_successful(),
+                        "Enforce checks should pass while enforcement is disabled"
+                    );
+                    assert_eq!(registry_events(&result.events), expected);
+                }),
+            });
+        }
+    }
+}
+
+//
+// TEST – min_expiry_offset bounds how negative the expiry offset can go
+//
+// - With a floor of -3600, an offset of -7200 fails with OffsetTooNegative and leaves the
+//   offset unchanged
+// - Offsets at or above the floor are accepted
+// - Without a floor, any negative offset is accepted
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_min_expiry_offset() {
+    let (test_data, mut runner) = setup_with_config(|config| config.min_expiry_offset = -3600);
+
+    let too_negative = CallMessage::SetExpiryOffset { new_offset: -7200 };
+    let rejected = runner.query_state(|state| {
+        SessionRegistry::<S>::default().simulate_call(
+            &test_data.owner.address(),
+            too_negative.clone(),
+            state,
+        )
+    });
+    assert_eq!(
+        rejected.error,
+        Some(SessionRegistryError::OffsetTooNegative.to_string())
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        too_negative,
+        false,
+        "An offset below min_expiry_offset should be rejected",
+    );
+    let offset = runner.query_state(|state| SessionRegistry::<S>::default().offset(state).unwrap());
+    assert_eq!(offset, 0, "A rejected offset must not be stored");
+
+    for new_offset in [-3600, -60, 600] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.owner,
+            CallMessage::SetExpiryOffset { new_offset },
+            true,
+            "An offset at or above min_expiry_offset should be accepted",
+        );
+        let offset =
+            runner.query_state(|state| SessionRegistry::<S>::default().offset(state).unwrap());
+        assert_eq!(offset, new_offset);
+    }
+
+    let (test_data, mut runner) = setup();
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetExpiryOffset {
+            new_offset: -1_000_000_000,
+        },
+        true,
+        "Without a floor, any offset should be accepted",
+    );
+}
+
+//
+// TEST – sessions_expiring_within lists sessions about to lapse
+//
+// - Three sessions: already expired, expiring in 1000 seconds and expiring in a day
+// - A 2000 second window captures only the middle one
+// - Clearing it drops it from the index; a negative window fails with InvalidTtl
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_sessions_expiring_within() {
+    let (test_data, mut runner) = setup();
+
+    let wallets: Vec<_> = (0..3)
+        .map(|_| TestUser::<S>::generate_with_default_balance().address())
+        .collect();
+    let now_ts = runner.query_state(|state| SessionRegistry::<S>::default().now_ts(state).unwrap());
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: test_data.signer.address(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: wallets.clone(),
+            expiries: vec![now_ts - 10, now_ts + 1000, now_ts + 86400],
+        },
+        true,
+        "SetSessionBatch should succeed for authorized session signer",
+    );
+
+    let (expiring, invalid) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.sessions_expiring_within(2000, state).unwrap(),
+            registry.sessions_expiring_within(-1, state),
+        )
+    });
+    assert_eq!(expiring, vec![wallets[1].clone()]);
+    assert_eq!(
+        invalid.unwrap_err().to_string(),
+        SessionRegistryError::InvalidTtl.to_string()
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallets[1].clone(),
+            expires_at: 0,
+        },
+        true,
+        "Clearing the expiring session should succeed",
+    );
+    let expiring = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .sessions_expiring_within(2000, state)
+            .unwrap()
+    });
+    assert_eq!(expiring, vec![]);
+    let all = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .sessions_expiring_within(86400, state)
+            .unwrap()
+    });
+    assert_eq!(all, vec![wallets[2].clone()]);
+}
+
+//
+// TEST – signers attach metadata to sessions
+//
+// - SetSessionWithMetadata stores the tag with the session
+// - A plain SetSession renewal keeps it, a new tag replaces it and an empty one clears it
+// - Deleting the session drops the tag, so a recreated session starts without one
+// - A tag longer than MAX_SESSION_METADATA_BYTES fails with MetadataTooLarge and writes nothing
+//
+#[cfg(feature = "native")]
+#[test]
+fn test_session_metadata() {
+    use sb_session_registry::MAX_SESSION_METADATA_BYTES;
+
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionWithMetadata {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+            metadata: b"device-1".to_vec(),
+        },
+        true,
+        "SetSessionWithMetadata should succeed for a signer",
+    );
+    let session = read_session(&mut runner, &wallet_addr).expect("Session should be set");
+    assert_eq!(session.expiry_ts, 2764177788);
+    assert_eq!(session.metadata, Some(b"device-1".to_vec()));
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177799,
+        },
+        true,
+        "SetSession renewal should succeed",
+    );
+    let session = read_session(&mut runner, &wallet_addr).expect("Session should be set");
+    assert_eq!(session.expiry_ts, 2764177799);
+    assert_eq!(
+        session.metadata,
+        Some(b"device-1".to_vec()),
+        "Renewal keeps the tag"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionWithMetadata {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177799,
+            metadata: b"device-2".to_vec(),
+        },
+        true,
+        "Replacing the tag should succeed",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).and_then(|session| session.metadata),
+        Some(b"device-2".to_vec())
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionWithMetadata {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177799,
+            metadata: Vec::new(),
+        },
+        true,
+        "Clearing the tag should succeed",
+    );
+    assert!(read_session(&mut runner, &wallet_addr).is_some());
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).and_then(|session| session.metadata),
+        None,
+        "An empty tag clears it"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionWithMetadata {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177799,
+            metadata: b"device-3".to_vec(),
+        },
+        true,
+        "Setting a new tag should succeed",
+    );
+    for (expires_at, message) in [(0, "Deleting the session"), (2764177788, "Recreating it")] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet_addr.clone(),
+                expires_at,
+            },
+            true,
+            message,
+        );
+    }
+    assert!(read_session(&mut runner, &wallet_addr).is_some());
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).and_then(|session| session.metadata),
+        None,
+        "Deleting the session drops the tag"
+    );
+
+    let max = CallMessage::SetSessionWithMetadata {
+        wallet: wallet2_addr.clone(),
+        expires_at: 2764177788,
+        metadata: vec![7; MAX_SESSION_METADATA_BYTES],
+    };
+    let oversized = CallMessage::SetSessionWithMetadata {
+        wallet: wallet2_addr.clone(),
+        expires_at: 2764177788,
+        metadata: vec![7; MAX_SESSION_METADATA_BYTES + 1],
+    };
+    let (max_result, oversized_result) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.simulate_call(&signer_addr, max.clone(), state),
+            registry.simulate_call(&signer_addr, oversized.clone(), state),
+        )
+    });
+    assert_eq!(max_result.error, None);
+    assert_eq!(
+        oversized_result.error,
+        Some(SessionRegistryError::MetadataTooLarge.to_string())
+    );
+    assert_eq!(oversized_result.code, Some(26));
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        oversized,
+        false,
+        "Oversized metadata should fail",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet2_addr),
+        None,
+        "No session should be written"
+    );
+}
+
+//
+// TEST – CheckSessionActive probes a wallet's status without reverting
+//
+// - The probe succeeds for an active wallet, a force-expired one and one without a session
+// - Each probe emits SessionChecked with the wallet's active and present flags
+//
+#[test]
+fn test_check_session_active() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let manager_addr = test_data.manager.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    for wallet in [&wallet_addr, &wallet2_addr] {
+        execute_registry_call(
+            &mut runner,
+            &test_data.signer,
+            CallMessage::SetSession {
+                wallet: wallet.clone(),
+                expires_at: 2764177788,
+            },
+            true,
+            "SetSession should succeed for signer",
+        );
+    }
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ForceExpire {
+            wallet: wallet2_addr.clone(),
+        },
+        true,
+        "ForceExpire should succeed for manager",
+    );
+
+    for (wallet, active, present) in [
+        (wallet_addr, true, true),
+        (wallet2_addr, false, true),
+        (manager_addr, false, false),
+    ] {
+        let expected = vec![Event::SessionChecked {
+            wallet: wallet.clone(),
+            active,
+            present,
+        }];
+
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .wallet
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                    CallMessage::CheckSessionActive { wallet },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "CheckSessionActive should never revert on the wallet's status"
+                );
+                assert_eq!(registry_events(&result.events), expected);
+            }),
+        });
+    }
+}
+
+//
+// TEST – expiry offset changes are audited with their author and time
+//
+// - Each SetExpiryOffset emits ExpiryOffsetUpdated with the owner and the chain time
+// - The changes are recorded in expiry_offset_history, oldest first, matching the events
+//
+#[test]
+fn test_expiry_offset_history() {
+    use sb_session_registry::ExpiryOffsetChange;
+    use std::sync::{Arc, Mutex};
+
+    let (test_data, mut runner) = setup();
+
+    let owner_addr = test_data.owner.address();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    for new_offset in [3600, -60] {
+        let events = events.clone();
+        runner.execute_transaction(TransactionTestCase {
+            input: test_data
+                .owner
+                .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                    CallMessage::SetExpiryOffset { new_offset },
+                ),
+            assert: Box::new(move |result, _| {
+                assert!(
+                    result.tx_receipt.is_successful(),
+                    "SetExpiryOffset should succeed for owner"
+                );
+                events
+                    .lock()
+                    .unwrap()
+                    .extend(registry_events(&result.events));
+            }),
+        });
+    }
+
+    let events = events.lock().unwrap().clone();
+    let changes: Vec<ExpiryOffsetChange<S>> = events
+        .iter()
+        .map(|event| match event {
+            Event::ExpiryOffsetUpdated {
+                old_offset,
+                new_offset,
+                updated_by,
+                updated_at_ts,
+            } => ExpiryOffsetChange {
+                old_offset: *old_offset,
+                new_offset: *new_offset,
+                updated_by: updated_by.clone(),
+                updated_at_ts: *updated_at_ts,
+            },
+            other => panic!("Unexpected event {other:?}"),
+        })
+        .collect();
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!((changes[0].old_offset, changes[0].new_offset), (None, 3600));
+    assert_eq!(
+        (changes[1].old_offset, changes[1].new_offset),
+        (Some(3600), -60)
+    );
+    for change in &changes {
+        assert_eq!(change.updated_by, owner_addr);
+        assert!(
+            change.updated_at_ts > 0,
+            "The change should carry the chain time"
+        );
+    }
+    assert!(changes[0].updated_at_ts <= changes[1].updated_at_ts);
+
+    let history = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .offset_history(state)
+            .unwrap()
+    });
+    assert_eq!(history, changes);
+}
+
+//
+// TEST – a session signer can revoke itself
+//
+// - Manager grants signer privileges, and the signer sets a session
+// - RevokeSelfAsSigner emits SessionSignerSet { allowed: false }, even while signers are frozen
+// - The revoked signer can no longer SetSession, nor revoke itself again
+//
+#[test]
+fn test_revoke_self_as_signer() {
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for signer",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetSignersFrozen { frozen: true },
+        true,
+        "SetSignersFrozen should succeed for owner",
+    );
+
+    let expected_signer = signer_addr.clone();
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .signer
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::RevokeSelfAsSigner,
+            ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "RevokeSelfAsSigner should succeed for a current signer"
+            );
+            assert_eq!(
+                registry_events(&result.events),
+                vec![Event::SessionSignerSet {
+                    signer: expected_signer,
+                    allowed: false,
+                    expires_at: 0,
+                }]
+            );
+        }),
+    });
+
+    let stored = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .session_signers
+            .get(&signer_addr, state)
+            .unwrap()
+    });
+    assert_eq!(stored, Some(SignerAuthorization::permanent(false)));
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr,
+            expires_at: 2764177788,
+        },
+        false,
+        "SetSession should fail once the signer revoked itself",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::RevokeSelfAsSigner,
+        false,
+        "RevokeSelfAsSigner should fail for a signer that is no longer allowed",
+    );
+}
+
+//
+// TEST – genesis rejects configs that would deploy a broken registry
+//
+// - An expiry_offset pulling sessions back by more than a year fails
+// - An expiry_offset below min_expiry_offset fails
+// - owner == manager fails only with require_distinct_manager set
+// - Each failure names the offending setting and is reported before anything is written
+//
+#[test]
+fn test_genesis_validation() {
+    use sb_session_registry::MAX_GENESIS_NEGATIVE_OFFSET_SECS;
+
+    let (test_data, mut runner) = setup();
+
+    let base = snapshot_registry(&mut runner, &[]);
+    let invalid_configs = [
+        (
+            RegistryConfig {
+                expiry_offset: -MAX_GENESIS_NEGATIVE_OFFSET_SECS - 1,
+                ..base.clone()
+            },
+            "expiry_offset pulls sessions back by more than a year",
+        ),
+        (
+            RegistryConfig {
+                expiry_offset: -120,
+                min_expiry_offset: -60,
+                ..base.clone()
+            },
+            "expiry_offset is below min_expiry_offset",
+        ),
+        (
+            RegistryConfig {
+                manager: test_data.owner.address(),
+                require_distinct_manager: true,
+                ..base.clone()
+            },
+            "owner and manager are the same address",
+        ),
+    ];
+
+    runner.query_state(|state| {
+        let mut registry = SessionRegistry::<S>::default();
+        for (config, reason) in &invalid_configs {
+            let err = registry
+                .genesis(&MockBlockHeader::from_height(0), config, state)
+                .expect_err("Genesis with an invalid config should fail");
+            assert!(
+                matches!(
+                    err.downcast_ref::<SessionRegistryError>(),
+                    Some(SessionRegistryError::InvalidGenesisConfig { reason: found })
+                        if found == reason
+                ),
+                "Unexpected error {err}"
+            );
+            assert_eq!(err.to_string(), format!("Invalid genesis config: {reason}"));
+        }
+
+        // Without the flag, a shared owner and manager passes validation and only trips over
+        // the genesis that already ran
+        let shared_keys = RegistryConfig {
+            manager: test_data.owner.address(),
+            ..base.clone()
+        };
+        let err = registry
+            .genesis(&MockBlockHeader::from_height(0), &shared_keys, state)
+            .expect_err("Second genesis should fail");
+        assert!(matches!(
+            err.downcast_ref::<SessionRegistryError>(),
+            Some(SessionRegistryError::GenesisAlreadyInitialized)
+        ));
+        assert_eq!(
+            registry.manager.get(state).unwrap(),
+            Some(test_data.manager.address()),
+            "A rejected genesis must not overwrite the manager"
+        );
+    });
+}
+
+//
+// TEST – enforce_session_active_with_reason reports why the check passed
+//
+// - A bypassed wallet passes with Bypass, a wallet with a timed session with TimeValid
+// - A wallet without a session fails with SessionNotActive
+// - With enforcement off, every wallet passes with EnforcementDisabled
+//
+#[test]
+fn test_enforce_session_active_with_reason() {
+    use sb_session_registry::ActivationReason;
+    use sov_test_utils::runtime::TestRunner;
+
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let manager_addr = test_data.manager.address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr,
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for signer",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetBypass {
+            wallet: wallet2_addr.clone(),
+            bypass: true,
+        },
+        true,
+        "SetBypass should succeed for manager",
+    );
+
+    let wallets = [wallet_addr, wallet2_addr, manager_addr];
+    let reasons = |runner: &mut TestRunner<TestRuntime<S>, S>| {
+        runner.query_state(|state| {
+            let registry = SessionRegistry::<S>::default();
+            wallets
+                .iter()
+                .map(|wallet| {
+                    registry
+                        .enforce_session_active_with_reason(wallet, state)
+                        .map_err(|e| e.to_string())
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(
+        reasons(&mut runner),
+        vec![
+            Ok(ActivationReason::TimeValid),
+            Ok(ActivationReason::Bypass),
+            Err(SessionRegistryError::SessionNotActive.to_string()),
+        ]
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::SetEnforcementEnabled { enabled: false },
+        true,
+        "SetEnforcementEnabled should succeed for owner",
+    );
+    assert_eq!(
+        reasons(&mut runner),
+        vec![Ok(ActivationReason::EnforcementDisabled); 3]
+    );
+}
+
+//
+// TEST – ImportSessions seeds sessions once
+//
+// - A non-manager can't import
+// - The manager imports a timed and a bypassed session, written verbatim with one
+//   SessionsImported event, without any of them being session signers
+// - The import is locked afterwards: a second ImportSessions fails and changes nothing
+//
+#[test]
+fn test_import_sessions() {
+    use sb_session_registry::Session;
+
+    let (test_data, mut runner) = setup();
+
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+
+    let timed = Session {
+        expiry_ts: 2764177788,
+        bypass: false,
+        renewals: 3,
+        created_ts: 1700000000,
+        metadata: Some(b"device".to_vec()),
+    };
+    let bypassed = Session {
+        expiry_ts: 0,
+        bypass: true,
+        renewals: 0,
+        created_ts: 1700000000,
+        metadata: None,
+    };
+    let entries = vec![
+        (wallet_addr.clone(), timed.clone()),
+        (wallet2_addr.clone(), bypassed.clone()),
+    ];
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::ImportSessions {
+            entries: entries.clone(),
+        },
+        false,
+        "ImportSessions should fail for a non-manager",
+    );
+
+    runner.execute_transaction(TransactionTestCase {
+        input: test_data
+            .manager
+            .create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+                CallMessage::ImportSessions {
+                    entries: entries.clone(),
+                },
+            ),
+        assert: Box::new(|result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "ImportSessions should succeed for manager"
+            );
+            assert_eq!(
+                registry_events(&result.events),
+                vec![Event::SessionsImported { count: 2 }]
+            );
+        }),
+    });
+
+    assert_eq!(read_session(&mut runner, &wallet_addr), Some(timed));
+    assert_eq!(read_session(&mut runner, &wallet2_addr), Some(bypassed));
+    let (count, allowed) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.active_session_count(state).unwrap(),
+            registry.import_allowed.get(state).unwrap(),
+        )
+    });
+    assert_eq!(count, 2);
+    assert_eq!(allowed, Some(false), "The first import should lock it");
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ImportSessions {
+            entries: vec![(
+                wallet_addr.clone(),
+                Session {
+                    expiry_ts: 1,
+                    bypass: true,
+                    renewals: 0,
+                    created_ts: 0,
+                    metadata: None,
+                },
+            )],
+        },
+        false,
+        "ImportSessions should fail once sessions were imported",
+    );
+    assert_eq!(
+        read_session(&mut runner, &wallet_addr).map(|session| session.expiry_ts),
+        Some(2764177788),
+        "A rejected import must not overwrite sessions"
+    );
+}
+
+//
+// TEST – SetOwner manages a set of concurrent owners
+//
+// - Only an owner can add owners
+// - An added owner can send owner-only calls, and remove the bootstrap owner, which then loses
+//   its privileges while `owner` keeps recording it
+// - The last remaining owner can't be removed
+//
+#[test]
+fn test_set_owner() {
+    let (test_data, mut runner) = setup();
+
+    let owner = &test_data.owner;
+    let second_owner = &test_data.wallet;
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetOwner {
+            owner: second_owner.address(),
+            allowed: true,
+        },
+        false,
+        "SetOwner should fail for a non-owner",
+    );
+
+    let second_owner_addr = second_owner.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<TestRuntime<S>, SessionRegistry<S>>(
+            CallMessage::SetOwner {
+                owner: second_owner_addr.clone(),
+                allowed: true,
+            },
+        ),
+        assert: Box::new(move |result, _| {
+            assert!(
+                result.tx_receipt.is_successful(),
+                "SetOwner should succeed for the owner"
+            );
+            assert_eq!(
+                registry_events(&result.events),
+                vec![Event::OwnerSet {
+                    owner: second_owner_addr,
+                    allowed: true,
+                }]
+            );
+        }),
+    });
+
+    execute_registry_call(
+        &mut runner,
+        second_owner,
+        CallMessage::SetPaused { paused: true },
+        true,
+        "An added owner should be able to pause",
+    );
+
+    execute_registry_call(
+        &mut runner,
+        second_owner,
+        CallMessage::SetOwner {
+            owner: owner.address(),
+            allowed: false,
+        },
+        true,
+        "An added owner should be able to remove the bootstrap owner",
+    );
+    execute_registry_call(
+        &mut runner,
+        owner,
+        CallMessage::SetPaused { paused: false },
+        false,
+        "A removed owner should no longer be able to unpause",
+    );
+
+    let (bootstrap_owner, owner_count) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        (
+            registry.owner.get(state).unwrap(),
+            registry.owner_count.get(state).unwrap(),
+        )
+    });
+    assert_eq!(bootstrap_owner, Some(owner.address()));
+    assert_eq!(owner_count, Some(1));
+
+    execute_registry_call(
+        &mut runner,
+        second_owner,
+        CallMessage::SetOwner {
+            owner: second_owner.address(),
+            allowed: false,
+        },
+        false,
+        "The last owner should not be removable",
+    );
+    execute_registry_call(
+        &mut runner,
+        second_owner,
+        CallMessage::SetPaused { paused: false },
+        true,
+        "The last owner should keep its privileges",
+    );
+}
+
+//
+// TEST – Owners seeded at genesis
+//
+// - Addresses in `owners` are owners alongside `owner`, duplicates counting once
+// - require_distinct_manager also rejects a manager listed in `owners`
+//
+#[test]
+fn test_genesis_owners() {
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.owners = vec![config.owner.clone(), config.manager.clone()];
+    });
+
+    let owner_count = runner.query_state(|state| {
+        SessionRegistry::<S>::default()
+            .owner_count
+            .get(state)
+            .unwrap()
+    });
+    assert_eq!(owner_count, Some(2));
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSignersFrozen { frozen: true },
+        true,
+        "A genesis owner should be able to freeze signers",
+    );
+
+    let config = snapshot_registry(&mut runner, &[test_data.manager.address()]);
+    assert_eq!(config.owners, vec![test_data.manager.address()]);
+    let err = RegistryConfig {
+        require_distinct_manager: true,
+        ..config
+    }
+    .validate()
+    .expect_err("A manager among the owners should fail validation");
+    assert!(matches!(
+        err,
+        SessionRegistryError::InvalidGenesisConfig {
+            reason: "manager is listed in owners"
+        }
+    ));
+}
+
+//
+// TEST – SetSessionSignersBatch is capped at max_batch_size
+//
+// - With a cap of 2, a 3-signer batch fails with BatchTooLarge and grants nobody
+// - A batch of exactly 2 signers succeeds
+//
+#[test]
+fn test_signers_batch_max_batch_size() {
+    let (test_data, mut runner) = setup_with_config(|config| config.max_batch_size = 2);
+
+    let signer_addrs = vec![
+        test_data.signer.address(),
+        test_data.wallet.address(),
+        test_data.wallet2.address(),
+    ];
+
+    #[cfg(feature = "native")]
+    {
+        let manager_addr = test_data.manager.address();
+        let signers = signer_addrs.clone();
+        let rejected = runner.query_state(|state| {
+            SessionRegistry::<S>::default().simulate_call(
+                &manager_addr,
+                CallMessage::SetSessionSignersBatch {
+                    signers: signers.into_iter().map(|signer| (signer, true)).collect(),
+                },
+                state,
+            )
+        });
+        assert_eq!(
+            rejected.error,
+            Some(SessionRegistryError::BatchTooLarge.to_string())
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSignersBatch {
+            signers: signer_addrs
+                .iter()
+                .map(|signer| (signer.clone(), true))
+                .collect(),
+        },
+        false,
+        "SetSessionSignersBatch over the cap should fail",
+    );
+    assert_session_signers(&mut runner, &[]);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSignersBatch {
+            signers: signer_addrs[..2]
+                .iter()
+                .map(|signer| (signer.clone(), true))
+                .collect(),
+        },
+        true,
+        "SetSessionSignersBatch at the cap should succeed",
+    );
+    assert_session_signers(&mut runner, &signer_addrs[..2]);
+}
+
+//
+// TEST – ImportSessions is capped at max_batch_size
+//
+// - With a cap of 2, a 3-entry import fails with BatchTooLarge, writes nothing and stays open
+// - An import of exactly 2 entries succeeds
+//
+#[test]
+fn test_import_sessions_max_batch_size() {
+    use sb_session_registry::Session;
+
+    let (test_data, mut runner) = setup_with_config(|config| config.max_batch_size = 2);
+
+    let session = Session {
+        expiry_ts: 2764177788,
+        bypass: false,
+        renewals: 0,
+        created_ts: 0,
+        metadata: None,
+    };
+    let entries: Vec<_> = [
+        test_data.signer.address(),
+        test_data.wallet.address(),
+        test_data.wallet2.address(),
+    ]
+    .into_iter()
+    .map(|wallet| (wallet, session.clone()))
+    .collect();
+
+    #[cfg(feature = "native")]
+    {
+        let manager_addr = test_data.manager.address();
+        let oversized = entries.clone();
+        let rejected = runner.query_state(|state| {
+            SessionRegistry::<S>::default().simulate_call(
+                &manager_addr,
+                CallMessage::ImportSessions { entries: oversized },
+                state,
+            )
+        });
+        assert_eq!(
+            rejected.error,
+            Some(SessionRegistryError::BatchTooLarge.to_string())
+        );
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ImportSessions {
+            entries: entries.clone(),
+        },
+        false,
+        "ImportSessions over the cap should fail",
+    );
+    for (wallet, _) in &entries {
+        assert_eq!(read_session(&mut runner, wallet), None);
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ImportSessions {
+            entries: entries[..2].to_vec(),
+        },
+        true,
+        "ImportSessions at the cap should succeed after a rejected import",
+    );
+    assert_eq!(read_session(&mut runner, &entries[1].0), Some(session));
+}
+
+//
+// TEST – ImportSessions validates entries and keeps the counters and indices consistent
+//
+// - Entries breaking a SetSession rule (negative expiry, oversized metadata, too many renewals,
+//   an expiry in the past) fail the whole import, which stays open
+// - A wallet whose session the signer created is released from the signer's quota on import
+// - active_session_count, session_wallets and session_wallet_index match the imported sessions,
+//   and an entry that is neither bypassed nor timed clears its wallet
+//
+#[test]
+fn test_import_sessions_validates_and_reconciles() {
+    use sb_session_registry::{Session, MAX_SESSION_METADATA_BYTES};
+
+    let (test_data, mut runner) = setup_with_config(|config| {
+        config.max_renewals = Some(2);
+        config.max_sessions_per_signer = Some(1);
+        config.reject_past_expiry = true;
+    });
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "SetSession should succeed for signer",
+    );
+
+    let timed = Session {
+        expiry_ts: 2764177788,
+        bypass: false,
+        renewals: 1,
+        created_ts: 1700000000,
+        metadata: None,
+    };
+    let bypassed = Session {
+        expiry_ts: 0,
+        bypass: true,
+        renewals: 0,
+        created_ts: 1700000000,
+        metadata: None,
+    };
+    let cleared = Session {
+        expiry_ts: 0,
+        bypass: false,
+        renewals: 0,
+        created_ts: 1700000000,
+        metadata: None,
+    };
+
+    let invalid_entries = [
+        Session {
+            expiry_ts: -1,
+            ..timed.clone()
+        },
+        Session {
+            metadata: Some(vec![0; MAX_SESSION_METADATA_BYTES + 1]),
+            ..timed.clone()
+        },
+        Session {
+            renewals: 3,
+            ..timed.clone()
+        },
+        Session {
+            expiry_ts: 1,
+            ..timed.clone()
+        },
+    ];
+    for invalid in invalid_entries {
+        execute_registry_call(
+            &mut runner,
+            &test_data.manager,
+            CallMessage::ImportSessions {
+                entries: vec![
+                    (wallet2_addr.clone(), bypassed.clone()),
+                    (wallet3_addr.clone(), invalid),
+                ],
+            },
+            false,
+            "An import with an invalid entry should fail",
+        );
+        assert_eq!(read_session(&mut runner, &wallet2_addr), None);
+    }
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ImportSessions {
+            entries: vec![
+                (wallet_addr.clone(), timed.clone()),
+                (wallet2_addr.clone(), bypassed.clone()),
+                (wallet3_addr.clone(), cleared),
+            ],
+        },
+        true,
+        "A valid import should succeed once the invalid ones were rejected",
+    );
+    assert_eq!(read_session(&mut runner, &wallet_addr), Some(timed));
+    assert_eq!(read_session(&mut runner, &wallet2_addr), Some(bypassed));
+    assert_eq!(read_session(&mut runner, &wallet3_addr), None);
+
+    let (count, listed, indexed, origin, held) = runner.query_state(|state| {
+        let registry = SessionRegistry::<S>::default();
+        let len = registry.session_wallets.len(state).unwrap();
+        let listed: Vec<_> = (0..len)
+            .map(|index| registry.session_wallets.get(index, state).unwrap().unwrap())
+            .collect();
+        let indexed: Vec<_> = listed
+            .iter()
+            .map(|wallet| registry.session_wallet_index.get(wallet, state).unwrap())
+            .collect();
+        (
+            registry.active_session_count(state).unwrap(),
+            listed,
+            indexed,
+            registry.session_origins.get(&wallet_addr, state).unwrap(),
+            registry
+                .sessions_per_signer
+                .get(&signer_addr, state)
+                .unwrap(),
+        )
+    });
+    assert_eq!(count, 2);
+    assert_eq!(listed.len(), 2);
+    assert!(listed.contains(&wallet_addr) && listed.contains(&wallet2_addr));
+    assert_eq!(indexed, vec![Some(0), Some(1)]);
+    assert_eq!(origin, None, "An imported session should have no origin");
+    assert_eq!(
+        held,
+        Some(0),
+        "The import should release the signer's quota"
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet3_addr.clone(),
+            expires_at: 2764177788,
+        },
+        true,
+        "The released quota should let the signer open another session",
+    );
+}
+
+//
+// TEST – JSON sessions written before renewals, created_ts and metadata still load
+//
+// - A session with only expiry_ts and bypass deserializes with the three later fields defaulted
+//
+#[test]
+fn test_session_json_defaults() {
+    use sb_session_registry::Session;
+
+    let session: Session =
+        serde_json::from_str(r#"{ "expiry_ts": 2764177788, "bypass": true }"#).unwrap();
+    assert_eq!(
+        session,
+        Session {
+            expiry_ts: 2764177788,
+            bypass: true,
+            renewals: 0,
+            created_ts: 0,
+            metadata: None,
+        }
+    );
+}
+
+//
+// TEST – clearing a session releases its bookkeeping
+//
+// - Clearing with SetSession, a clearing SetSessionBatch entry or PurgeWallet decrements
+//   active_session_count and the signer's sessions_per_signer, and drops the wallet from
+//   session_wallets and session_wallet_index
+// - ForceExpire keeps the record present, so it stays counted and indexed until it is purged
+//
+#[test]
+fn test_clear_paths_release_bookkeeping() {
+    use sov_test_utils::runtime::TestRunner;
+
+    let (test_data, mut runner) = setup();
+
+    let signer_addr = test_data.signer.address();
+    let wallet_addr = test_data.wallet.address();
+    let wallet2_addr = test_data.wallet2.address();
+    let wallet3_addr = TestUser::<S>::generate_with_default_balance().address();
+    let wallets = vec![
+        wallet_addr.clone(),
+        wallet2_addr.clone(),
+        wallet3_addr.clone(),
+    ];
+
+    // Asserts the session count, the signer's held sessions and the indexed wallets
+    let assert_bookkeeping = |runner: &mut TestRunner<TestRuntime<S>, S>,
+                              count: u64,
+                              held: u32,
+                              listed: &[&<S as Spec>::Address]| {
+        runner.query_state(|state| {
+            let registry = SessionRegistry::<S>::default();
+            assert_eq!(registry.active_session_count(state).unwrap(), count);
+            assert_eq!(
+                registry
+                    .sessions_per_signer
+                    .get(&signer_addr, state)
+                    .unwrap(),
+                Some(held)
+            );
+            assert_eq!(
+                registry.session_wallets.len(state).unwrap(),
+                listed.len() as u64
+            );
+            for wallet in &wallets {
+                let index = registry.session_wallet_index.get(wallet, state).unwrap();
+                match index {
+                    Some(index) => {
+                        assert!(listed.contains(&wallet), "{wallet:?} should not be indexed");
+                        assert_eq!(
+                            registry.session_wallets.get(index, state).unwrap().as_ref(),
+                            Some(wallet),
+                            "{wallet:?} should be listed at its index"
+                        );
+                    }
+                    None => assert!(!listed.contains(&wallet), "{wallet:?} should be indexed"),
+                }
+            }
+        })
+    };
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::SetSessionSigner {
+            signer: signer_addr.clone(),
+            allowed: true,
+            expires_at: 0,
+        },
+        true,
+        "SetSessionSigner should succeed for manager",
+    );
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: wallets.clone(),
+            expiries: vec![2764177788; 3],
+        },
+        true,
+        "SetSessionBatch should succeed for signer",
+    );
+    assert_bookkeeping(
+        &mut runner,
+        3,
+        3,
+        &[&wallet_addr, &wallet2_addr, &wallet3_addr],
+    );
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSession {
+            wallet: wallet_addr.clone(),
+            expires_at: 0,
+        },
+        true,
+        "Clearing with SetSession should succeed",
+    );
+    assert_bookkeeping(&mut runner, 2, 2, &[&wallet2_addr, &wallet3_addr]);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.signer,
+        CallMessage::SetSessionBatch {
+            wallets: vec![wallet2_addr.clone()],
+            expiries: vec![0],
+        },
+        true,
+        "Clearing with SetSessionBatch should succeed",
+    );
+    assert_bookkeeping(&mut runner, 1, 1, &[&wallet3_addr]);
+
+    // A force-expired session is still present
+    execute_registry_call(
+        &mut runner,
+        &test_data.manager,
+        CallMessage::ForceExpire {
+            wallet: wallet3_addr.clone(),
+        },
+        true,
+        "ForceExpire should succeed for manager",
+    );
+    assert_bookkeeping(&mut runner, 1, 1, &[&wallet3_addr]);
+
+    execute_registry_call(
+        &mut runner,
+        &test_data.owner,
+        CallMessage::PurgeWallet {
+            wallet: wallet3_addr.clone(),
+        },
+        true,
+        "PurgeWallet should succeed for owner",
+    );
+    assert_bookkeeping(&mut runner, 0, 0, &[]);
+}
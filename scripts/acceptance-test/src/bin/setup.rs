@@ -1,25 +1,27 @@
 use std::process::Command;
 
-use acceptance_test::fetch_and_compare::{GetItemBehavior, SlotFetcher};
+use acceptance_test::event_order::check_event_order;
+use acceptance_test::fetch_and_compare::{GetItemBehavior, SlotFetcher, DEFAULT_MAX_RESUBSCRIBES};
 use acceptance_test::{
-    cleanup_postgres_container, generate_postgres_password, get_rollup_client, interpolate_config,
-    run_soak, start_and_wait_for_postgres_ready, wait_for_sequencer_ready, Directories, Runtime,
-    Spec, API_URL, NUM_SOAK_BATCHES, POSTGRES_CONTAINER_NAME,
+    cleanup_postgres_container, generate_postgres_password, get_rollup_client,
+    get_subscription_client, interpolate_config, run_soak, start_and_wait_for_postgres_ready,
+    wait_for_sequencer_ready, Directories, SoakConfig, Spec, API_URL,
+    DEFAULT_FULL_SLOT_SAVE_INTERVAL, NUM_SOAK_BATCHES, POSTGRES_CONTAINER_NAME,
 };
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use clap::Parser;
 use sov_api_spec::types::{self, AcceptTxBody};
 
 use acceptance_test::fetch_and_compare::SlotMonitor;
+use acceptance_test::subscriptions::SubscriptionTracker;
+use acceptance_test::tx::{encode_and_sign_tx, TxFees};
+use rollup_starter::archival::{
+    TotalSupplyBatchRequest, TotalSupplyBatchResponse, MAX_BATCH_SLOTS,
+};
 use sov_api_spec::ResponseValue;
 use sov_bank::{get_token_id, Amount, CallMessage as BankCallMessage, Coins, TokenId};
 use sov_modules_api::Spec as SpecT;
-use stf_starter::sov_modules_api::capabilities::UniquenessData;
-use stf_starter::sov_modules_api::macros::config_value;
-use stf_starter::sov_modules_api::transaction::{
-    PriorityFeeBips, Transaction, UnsignedTransaction,
-};
-use stf_starter::sov_modules_api::{CryptoSpec, RawTx};
 use stf_starter::RuntimeCall;
 use tokio_stream::StreamExt;
 
@@ -58,6 +60,14 @@ fn compare_tx_info_and_accepted_tx(
     }
 }
 
+#[derive(Parser)]
+struct Args {
+    #[arg(long, default_value_t = DEFAULT_FULL_SLOT_SAVE_INTERVAL)]
+    /// Every this many soaked slots, save a full snapshot of the slot including its batches and
+    /// txs. These are much more expensive to fetch. Defaults to 25.
+    full_snapshot_interval: u64,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     // Initialize tracing subscriber with RUST_LOG environment variable, fallback to info
@@ -68,6 +78,16 @@ async fn main() -> Result<(), anyhow::Error> {
         )
         .init();
 
+    let args = Args::parse();
+    anyhow::ensure!(
+        args.full_snapshot_interval > 0,
+        "--full-snapshot-interval must be positive"
+    );
+    let soak_config = SoakConfig {
+        full_snapshot_interval: args.full_snapshot_interval,
+        ..SoakConfig::default()
+    };
+
     let directories = Directories::new()?;
     let password = generate_postgres_password()?;
     start_and_wait_for_postgres_ready(POSTGRES_CONTAINER_NAME, &password)?;
@@ -107,7 +127,7 @@ async fn main() -> Result<(), anyhow::Error> {
 
     // First, run some manual setup. This creates and checks some very simple state with expensive consistency checks.
     do_manual_setup(directories.clone()).await?;
-    let throughput_report = run_soak(directories.clone(), rollup, 3, true).await?;
+    let throughput_report = run_soak(directories.clone(), rollup, 3, true, soak_config).await?;
     std::fs::write(
         directories.output_dir.join("throughput_report.json"),
         serde_json::to_string(&throughput_report)?,
@@ -126,12 +146,17 @@ async fn do_manual_setup(directories: Directories) -> Result<(), anyhow::Error>
 
     // Send the known good txs: Create token, mint token, transfer token
     let client = get_rollup_client()?;
+    let subscription_client = get_subscription_client()?;
     let http_client = reqwest::Client::new();
 
-    let mut slot_monitor = SlotMonitor::new(&client, &directories).await?;
+    let subscriptions = SubscriptionTracker::new();
+    let mut slot_monitor =
+        SlotMonitor::new_tracked(&subscription_client, &directories, &subscriptions).await?;
 
-    let mut sequencer_events = client.subscribe_to_events().await?;
-    let mut sequencer_txs = client.subscribe_to_txs(None).await?;
+    let mut sequencer_events =
+        subscriptions.track(subscription_client.subscribe_to_events().await?);
+    let mut sequencer_txs = subscriptions.track(subscription_client.subscribe_to_txs(None).await?);
+    let mut subscribed_events = Vec::new();
 
     let ([create_token, mint, transfer], token_id) = set_txs();
     let initial_supply = get_supply(&http_client, token_id).await?;
@@ -140,10 +165,9 @@ async fn do_manual_setup(directories: Directories) -> Result<(), anyhow::Error>
     // Create the token and check consistency between the sequencer and ledger
     let response = sign_and_send_tx(create_token, &client).await?;
     assert_eq!(response.events.len(), 1);
-    assert_eq!(
-        response.events[0],
-        sequencer_events.next().await.unwrap().unwrap()
-    );
+    let event = sequencer_events.next().await.unwrap().unwrap();
+    assert_eq!(response.events[0], event);
+    subscribed_events.push(event);
     let accepted_tx = sequencer_txs.next().await.unwrap().unwrap();
     compare_tx_info_and_accepted_tx(&response, &accepted_tx, "Create token transaction");
 
@@ -180,10 +204,9 @@ async fn do_manual_setup(directories: Directories) -> Result<(), anyhow::Error>
     info!("First batch posted, sending mint and transfer txs");
     let response = sign_and_send_tx(mint, &client).await?;
     assert_eq!(response.events.len(), 1);
-    assert_eq!(
-        response.events[0],
-        sequencer_events.next().await.unwrap().unwrap()
-    );
+    let event = sequencer_events.next().await.unwrap().unwrap();
+    assert_eq!(response.events[0], event);
+    subscribed_events.push(event);
     let accepted_tx = sequencer_txs.next().await.unwrap().unwrap();
     compare_tx_info_and_accepted_tx(&response, &accepted_tx, "Mint transaction");
     let new_supply = get_supply(&http_client, token_id).await?;
@@ -191,10 +214,9 @@ async fn do_manual_setup(directories: Directories) -> Result<(), anyhow::Error>
 
     let response = sign_and_send_tx(transfer, &client).await?;
     assert_eq!(response.events.len(), 1);
-    assert_eq!(
-        response.events[0],
-        sequencer_events.next().await.unwrap().unwrap()
-    );
+    let event = sequencer_events.next().await.unwrap().unwrap();
+    assert_eq!(response.events[0], event);
+    subscribed_events.push(event);
     let accepted_tx = sequencer_txs.next().await.unwrap().unwrap();
     compare_tx_info_and_accepted_tx(&response, &accepted_tx, "Transfer transaction");
     let new_supply = get_supply(&http_client, token_id).await?;
@@ -226,21 +248,42 @@ async fn do_manual_setup(directories: Directories) -> Result<(), anyhow::Error>
     }
     info!("Next batch posted, fetching and comparing slots");
 
-    let last_slot = slot_monitor.prev_slot_with_children.as_ref().unwrap();
-    let slot_fetcher = SlotFetcher::new(client, &directories);
+    // Everything from here on reads the ledger, so the subscriptions can be closed
+    let last_slot = slot_monitor.prev_slot_with_children.clone().unwrap();
+    sequencer_events.close();
+    sequencer_txs.close();
+    drop(slot_monitor);
+    subscriptions.ensure_all_closed()?;
+
+    let slot_fetcher = SlotFetcher::new(client, &directories, DEFAULT_MAX_RESUBSCRIBES);
+    let mut ledger_slots = Vec::new();
     for slotnum in 0..first_subscribed_slot_number {
-        let _slot = slot_fetcher
-            .fetch_and_compare_slot(slotnum, GetItemBehavior::SaveSnapshot)
-            .await?;
+        ledger_slots.push(
+            slot_fetcher
+                .fetch_and_compare_slot(slotnum, GetItemBehavior::SaveSnapshot)
+                .await?,
+        );
     }
     for slotnum in first_subscribed_slot_number..=last_slot.number {
-        let _slot = slot_fetcher
-            .fetch_and_compare_slot(slotnum, GetItemBehavior::CheckAgainstSnapshot)
-            .await?;
+        ledger_slots.push(
+            slot_fetcher
+                .fetch_and_compare_slot(slotnum, GetItemBehavior::CheckAgainstSnapshot)
+                .await?,
+        );
     }
 
-    for slot_num in 0..=last_slot.number {
-        let supply = get_supply_archival(&http_client, token_id, Some(slot_num)).await?;
+    // The event subscription must deliver events in the same order they're embedded in the ledger
+    let ledger_events: Vec<_> = ledger_slots
+        .iter()
+        .flat_map(|slot| slot.batches.iter())
+        .flat_map(|batch| batch.txs.iter())
+        .flat_map(|tx| tx.events.iter().cloned())
+        .collect();
+    check_event_order(&subscribed_events, &ledger_events)?;
+
+    let slot_numbers: Vec<u64> = (0..=last_slot.number).collect();
+    let supplies = get_supplies_archival(&http_client, token_id, &slot_numbers).await?;
+    for (slot_num, supply) in slot_numbers.into_iter().zip(supplies) {
         if slot_num < first_non_empty_slot_number {
             assert_eq!(
                 supply,
@@ -302,35 +345,11 @@ fn save_mock_data(directories: Directories) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-fn encode_and_sign_tx(msg: RuntimeCall<Spec>) -> Result<RawTx, anyhow::Error> {
-    let utx = UnsignedTransaction::<Runtime, Spec>::new(
-        msg,
-        config_value!("CHAIN_ID"),
-        PriorityFeeBips(0),
-        Amount::new(100_000_000),
-        UniquenessData::Generation(0),
-        None,
-    );
-    let priv_key: <<Spec as SpecT>::CryptoSpec as CryptoSpec>::PrivateKey = serde_json::from_str(
-        "\"0d87c12ea7c12024b3f70a26d735874608f17c8bce2b48e6fe87389310191264\"",
-    )
-    .unwrap();
-
-    let tx: Transaction<Runtime, Spec> = Transaction::new_signed_tx(
-        &priv_key,
-        &<Runtime as sov_modules_stf_blueprint::Runtime<Spec>>::CHAIN_HASH,
-        utx,
-    );
-    let tx = RawTx::new(borsh::to_vec(&tx).unwrap());
-
-    Ok(tx)
-}
-
 async fn sign_and_send_tx(
     msg: RuntimeCall<Spec>,
     client: &sov_api_spec::Client,
 ) -> Result<ResponseValue<types::TxInfoWithConfirmation>, anyhow::Error> {
-    let tx = encode_and_sign_tx(msg)?;
+    let tx = encode_and_sign_tx(msg, TxFees::default())?;
     Ok(client
         .accept_tx(&AcceptTxBody {
             body: BASE64_STANDARD.encode(tx),
@@ -408,11 +427,66 @@ async fn get_supply_archival(
     let Some(supply) = get_from_base_url(client, &url).await? else {
         return Ok(Amount::ZERO);
     };
-    let supply = supply["amount"]
+    parse_supply(&supply)
+}
+
+fn parse_supply(supply: &serde_json::Value) -> Result<Amount, anyhow::Error> {
+    let amount = supply["amount"]
         .as_str()
         .expect(&format!("Supply not found in {}", supply.to_string()));
-    let supply = u128::from_str_radix(supply, 10)?;
-    Ok(Amount::new(supply))
+    let amount = u128::from_str_radix(amount, 10)?;
+    Ok(Amount::new(amount))
+}
+
+/// Reads the total supply at each of `slot_numbers` with batched requests of at most
+/// `MAX_BATCH_SLOTS` slots, falling back to one request per slot on nodes that don't serve the
+/// batched route.
+async fn get_supplies_archival(
+    client: &reqwest::Client,
+    token_id: TokenId,
+    slot_numbers: &[u64],
+) -> Result<Vec<Amount>, anyhow::Error> {
+    let url = format!(
+        "{}/modules/bank/tokens/{}/total-supply/batch",
+        API_URL, token_id
+    );
+    let mut supplies = Vec::with_capacity(slot_numbers.len());
+    // The route turns down requests for more than `MAX_BATCH_SLOTS` slots
+    for chunk in slot_numbers.chunks(MAX_BATCH_SLOTS) {
+        let response = client
+            .post(&url)
+            .json(&TotalSupplyBatchRequest {
+                slot_numbers: chunk.to_vec(),
+            })
+            .send()
+            .await?;
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::METHOD_NOT_ALLOWED
+        ) {
+            info!("Batched total-supply route unavailable, querying slots one by one");
+            for slot_number in &slot_numbers[supplies.len()..] {
+                supplies.push(get_supply_archival(client, token_id, Some(*slot_number)).await?);
+            }
+            return Ok(supplies);
+        }
+
+        let response: TotalSupplyBatchResponse = response.error_for_status()?.json().await?;
+        anyhow::ensure!(
+            response.supplies.len() == chunk.len(),
+            "Requested supplies for {} slots, got {}",
+            chunk.len(),
+            response.supplies.len()
+        );
+        for entry in response.supplies {
+            supplies.push(match entry.supply {
+                Some(supply) => parse_supply(&supply)?,
+                None => Amount::ZERO,
+            });
+        }
+    }
+    Ok(supplies)
 }
 
 async fn get_from_base_url(
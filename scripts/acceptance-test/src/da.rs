@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tracing::info;
+
+use crate::{Directories, NUM_SOAK_BATCHES};
+
+pub const CELESTIA_RPC_URL_ENV: &str = "ACCEPTANCE_CELESTIA_RPC_URL";
+pub const CELESTIA_GRPC_URL_ENV: &str = "ACCEPTANCE_CELESTIA_GRPC_URL";
+pub const CELESTIA_SIGNER_KEY_ENV: &str = "ACCEPTANCE_CELESTIA_SIGNER_KEY";
+pub const CELESTIA_GENESIS_HEIGHT_ENV: &str = "ACCEPTANCE_CELESTIA_GENESIS_HEIGHT";
+
+/// The DA layer the harness runs the rollup against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum DaMode {
+    /// The local mock DA, replaying the recorded `persistent_mock_da.sqlite` history.
+    #[default]
+    Mock,
+    /// A Celestia devnet, configured with the `ACCEPTANCE_CELESTIA_*` environment variables.
+    Celestia,
+}
+
+impl DaMode {
+    /// Extra `cargo run` arguments building the rollup with this DA adapter.
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            DaMode::Mock => &[],
+            DaMode::Celestia => &[
+                "--no-default-features",
+                "--features",
+                "celestia_da,mock_zkvm",
+            ],
+        }
+    }
+
+    /// The genesis file the rollup is started with.
+    pub fn genesis_path(self, directories: &Directories) -> PathBuf {
+        match self {
+            DaMode::Mock => directories.acceptance_test_dir.join("genesis.json"),
+            DaMode::Celestia => directories
+                .rollup_root
+                .join("configs/celestia/genesis.json"),
+        }
+    }
+
+    /// Whether the run replays the recorded mock DA database.
+    pub fn uses_recorded_mock_data(self) -> bool {
+        self == DaMode::Mock
+    }
+
+    /// Directory, under `output_dir`, of the slot snapshots the resync compares against.
+    ///
+    /// The mock DA snapshots are recorded by `setup`. The Celestia ones are recorded by the soak
+    /// of the previous run, since the devnet's history keeps growing.
+    pub fn snapshots_dir_name(self) -> &'static str {
+        match self {
+            DaMode::Mock => "snapshots",
+            DaMode::Celestia => "celestia-snapshots",
+        }
+    }
+
+    /// File, under `output_dir`, of the throughput report the soak is compared against.
+    ///
+    /// The mock DA report is recorded by `setup`. The Celestia one is written by the previous run.
+    pub fn throughput_report_name(self) -> &'static str {
+        match self {
+            DaMode::Mock => "throughput_report.json",
+            DaMode::Celestia => "celestia_throughput_report.json",
+        }
+    }
+
+    /// Whether the soak saves snapshots of the slots it sees, for the next run to resync against.
+    pub fn records_soak_snapshots(self) -> bool {
+        self == DaMode::Celestia
+    }
+
+    /// Number of batches the recorded snapshots must cover. A missing snapshot before that fails
+    /// the resync, a missing snapshot after it finishes the resync.
+    pub fn required_resync_batches(self) -> u64 {
+        match self {
+            DaMode::Mock => NUM_SOAK_BATCHES,
+            DaMode::Celestia => 0,
+        }
+    }
+
+    /// The rollup height to stop at, if any. The recorded mock history is `NUM_SOAK_BATCHES`
+    /// long, while a Celestia devnet's history isn't known upfront, so that rollup is only
+    /// stopped by the soak once it processed `num_batches` batches.
+    pub fn stop_height(self, num_batches: u64) -> Option<u64> {
+        match self {
+            DaMode::Mock => Some(NUM_SOAK_BATCHES + num_batches),
+            DaMode::Celestia => None,
+        }
+    }
+
+    /// The soak watchdog interval used when [`crate::fetch_and_compare::STALL_TIMEOUT_ENV`] is
+    /// not set. Celestia blocks are slower and its finalization delay holds batches back for
+    /// several blocks, so slots can be much further apart than on the mock DA.
+    pub fn default_stall_timeout(self) -> Duration {
+        match self {
+            DaMode::Mock => crate::fetch_and_compare::DEFAULT_STALL_TIMEOUT,
+            DaMode::Celestia => Duration::from_secs(300),
+        }
+    }
+}
+
+/// Connection parameters of the Celestia devnet, read from the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CelestiaParams {
+    pub rpc_url: String,
+    pub grpc_url: String,
+    /// Hex-encoded key of a Celestia account funded to publish blobs.
+    pub signer_private_key: String,
+    pub genesis_height: u64,
+}
+
+impl CelestiaParams {
+    pub fn from_env() -> Result<Self, anyhow::Error> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Reads the parameters with `lookup`, which returns the value of an environment variable.
+    ///
+    /// Only [`CELESTIA_SIGNER_KEY_ENV`] is required. The URLs default to a local devnet as
+    /// started by `integrations/`, and the genesis height to 3.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, anyhow::Error> {
+        let signer_private_key = lookup(CELESTIA_SIGNER_KEY_ENV).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} must be set to run against Celestia",
+                CELESTIA_SIGNER_KEY_ENV
+            )
+        })?;
+        let genesis_height = match lookup(CELESTIA_GENESIS_HEIGHT_ENV) {
+            None => 3,
+            Some(height) => height.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid {} value {:?}. Expected a block height",
+                    CELESTIA_GENESIS_HEIGHT_ENV,
+                    height
+                )
+            })?,
+        };
+        Ok(Self {
+            rpc_url: lookup(CELESTIA_RPC_URL_ENV)
+                .unwrap_or_else(|| "ws://127.0.0.1:26658".to_string()),
+            grpc_url: lookup(CELESTIA_GRPC_URL_ENV)
+                .unwrap_or_else(|| "http://127.0.0.1:9091".to_string()),
+            signer_private_key,
+            genesis_height,
+        })
+    }
+}
+
+/// Like [`crate::interpolate_config`], but writes the Celestia config from
+/// `celestia_rollup_config.toml`.
+pub fn interpolate_celestia_config(
+    password: &str,
+    directories: &Directories,
+    params: &CelestiaParams,
+) -> Result<(), anyhow::Error> {
+    let config_path = directories
+        .acceptance_test_dir
+        .join("celestia_rollup_config.toml");
+    info!("Reading config from: {}", config_path.display());
+    let config_content = fs::read_to_string(config_path)?;
+
+    let interpolated_config = config_content
+        .replace("{password}", password)
+        .replace("{celestia_rpc_url}", &params.rpc_url)
+        .replace("{celestia_grpc_url}", &params.grpc_url)
+        .replace("{celestia_signer_private_key}", &params.signer_private_key)
+        .replace(
+            "{celestia_genesis_height}",
+            &params.genesis_height.to_string(),
+        )
+        .replace(
+            "{rollup_data_path}",
+            &directories.rollup_data_path.display().to_string(),
+        );
+
+    let output_path = directories.output_dir.join("config.toml");
+    info!("Writing interpolated config to: {}", output_path.display());
+    fs::write(output_path, interpolated_config)?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::fmt::Debug;
+
+/// A divergence between the events delivered by the event subscription and the events embedded in
+/// the ledger's slots.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EventOrderError {
+    #[error("Event {index} arrived out of order: subscription delivered {subscribed}, ledger has {ledger}")]
+    Reordered {
+        index: usize,
+        subscribed: String,
+        ledger: String,
+    },
+    #[error("Event {index} differs: subscription delivered {subscribed}, ledger has {ledger}")]
+    Mismatch {
+        index: usize,
+        subscribed: String,
+        ledger: String,
+    },
+    #[error("Subscription delivered {subscribed} events but the ledger contains {ledger}")]
+    LengthMismatch { subscribed: usize, ledger: usize },
+}
+
+/// Checks that `subscribed` (events in the order the subscription delivered them) is exactly
+/// `ledger` (events in the order they are embedded in slots, batches and txs).
+///
+/// At the first differing position, the error is [`EventOrderError::Reordered`] if the delivered
+/// event does appear elsewhere in the ledger and [`EventOrderError::Mismatch`] otherwise.
+pub fn check_event_order<E: PartialEq + Debug>(
+    subscribed: &[E],
+    ledger: &[E],
+) -> Result<(), EventOrderError> {
+    for (index, (subscribed_event, ledger_event)) in subscribed.iter().zip(ledger).enumerate() {
+        if subscribed_event == ledger_event {
+            continue;
+        }
+
+        let subscribed = format!("{:?}", subscribed_event);
+        let ledger_repr = format!("{:?}", ledger_event);
+        return Err(if ledger.contains(subscribed_event) {
+            EventOrderError::Reordered {
+                index,
+                subscribed,
+                ledger: ledger_repr,
+            }
+        } else {
+            EventOrderError::Mismatch {
+                index,
+                subscribed,
+                ledger: ledger_repr,
+            }
+        });
+    }
+
+    if subscribed.len() != ledger.len() {
+        return Err(EventOrderError::LengthMismatch {
+            subscribed: subscribed.len(),
+            ledger: ledger.len(),
+        });
+    }
+
+    Ok(())
+}
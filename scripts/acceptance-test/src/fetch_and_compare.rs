@@ -1,11 +1,15 @@
 use sov_api_spec::types::{self, GetBatchByIdChildren, GetSlotByIdChildren, LedgerBatch, Slot};
 
+use futures::future::LocalBoxFuture;
 use futures::stream::Stream;
 use serde_json::Value;
 use sov_rollup_interface::node::ledger_api::IncludeChildren;
-use std::path::PathBuf;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio_stream::StreamExt;
 
+use crate::subscriptions::SubscriptionTracker;
 use crate::Directories;
 
 fn assert_slots_match_excluding_batches(slot1: &Slot, slot2: &Slot, description: &str) {
@@ -69,14 +73,42 @@ fn assert_slots_match_json_excluding_batches(
     Ok(())
 }
 
+/// Outcome of a snapshot comparison that didn't fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotComparison {
+    /// The slot is identical to its snapshot.
+    Matched,
+    /// The slot changed, but its snapshot was taken before the slot was finalized, so the change
+    /// can be a benign pre-finality reorg. The snapshot should be replaced by the current slot.
+    ChangedBeforeFinality,
+}
+
+/// Whether a slot, as JSON, was finalized when it was serialized.
+pub(crate) fn is_finalized(slot_json: &Value) -> bool {
+    slot_json.get("finality_status").and_then(Value::as_str) == Some("finalized")
+}
+
+/// Compares `slot` against its `snapshot`.
+///
+/// Only a change to a slot whose snapshot was finalized is an error; a snapshot taken before
+/// finality may legitimately be reorged away.
 pub fn compare_against_snapshot(
     slot: &Slot,
     snapshot: serde_json::Value,
     description: &str,
     exclude_batches: bool,
-) -> Result<(), ValidationError> {
+) -> Result<SnapshotComparison, ValidationError> {
     let slot_json = slot_to_json(slot, exclude_batches).expect("Failed to convert slot to JSON");
 
+    if slot_json != snapshot && !is_finalized(&snapshot) {
+        tracing::warn!(
+            "{}: slot {} changed before finality, re-snapshotting",
+            description,
+            slot.number
+        );
+        return Ok(SnapshotComparison::ChangedBeforeFinality);
+    }
+
     if slot_json != snapshot {
         println!("❌ {} snapshot mismatch:", description);
         println!(
@@ -89,9 +121,87 @@ pub fn compare_against_snapshot(
         );
         return Err(ValidationError::InvalidSnapshot);
     }
+    Ok(SnapshotComparison::Matched)
+}
+
+/// The first event of a tx that differs from the tx's snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Batch {batch} tx {tx}: event {index} is {actual}, but the snapshot has {expected}")]
+pub struct TxEventMismatch {
+    pub batch: u64,
+    pub tx: u64,
+    pub index: usize,
+    /// The serialized event, or `none` if the tx has fewer events than its snapshot.
+    pub actual: String,
+    /// The serialized snapshot event, or `none` if the tx has more events than its snapshot.
+    pub expected: String,
+}
+
+/// Compares the `events` of every tx in `slot_json` against the same tx in `snapshot`, both slots
+/// as JSON with their batches and txs.
+///
+/// Txs are matched by position within their batch. A tx missing from one side counts as having no
+/// events. A snapshot without batches has nothing to compare.
+pub fn compare_tx_events_against_snapshot(
+    slot_json: &Value,
+    snapshot: &Value,
+) -> Result<(), TxEventMismatch> {
+    let batches = |slot: &Value| json_array(slot, "batches");
+    let (actual_batches, expected_batches) = (batches(slot_json), batches(snapshot));
+
+    for batch_index in 0..actual_batches.len().max(expected_batches.len()) {
+        let actual_batch = actual_batches.get(batch_index);
+        let expected_batch = expected_batches.get(batch_index);
+        let txs =
+            |batch: Option<&Value>| batch.map(|batch| json_array(batch, "txs")).unwrap_or(&[]);
+        let (actual_txs, expected_txs) = (txs(actual_batch), txs(expected_batch));
+
+        for tx_index in 0..actual_txs.len().max(expected_txs.len()) {
+            let events = |tx: Option<&Value>| tx.map(|tx| json_array(tx, "events")).unwrap_or(&[]);
+            let actual_events = events(actual_txs.get(tx_index));
+            let expected_events = events(expected_txs.get(tx_index));
+            if actual_events == expected_events {
+                continue;
+            }
+
+            let index = actual_events
+                .iter()
+                .zip(expected_events)
+                .position(|(actual, expected)| actual != expected)
+                .unwrap_or(actual_events.len().min(expected_events.len()));
+            let describe = |event: Option<&Value>| match event {
+                Some(event) => event.to_string(),
+                None => "none".to_string(),
+            };
+            let number = |item: Option<&Value>, fallback: usize| {
+                item.and_then(|item| item.get("number"))
+                    .and_then(Value::as_u64)
+                    .unwrap_or(fallback as u64)
+            };
+            return Err(TxEventMismatch {
+                batch: number(expected_batch.or(actual_batch), batch_index),
+                tx: number(
+                    expected_txs.get(tx_index).or(actual_txs.get(tx_index)),
+                    tx_index,
+                ),
+                index,
+                actual: describe(actual_events.get(index)),
+                expected: describe(expected_events.get(index)),
+            });
+        }
+    }
     Ok(())
 }
 
+/// The array under `key` in the JSON object `value`, empty if there is none.
+fn json_array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
 pub fn save_slot_snapshot(slot: &Slot, output_dir: &PathBuf) -> Result<(), anyhow::Error> {
     let json = slot_to_json(slot, false)?;
     let snapshot_json = serde_json::to_string_pretty(&json)?;
@@ -109,6 +219,8 @@ pub enum ValidationError {
     MissingSnapshot(std::io::Error),
     #[error("Invalid snapshot")]
     InvalidSnapshot,
+    #[error("Failed to re-snapshot a slot that changed before finality: {0}")]
+    Resnapshot(anyhow::Error),
 }
 
 pub fn load_snapshot_json(
@@ -129,7 +241,12 @@ pub fn validate_against_snapshot(
     let json = load_snapshot_json(slot.number, output_dir)
         .map_err(|e| ValidationError::MissingSnapshot(e))?;
 
-    compare_against_snapshot(slot, json, description, false)
+    match compare_against_snapshot(slot, json, description, false)? {
+        SnapshotComparison::Matched => Ok(()),
+        SnapshotComparison::ChangedBeforeFinality => {
+            save_slot_snapshot(slot, output_dir).map_err(ValidationError::Resnapshot)
+        }
+    }
 }
 
 pub enum GetItemBehavior {
@@ -137,11 +254,53 @@ pub enum GetItemBehavior {
     DoNothing,
     CheckAgainstSnapshot,
 }
+/// A slot subscription with at-least-once delivery semantics.
+///
+/// Some subscription backends re-deliver the last slot on reconnect. An exact duplicate of the
+/// previous slot (same number, identical content) is skipped, while a different slot carrying the
+/// previous slot's number is an error.
+pub struct DedupSlots {
+    inner: Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>,
+    last: Option<Slot>,
+}
+
+impl DedupSlots {
+    pub fn new(stream: impl Stream<Item = Result<Slot, anyhow::Error>> + 'static) -> Self {
+        Self {
+            inner: Box::new(Box::pin(stream)),
+            last: None,
+        }
+    }
+
+    pub async fn next(&mut self) -> Option<Result<Slot, anyhow::Error>> {
+        loop {
+            let slot = match self.inner.next().await? {
+                Ok(slot) => slot,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(last) = &self.last {
+                if last.number == slot.number {
+                    if last == &slot {
+                        tracing::debug!("Skipping duplicate delivery of slot {}", slot.number);
+                        continue;
+                    }
+                    return Some(Err(anyhow::anyhow!(
+                        "Slot {} was delivered twice with different contents",
+                        slot.number
+                    )));
+                }
+            }
+            self.last = Some(slot.clone());
+            return Some(Ok(slot));
+        }
+    }
+}
+
 pub struct SlotMonitor {
-    slots: Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>,
-    slots_with_children: Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>,
-    finalized_slots: Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>,
-    finalized_slots_with_children: Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>,
+    slots: DedupSlots,
+    slots_with_children: DedupSlots,
+    finalized_slots: DedupSlots,
+    finalized_slots_with_children: DedupSlots,
     pub prev_slot_with_children: Option<Slot>,
     snapshots_dir: PathBuf,
     expected_slot_number: Option<u64>,
@@ -151,6 +310,16 @@ impl SlotMonitor {
     pub async fn new(
         client: &sov_api_spec::Client,
         directories: &Directories,
+    ) -> Result<Self, anyhow::Error> {
+        Self::new_tracked(client, directories, &SubscriptionTracker::new()).await
+    }
+
+    /// Like [`SlotMonitor::new`], registering the four slot subscriptions with `tracker`. They
+    /// are closed when the monitor is dropped.
+    pub async fn new_tracked(
+        client: &sov_api_spec::Client,
+        directories: &Directories,
+        tracker: &SubscriptionTracker,
     ) -> Result<Self, anyhow::Error> {
         let finalized_slots = client.subscribe_finalized_slots().await?;
         let finalized_slots_with_children = client
@@ -162,10 +331,12 @@ impl SlotMonitor {
             .await?;
 
         Ok(Self {
-            slots: Box::new(slots),
-            slots_with_children: Box::new(slots_with_children),
-            finalized_slots: Box::new(finalized_slots),
-            finalized_slots_with_children: Box::new(finalized_slots_with_children),
+            slots: DedupSlots::new(tracker.track(slots)),
+            slots_with_children: DedupSlots::new(tracker.track(slots_with_children)),
+            finalized_slots: DedupSlots::new(tracker.track(finalized_slots)),
+            finalized_slots_with_children: DedupSlots::new(
+                tracker.track(finalized_slots_with_children),
+            ),
             prev_slot_with_children: None,
             snapshots_dir: directories.snapshots_dir.clone(),
             expected_slot_number: None,
@@ -270,18 +441,230 @@ impl SlotMonitor {
     }
 }
 
+/// A batch reported a different hash depending on how it was fetched.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Batch {number}: fetched {method}, hash is {actual}, expected {expected}")]
+pub struct BatchHashMismatch {
+    pub number: u64,
+    pub method: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Checks that every fetch of batch `number` reports the hash it was looked up by.
+///
+/// The batch hash commits to the DA blob, which the ledger API doesn't expose, so it can't be
+/// recomputed client-side; instead the hash must be identical across all fetch methods.
+pub fn check_batch_hashes<H: PartialEq + std::fmt::Debug>(
+    number: u64,
+    expected: &H,
+    fetched: &[(&'static str, &H)],
+) -> Result<(), BatchHashMismatch> {
+    for (method, actual) in fetched {
+        if *actual != expected {
+            return Err(BatchHashMismatch {
+                number,
+                method: *method,
+                expected: format!("{:?}", expected),
+                actual: format!("{:?}", actual),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// No new slot arrived within the watchdog interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Rollup appears stalled: no new slot within {timeout:?}")]
+pub struct SlotStalled {
+    pub timeout: Duration,
+}
+
+/// Environment variable overriding the soak watchdog interval, in seconds.
+pub const STALL_TIMEOUT_ENV: &str = "ACCEPTANCE_STALL_TIMEOUT_SECS";
+
+/// How long the soak waits for a new slot before declaring the rollup stalled.
+pub const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Reads the soak watchdog interval from [`STALL_TIMEOUT_ENV`], defaulting to
+/// [`DEFAULT_STALL_TIMEOUT`].
+pub fn stall_timeout_from_env() -> Result<Duration, anyhow::Error> {
+    stall_timeout_from_env_or(DEFAULT_STALL_TIMEOUT)
+}
+
+/// Like [`stall_timeout_from_env`], defaulting to `default`.
+pub fn stall_timeout_from_env_or(default: Duration) -> Result<Duration, anyhow::Error> {
+    match std::env::var(STALL_TIMEOUT_ENV) {
+        Err(_) => Ok(default),
+        Ok(secs) => match secs.parse::<u64>() {
+            Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+            _ => anyhow::bail!(
+                "Invalid {} value {:?}. Expected a positive number of seconds",
+                STALL_TIMEOUT_ENV,
+                secs
+            ),
+        },
+    }
+}
+
+/// Environment variable selecting how [`SlotFetcher`] learns about new slots (`subscribe` or `poll`).
+pub const SLOT_FETCH_MODE_ENV: &str = "ACCEPTANCE_SLOT_FETCH_MODE";
+
+/// How [`SlotFetcher`] learns about new slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlotFetchMode {
+    /// Use the node's websocket slot subscription.
+    #[default]
+    Subscribe,
+    /// Repeatedly query `get_slot_by_id` for the next expected slot number.
+    /// Works against nodes that don't expose the subscription endpoint.
+    Poll(PollConfig),
+}
+
+impl SlotFetchMode {
+    /// Reads the mode from [`SLOT_FETCH_MODE_ENV`], defaulting to [`SlotFetchMode::Subscribe`].
+    pub fn from_env() -> Result<Self, anyhow::Error> {
+        match std::env::var(SLOT_FETCH_MODE_ENV) {
+            Err(_) => Ok(Self::default()),
+            Ok(mode) => match mode.as_str() {
+                "subscribe" => Ok(Self::Subscribe),
+                "poll" => Ok(Self::Poll(PollConfig::default())),
+                other => anyhow::bail!(
+                    "Unknown {} value {:?}. Expected \"subscribe\" or \"poll\"",
+                    SLOT_FETCH_MODE_ENV,
+                    other
+                ),
+            },
+        }
+    }
+}
+
+/// Backoff settings for [`SlotFetchMode::Poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollConfig {
+    /// Delay before re-querying a slot that isn't available yet.
+    pub interval: Duration,
+    /// Upper bound for the exponentially growing delay.
+    pub max_backoff: Duration,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Builds a slot stream by calling `fetch` for consecutive slot numbers starting at `start`.
+///
+/// `fetch` returns `Ok(None)` while the requested slot doesn't exist yet, in which case the
+/// stream sleeps (backing off exponentially up to `config.max_backoff`) and asks again.
+pub fn poll_slot_stream<F, Fut>(
+    start: u64,
+    config: PollConfig,
+    fetch: F,
+) -> impl Stream<Item = Result<Slot, anyhow::Error>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<Option<Slot>, anyhow::Error>>,
+{
+    futures::stream::unfold((start, fetch), move |(next, mut fetch)| async move {
+        let mut delay = config.interval;
+        loop {
+            match fetch(next).await {
+                Ok(Some(slot)) => return Some((Ok(slot), (next + 1, fetch))),
+                Ok(None) => {
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(config.max_backoff);
+                }
+                Err(e) => return Some((Err(e), (next, fetch))),
+            }
+        }
+    })
+}
+
+/// Retry settings for [`retry_while_missing`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the second attempt. Doubles with every further attempt.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Calls `fetch` until it returns a value, at most `config.attempts` times.
+///
+/// `fetch` returns `Ok(None)` while the value isn't available yet, e.g. when the API lags behind
+/// the slot stream, in which case it's retried after a growing backoff. Errors are returned
+/// right away without retrying.
+pub async fn retry_while_missing<T, F, Fut>(
+    config: RetryConfig,
+    mut fetch: F,
+) -> Result<T, anyhow::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>, anyhow::Error>>,
+{
+    let mut delay = config.initial_backoff;
+    for attempt in 1..=config.attempts {
+        if let Some(value) = fetch().await? {
+            return Ok(value);
+        }
+        if attempt < config.attempts {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+    anyhow::bail!("Still not available after {} attempts", config.attempts)
+}
+
+/// Number of times [`SlotFetcher`] reopens a dropped slot stream in a row before giving up.
+pub const DEFAULT_MAX_RESUBSCRIBES: u32 = 5;
+
+/// Delay before the first attempt to reopen a dropped slot stream. Doubles with every attempt.
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_millis(500);
+
+type SlotStream = Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>;
+
+/// Reopens a dropped slot stream so that it starts at the given slot number.
+type Resubscribe =
+    Box<dyn FnMut(u64) -> LocalBoxFuture<'static, Result<SlotStream, anyhow::Error>>>;
+
 pub struct SlotFetcher {
     client: sov_api_spec::Client,
     output_dir: PathBuf,
-    stream: Option<Box<dyn Stream<Item = Result<Slot, anyhow::Error>> + Unpin>>,
+    stream: Option<SlotStream>,
+    resubscribe: Option<Resubscribe>,
+    max_resubscribes: u32,
+    last_slot_number: Option<u64>,
 }
 
 impl SlotFetcher {
-    pub fn new(client: sov_api_spec::Client, directories: &Directories) -> Self {
+    /// Creates a fetcher that reopens a dropped slot stream up to `max_resubscribes` times in a
+    /// row before failing.
+    pub fn new(
+        client: sov_api_spec::Client,
+        directories: &Directories,
+        max_resubscribes: u32,
+    ) -> Self {
         Self {
             client,
             output_dir: directories.snapshots_dir.clone(),
             stream: None,
+            resubscribe: None,
+            max_resubscribes,
+            last_slot_number: None,
         }
     }
 
@@ -291,11 +674,148 @@ impl SlotFetcher {
             .subscribe_slots_with_children(IncludeChildren::new(include_children))
             .await?;
         self.stream = Some(Box::new(stream));
+
+        let client = self.client.clone();
+        self.resubscribe = Some(Box::new(move |next| {
+            Box::pin(resubscribe_slots(client.clone(), next, include_children))
+        }));
         Ok(())
     }
 
+    /// Polls the node for slots starting at `start_slot` instead of subscribing.
+    pub fn poll_slots(&mut self, start_slot: u64, include_children: bool, config: PollConfig) {
+        let client = self.client.clone();
+        let poll = move |start_slot| -> SlotStream {
+            let client = client.clone();
+            Box::new(Box::pin(poll_slot_stream(
+                start_slot,
+                config,
+                move |slot_number| fetch_slot(client.clone(), slot_number, include_children),
+            )))
+        };
+        self.stream = Some(poll(start_slot));
+        self.resubscribe = Some(Box::new(move |next| {
+            let stream = poll(next);
+            Box::pin(async move { Ok(stream) })
+        }));
+    }
+
+    /// Starts delivering slots according to `mode`. Polling starts at `start_slot`.
+    pub async fn start_slots(
+        &mut self,
+        mode: SlotFetchMode,
+        start_slot: u64,
+        include_children: bool,
+    ) -> Result<(), anyhow::Error> {
+        match mode {
+            SlotFetchMode::Subscribe => self.subscribe_slots(include_children).await,
+            SlotFetchMode::Poll(config) => {
+                self.poll_slots(start_slot, include_children, config);
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the slot source backing [`SlotFetcher::next_slot`]. The stream isn't reopened
+    /// once it ends or fails.
+    pub fn set_slot_stream(
+        &mut self,
+        stream: impl Stream<Item = Result<Slot, anyhow::Error>> + 'static,
+    ) {
+        self.stream = Some(Box::new(Box::pin(stream)));
+        self.resubscribe = None;
+    }
+
+    /// Like [`SlotFetcher::set_slot_stream`], reopening the stream with `resubscribe` when it
+    /// ends or fails. `resubscribe` is called with the number of the next expected slot.
+    pub fn set_resubscribable_slot_stream<S, F, Fut>(&mut self, stream: S, mut resubscribe: F)
+    where
+        S: Stream<Item = Result<Slot, anyhow::Error>> + 'static,
+        F: FnMut(u64) -> Fut + 'static,
+        Fut: Future<Output = Result<S, anyhow::Error>> + 'static,
+    {
+        self.stream = Some(Box::new(Box::pin(stream)));
+        self.resubscribe = Some(Box::new(move |next| {
+            let stream = resubscribe(next);
+            Box::pin(async move { Ok(Box::new(Box::pin(stream.await?)) as SlotStream) })
+        }));
+    }
+
+    /// Returns the next slot, or `None` once the slot stream ended for good.
+    ///
+    /// A stream that ends or fails is reopened at the slot after the last one returned, backing
+    /// off between attempts, up to `max_resubscribes` times in a row. Slots delivered again by
+    /// the reopened stream are skipped.
     pub async fn next_slot(&mut self) -> Result<Option<Slot>, anyhow::Error> {
-        Ok(self.stream.as_mut().unwrap().next().await.transpose()?)
+        let mut resubscribes = 0;
+        loop {
+            let Some(stream) = self.stream.as_mut() else {
+                anyhow::bail!("No slot stream was started");
+            };
+            let failure = match stream.next().await {
+                Some(Ok(slot)) => {
+                    if resubscribes > 0
+                        && self
+                            .last_slot_number
+                            .is_some_and(|last| slot.number <= last)
+                    {
+                        continue;
+                    }
+                    self.last_slot_number = Some(slot.number);
+                    return Ok(Some(slot));
+                }
+                Some(Err(e)) => Some(e),
+                None => None,
+            };
+
+            let Some(resubscribe) = self.resubscribe.as_mut() else {
+                return match failure {
+                    Some(e) => Err(e),
+                    None => Ok(None),
+                };
+            };
+            if resubscribes >= self.max_resubscribes {
+                return Err(failure
+                    .unwrap_or_else(|| anyhow::anyhow!("Slot stream ended"))
+                    .context(format!(
+                        "Slot stream failed after {} resubscribes",
+                        resubscribes
+                    )));
+            }
+
+            resubscribes += 1;
+            let next = self.last_slot_number.map_or(0, |last| last + 1);
+            match &failure {
+                Some(e) => {
+                    tracing::warn!("Slot stream failed: {}. Resubscribing at slot {}", e, next)
+                }
+                None => tracing::warn!("Slot stream ended. Resubscribing at slot {}", next),
+            }
+            tokio::time::sleep(RESUBSCRIBE_BACKOFF * 2u32.pow(resubscribes - 1)).await;
+            match resubscribe(next).await {
+                Ok(stream) => self.stream = Some(stream),
+                // Keep the ended stream, so the next iteration counts another attempt
+                Err(e) => {
+                    tracing::warn!("Failed to resubscribe to slots: {}", e);
+                    self.stream = Some(Box::new(futures::stream::iter(vec![Err(e)])));
+                }
+            }
+        }
+    }
+
+    /// Like [`SlotFetcher::next_slot`], but fails with [`SlotStalled`] if no slot arrives within
+    /// `stall_timeout`.
+    pub async fn next_slot_within(
+        &mut self,
+        stall_timeout: Duration,
+    ) -> Result<Option<Slot>, anyhow::Error> {
+        match tokio::time::timeout(stall_timeout, self.next_slot()).await {
+            Ok(slot) => slot,
+            Err(_) => Err(SlotStalled {
+                timeout: stall_timeout,
+            }
+            .into()),
+        }
     }
 
     pub async fn fetch_batch_without_children(
@@ -309,6 +829,27 @@ impl SlotFetcher {
             .into_inner())
     }
 
+    /// Like [`Self::fetch_batch_without_children`], retrying with `config` while the batch isn't
+    /// found. Any other error fails right away.
+    pub async fn fetch_batch_with_retry(
+        &self,
+        batch_number: u64,
+        config: RetryConfig,
+    ) -> Result<LedgerBatch, anyhow::Error> {
+        let client = &self.client;
+        retry_while_missing(config, move || async move {
+            match client
+                .get_batch_by_id(&types::IntOrHash::Integer(batch_number), None)
+                .await
+            {
+                Ok(batch) => Ok(Some(batch.into_inner())),
+                Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+        .await
+    }
+
     pub async fn fetch_and_compare_batch(
         &self,
         batch_number: u64,
@@ -339,6 +880,16 @@ impl SlotFetcher {
             .await?
             .into_inner();
 
+        // Check that every fetch method reports the hash the batch was looked up by
+        check_batch_hashes(
+            batch_number,
+            &batch.hash,
+            &[
+                ("by hash", &batch_by_hash.hash),
+                ("by number with children", &batch_with_children.hash),
+                ("by hash with children", &batch_by_hash_with_children.hash),
+            ],
+        )?;
         // Check that the batch fetched by number matches the batch fetched by hash
         assert_eq!(batch, batch_by_hash);
         // Check that the batch fetched by number with children matches the batch fetched by hash with children
@@ -358,13 +909,7 @@ impl SlotFetcher {
         behavior: GetItemBehavior,
     ) -> Result<Slot, anyhow::Error> {
         // Fetch slot in all 4 possible ways
-        let slot_with_children = self
-            .client
-            .get_slot_by_id(
-                &types::IntOrHash::Integer(slot_number),
-                Some(GetSlotByIdChildren::_1),
-            )
-            .await?;
+        let slot_with_children = self.fetch_slot_with_children(slot_number).await?;
         let slot_without_children = self
             .client
             .get_slot_by_id(
@@ -419,7 +964,36 @@ impl SlotFetcher {
         }
 
         // Return the most complete version (with children)
-        Ok(slot_with_children.into_inner())
+        Ok(slot_with_children)
+    }
+
+    /// Fetches slot `slot_number` with its batches, txs and events, without the consistency
+    /// checks of [`Self::fetch_and_compare_slot`].
+    pub async fn fetch_slot_with_children(&self, slot_number: u64) -> Result<Slot, anyhow::Error> {
+        Ok(self
+            .client
+            .get_slot_by_id(
+                &types::IntOrHash::Integer(slot_number),
+                Some(GetSlotByIdChildren::_1),
+            )
+            .await?
+            .into_inner())
+    }
+
+    /// Writes the slots numbered `start..end`, with their children, to `path` as a single
+    /// pretty-printed JSON array, for offline analysis.
+    pub async fn export_range(
+        &self,
+        start: u64,
+        end: u64,
+        path: &Path,
+    ) -> Result<(), anyhow::Error> {
+        let mut slots = Vec::with_capacity(end.saturating_sub(start) as usize);
+        for slot_number in start..end {
+            slots.push(self.fetch_slot_with_children(slot_number).await?);
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&slots)?)?;
+        Ok(())
     }
 
     fn compare_slot_variations(
@@ -489,3 +1063,45 @@ impl SlotFetcher {
         Ok(())
     }
 }
+
+/// Fetches slot `slot_number`, or `None` if it doesn't exist yet.
+async fn fetch_slot(
+    client: sov_api_spec::Client,
+    slot_number: u64,
+    include_children: bool,
+) -> Result<Option<Slot>, anyhow::Error> {
+    let children = include_children.then_some(GetSlotByIdChildren::_1);
+    match client
+        .get_slot_by_id(&types::IntOrHash::Integer(slot_number), children)
+        .await
+    {
+        Ok(slot) => Ok(Some(slot.into_inner())),
+        Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reopens the slot subscription, first delivering the slots from `next` that were produced while
+/// the previous subscription was down.
+///
+/// The subscription is opened before catching up, so no slot falls between the two; slots it
+/// delivers that were already caught up on are dropped.
+async fn resubscribe_slots(
+    client: sov_api_spec::Client,
+    next: u64,
+    include_children: bool,
+) -> Result<SlotStream, anyhow::Error> {
+    let live = client
+        .subscribe_slots_with_children(IncludeChildren::new(include_children))
+        .await?;
+
+    let mut missed = Vec::new();
+    while let Some(slot) =
+        fetch_slot(client.clone(), next + missed.len() as u64, include_children).await?
+    {
+        missed.push(Ok(slot));
+    }
+    let caught_up = next + missed.len() as u64;
+    let live = live.filter(move |slot| !matches!(slot, Ok(slot) if slot.number < caught_up));
+    Ok(Box::new(futures::stream::iter(missed).chain(live)))
+}
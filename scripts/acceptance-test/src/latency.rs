@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// A histogram of latencies in whole milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: BTreeMap<u64, u64>,
+    len: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        *self.counts.entry(ms).or_default() += 1;
+        self.len += 1;
+    }
+
+    /// Number of recorded latencies.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The smallest recorded latency that at least `percentile` percent of the samples don't
+    /// exceed (nearest-rank), or `None` if nothing was recorded.
+    pub fn percentile_ms(&self, percentile: f64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * self.len as f64).ceil() as u64;
+        let rank = rank.max(1);
+        let mut seen = 0;
+        self.counts.iter().find_map(|(ms, count)| {
+            seen += count;
+            (seen >= rank).then_some(*ms)
+        })
+    }
+}
+
+/// Measures how long each tx takes from being accepted by the sequencer to landing in a batch.
+///
+/// Txs are identified by their tx number, which batches report as a contiguous range.
+#[derive(Debug, Clone, Default)]
+pub struct TxLatencyTracker {
+    pending: BTreeMap<u64, Instant>,
+    histogram: LatencyHistogram,
+}
+
+impl TxLatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that tx `tx_number` was accepted at `at`.
+    pub fn accepted(&mut self, tx_number: u64, at: Instant) {
+        self.pending.entry(tx_number).or_insert(at);
+    }
+
+    /// Records that every tx numbered below `tx_end` was confirmed at `at`.
+    pub fn confirmed_before(&mut self, tx_end: u64, at: Instant) {
+        let still_pending = self.pending.split_off(&tx_end);
+        for accepted_at in std::mem::replace(&mut self.pending, still_pending).into_values() {
+            self.histogram
+                .record(at.saturating_duration_since(accepted_at));
+        }
+    }
+
+    /// Number of accepted txs not confirmed yet.
+    pub fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn histogram(&self) -> &LatencyHistogram {
+        &self.histogram
+    }
+}
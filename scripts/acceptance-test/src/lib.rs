@@ -1,26 +1,47 @@
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 use rollup_starter::rollup::StarterRollup;
+use rollup_starter_soak_test::worker_seed;
 use sov_api_spec::types::{self, GetSlotByIdChildren, Slot};
 use sov_modules_api::execution_mode::Native;
 use sov_modules_api::prelude::serde;
 use sov_modules_rollup_blueprint::RollupBlueprint;
 use sov_soak_testing_lib::{SoakTestRunner, ValidityProfile};
 use std::path::PathBuf;
+use std::time::Instant;
 use std::{env, fs, process::Command, thread, time::Duration};
 use tokio::sync::watch;
 use tokio::task::JoinSet;
+use tokio_stream::StreamExt;
 use tracing::{debug, info};
 
-use crate::fetch_and_compare::{save_slot_snapshot, SlotFetcher};
+use crate::da::DaMode;
+use crate::fetch_and_compare::{
+    save_slot_snapshot, stall_timeout_from_env_or, RetryConfig, SlotFetcher, SlotStalled,
+    DEFAULT_MAX_RESUBSCRIBES,
+};
+use crate::latency::TxLatencyTracker;
+pub mod da;
+pub mod event_order;
 pub mod fetch_and_compare;
+pub mod latency;
+pub mod parse;
+pub mod resync;
+pub mod state_dump;
+pub mod subscriptions;
+pub mod tx;
 
 pub const POSTGRES_CONTAINER_NAME: &str = "postgres-acceptance-test";
 pub const API_URL: &str = "http://localhost:12348";
 
-// Save a full snapshot of the slot every N slots
-const FULL_SLOT_SAVE_INTERVAL: u64 = 25;
+/// Default of [`SoakConfig::full_snapshot_interval`].
+pub const DEFAULT_FULL_SLOT_SAVE_INTERVAL: u64 = 25;
 pub const NUM_SOAK_BATCHES: u64 = 1000;
+/// Number of soak workers used when none is given on the command line.
+pub const DEFAULT_NUM_WORKERS: u32 = 20;
+/// Errors fetching a batch within this many batches of the end of the soak are taken to mean the
+/// rollup already shut down.
+const SOAK_END_TOLERANCE_BATCHES: u64 = 15;
 
 pub type Runtime = <StarterRollup<Native> as RollupBlueprint<Native>>::Runtime;
 pub type Spec = <StarterRollup<Native> as RollupBlueprint<Native>>::Spec;
@@ -113,6 +134,14 @@ pub struct Directories {
 }
 
 impl Directories {
+    /// Like [`Directories::new`], with the snapshots directory of `da`.
+    pub fn for_da(da: DaMode) -> Result<Self, anyhow::Error> {
+        let mut directories = Self::new()?;
+        directories.snapshots_dir = directories.output_dir.join(da.snapshots_dir_name());
+        std::fs::create_dir_all(&directories.snapshots_dir).ok();
+        Ok(directories)
+    }
+
     pub fn new() -> Result<Self, anyhow::Error> {
         let acceptance_test_dir = env::var("CARGO_MANIFEST_DIR")
             .map(std::path::PathBuf::from)
@@ -168,14 +197,119 @@ pub fn interpolate_config(password: &str, directories: &Directories) -> Result<(
     Ok(())
 }
 
+/// Environment variable overriding the total request timeout of query clients, in seconds.
+pub const REQUEST_TIMEOUT_ENV: &str = "ACCEPTANCE_REQUEST_TIMEOUT_SECS";
+/// Environment variable overriding the connect timeout of every client, in seconds.
+pub const CONNECT_TIMEOUT_ENV: &str = "ACCEPTANCE_CONNECT_TIMEOUT_SECS";
+/// Environment variable overriding the read timeout of every client, in seconds.
+pub const READ_TIMEOUT_ENV: &str = "ACCEPTANCE_READ_TIMEOUT_SECS";
+
+/// Timeouts of the HTTP client backing a [`sov_api_spec::Client`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RollupClientConfig {
+    /// Total time allowed for a request, including reading the whole body. `None` disables it.
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Duration,
+    /// Maximum time between two reads of the response body.
+    pub read_timeout: Duration,
+}
+
+impl RollupClientConfig {
+    /// Configuration for one-shot queries.
+    pub fn query() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(600)),
+            connect_timeout: Duration::from_secs(60),
+            read_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Configuration for long-lived subscription streams.
+    ///
+    /// A stream stays open for the whole run and is silent between slots, so it has no total
+    /// timeout and a read timeout long enough to cover slow DA block times.
+    pub fn subscription() -> Self {
+        Self {
+            timeout: None,
+            connect_timeout: Duration::from_secs(60),
+            read_timeout: Duration::from_secs(900),
+        }
+    }
+
+    /// Applies the timeouts set in [`REQUEST_TIMEOUT_ENV`], [`CONNECT_TIMEOUT_ENV`] and
+    /// [`READ_TIMEOUT_ENV`], keeping the others.
+    pub fn with_env_overrides(self) -> Result<Self, anyhow::Error> {
+        self.with_overrides(|name| env::var(name).ok())
+    }
+
+    /// Like [`Self::with_env_overrides`], reading the variables with `lookup`.
+    ///
+    /// The request timeout only applies to configurations that have one, so it never cuts off a
+    /// subscription stream.
+    pub fn with_overrides(
+        mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, anyhow::Error> {
+        let secs_from = |name: &str| -> Result<Option<Duration>, anyhow::Error> {
+            match lookup(name) {
+                None => Ok(None),
+                Some(secs) => match secs.parse::<u64>() {
+                    Ok(secs) if secs > 0 => Ok(Some(Duration::from_secs(secs))),
+                    _ => anyhow::bail!(
+                        "Invalid {} value {:?}. Expected a positive number of seconds",
+                        name,
+                        secs
+                    ),
+                },
+            }
+        };
+
+        if let Some(timeout) = secs_from(REQUEST_TIMEOUT_ENV)? {
+            if self.timeout.is_some() {
+                self.timeout = Some(timeout);
+            }
+        }
+        if let Some(connect_timeout) = secs_from(CONNECT_TIMEOUT_ENV)? {
+            self.connect_timeout = connect_timeout;
+        }
+        if let Some(read_timeout) = secs_from(READ_TIMEOUT_ENV)? {
+            self.read_timeout = read_timeout;
+        }
+        Ok(self)
+    }
+
+    pub fn build(&self, api_url: &str) -> Result<sov_api_spec::Client, anyhow::Error> {
+        let mut builder = reqwest::ClientBuilder::new()
+            .connect_timeout(self.connect_timeout)
+            .read_timeout(self.read_timeout);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        Ok(sov_api_spec::Client::new_with_client(
+            api_url,
+            builder.build()?,
+        ))
+    }
+}
+
+/// Returns a client for one-shot queries against the rollup.
+///
+/// Its timeouts can be overridden through the environment, see
+/// [`RollupClientConfig::with_env_overrides`].
 pub fn get_rollup_client() -> Result<sov_api_spec::Client, anyhow::Error> {
-    let reqwest_client = reqwest::ClientBuilder::new()
-        .timeout(Duration::from_secs(600))
-        .connect_timeout(Duration::from_secs(60))
-        .read_timeout(Duration::from_secs(120))
-        .build()?;
-    let client = sov_api_spec::Client::new_with_client(API_URL, reqwest_client);
-    Ok(client)
+    RollupClientConfig::query()
+        .with_env_overrides()?
+        .build(API_URL)
+}
+
+/// Returns a client for subscribing to the rollup's slot, event and tx streams.
+///
+/// Like [`get_rollup_client`], its connect and read timeouts can be overridden through the
+/// environment.
+pub fn get_subscription_client() -> Result<sov_api_spec::Client, anyhow::Error> {
+    RollupClientConfig::subscription()
+        .with_env_overrides()?
+        .build(API_URL)
 }
 
 pub async fn wait_for_sequencer_ready() -> Result<(), anyhow::Error> {
@@ -211,6 +345,7 @@ async fn worker_task(
 }
 
 fn start_workers(
+    num_workers: u32,
     salt: u32,
 ) -> Result<
     (
@@ -219,18 +354,17 @@ fn start_workers(
     ),
     anyhow::Error,
 > {
-    tracing::info!("Starting {} workers", NUM_WORKERS);
-    const NUM_WORKERS: u32 = 20;
+    tracing::info!("Starting {} workers", num_workers);
     let mut worker_set = JoinSet::new();
     let (tx, rx) = tokio::sync::watch::channel(false);
     let client = get_rollup_client()?;
 
-    for i in 0..NUM_WORKERS {
+    for i in 0..num_workers {
         worker_set.spawn(worker_task(
             client.clone(),
             rx.clone(),
-            (i + salt) as u128,
-            NUM_WORKERS,
+            worker_seed(salt, i),
+            num_workers,
         ));
     }
     Ok((tx, worker_set))
@@ -247,18 +381,90 @@ fn save_slot_snapshot_if_needed(
     Ok(())
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ThroughputReport {
     pub num_txs: u64,
     pub num_slots: u64,
+    /// Percentiles of the time from the sequencer accepting a tx to the tx landing in a batch.
+    /// `None` in reports recorded before latencies were tracked.
+    #[serde(default)]
+    pub p50_ms: Option<u64>,
+    #[serde(default)]
+    pub p95_ms: Option<u64>,
+    #[serde(default)]
+    pub p99_ms: Option<u64>,
+}
+
+impl ThroughputReport {
+    /// Average number of txs per slot, or `None` if no slot was soaked.
+    pub fn txs_per_slot(&self) -> Option<f64> {
+        (self.num_slots > 0).then(|| self.num_txs as f64 / self.num_slots as f64)
+    }
+}
+
+/// Parameters of a [`run_soak`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoakConfig {
+    /// Number of concurrent workers sending txs.
+    pub num_workers: u32,
+    /// Salt of the workers' seeds, on top of the number of previous batches (see [`soak_salt`]).
+    pub salt: u32,
+    /// Number of new batches to soak for before shutting the rollup down.
+    pub num_batches: u64,
+    /// The DA layer the rollup runs on.
+    pub da: DaMode,
+    /// Every this many soaked slots, the snapshot includes the slot's children. Must be positive.
+    pub full_snapshot_interval: u64,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            num_workers: DEFAULT_NUM_WORKERS,
+            salt: 0,
+            num_batches: NUM_SOAK_BATCHES,
+            da: DaMode::default(),
+            full_snapshot_interval: DEFAULT_FULL_SLOT_SAVE_INTERVAL,
+        }
+    }
 }
 
+/// Returns the salt of the workers of a soak that follows `num_previous_batches` batches, so that
+/// they don't replay the transactions of the soaks before it.
+///
+/// Fails if the sum doesn't fit the salt, rather than wrapping around onto a salt already used.
+pub fn soak_salt(salt: u32, num_previous_batches: u64) -> Result<u32, anyhow::Error> {
+    u32::try_from(num_previous_batches)
+        .ok()
+        .and_then(|num_previous_batches| salt.checked_add(num_previous_batches))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "The salt {} is too large to offset by {} previous batches",
+                salt,
+                num_previous_batches
+            )
+        })
+}
+
+/// Soaks the rollup until `config.num_batches` new batches are processed or the rollup stops.
 pub async fn run_soak(
     directories: Directories,
     mut rollup: std::process::Child,
     num_previous_batches: u64,
     save_slot_snapshots: bool,
+    config: SoakConfig,
 ) -> Result<ThroughputReport, anyhow::Error> {
+    let SoakConfig {
+        num_workers,
+        salt,
+        num_batches,
+        da,
+        full_snapshot_interval,
+    } = config;
+    anyhow::ensure!(
+        full_snapshot_interval > 0,
+        "The full snapshot interval must be positive"
+    );
     let (rollup_tx, mut rollup_rx) = tokio::sync::oneshot::channel();
     let rollup_id = rollup.id();
     // Spawn background task to wait for rollup process
@@ -267,9 +473,37 @@ pub async fn run_soak(
         let _ = rollup_tx.send(result);
     });
 
-    let mut slot_fetcher = SlotFetcher::new(get_rollup_client()?, &directories);
+    let stall_timeout = stall_timeout_from_env_or(da.default_stall_timeout())?;
+    let mut slot_fetcher = SlotFetcher::new(
+        get_subscription_client()?,
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
     slot_fetcher.subscribe_slots(false).await?;
-    let (tx, worker_set) = start_workers(num_previous_batches as u32)?;
+
+    // Timestamp txs as the sequencer accepts them, so their latency can be measured once their
+    // batch arrives. This runs in its own task so that txs don't restart the stall watchdog.
+    let (accepted_sender, mut accepted_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let accepted_txs = get_subscription_client()?.subscribe_to_txs(None).await?;
+    let accepted_task = tokio::spawn(async move {
+        let mut accepted_txs = Box::pin(accepted_txs);
+        while let Some(accepted_tx) = accepted_txs.next().await {
+            match accepted_tx {
+                Ok(accepted_tx) => {
+                    if accepted_sender
+                        .send((accepted_tx.tx_number, Instant::now()))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(e) => tracing::warn!("Error in the accepted tx subscription: {}", e),
+            }
+        }
+    });
+    let mut latencies = TxLatencyTracker::new();
+
+    let (tx, worker_set) = start_workers(num_workers, soak_salt(salt, num_previous_batches)?)?;
 
     use tokio::signal::unix::SignalKind;
     let mut terminate = tokio::signal::unix::signal(SignalKind::terminate())
@@ -282,6 +516,7 @@ pub async fn run_soak(
     let mut num_soak_txs = 0;
     let mut num_soak_slots = 0;
     let mut num_soak_batches = 0;
+    let mut stalled = None;
     let num_previous_txs = slot_fetcher
         .fetch_batch_without_children(num_previous_batches)
         .await
@@ -293,14 +528,38 @@ pub async fn run_soak(
         tokio::select! {
             // On each slot, we update our counters and save a snapshot of the slot.
             // Every N slots, we save a full snapshot of the slot. (This is much more expensive, but also allows more thorough checks)
-            new_slot = slot_fetcher.next_slot() => {
+            // The watchdog restarts with every iteration, i.e. on every received slot.
+            new_slot = slot_fetcher.next_slot_within(stall_timeout) => {
+                let new_slot = match new_slot {
+                    Err(e) if e.is::<SlotStalled>() => {
+                        tracing::error!("{}, shutting down rollup", e);
+                        if let Ok(mut interrupt) = Command::new("kill")
+                            .args(["-s", "SIGINT", &rollup_id.to_string()])
+                            .spawn() {
+                            let _ = interrupt.wait();
+                        }
+                        stalled = Some(e);
+                        break;
+                    }
+                    new_slot => new_slot?,
+                };
 
-                if let Some(slot) = new_slot? {
+                if let Some(slot) = new_slot {
                     // Get the latest tx number after the slot
                     if slot.batch_range.start != slot.batch_range.end {
                         let batch_num = slot.batch_range.end - 1;
-                        match slot_fetcher.fetch_batch_without_children(batch_num).await {
+                        // The API can briefly lag behind the slot stream, so a missing batch is retried
+                        match slot_fetcher
+                            .fetch_batch_with_retry(batch_num, RetryConfig::default())
+                            .await
+                        {
                             Ok(batch) => {
+                                while let Ok((tx_number, accepted_at)) =
+                                    accepted_receiver.try_recv()
+                                {
+                                    latencies.accepted(tx_number, accepted_at);
+                                }
+                                latencies.confirmed_before(batch.tx_range.end, Instant::now());
                                 num_soak_txs = batch.tx_range.end.saturating_sub(num_previous_txs);
                                 // If the slot contains a batch (checked above) and we're into new batches, increment the counter
                                 if slot.batch_range.end > num_previous_batches {
@@ -310,7 +569,7 @@ pub async fn run_soak(
                             Err(e) => {
                                 // If we're very close to the end of the test, the rollup might have shut down before we could finish querying.
                                 // The test shouldn't fail for this reason, so we just skip the batch.
-                                if num_soak_batches + 15 > NUM_SOAK_BATCHES {
+                                if num_soak_batches + SOAK_END_TOLERANCE_BATCHES > num_batches {
                                     tracing::warn!("Encountered an error very near the end of the test. Assuming the rollup shut down.");
                                     break;
                                 } else {
@@ -329,7 +588,7 @@ pub async fn run_soak(
                     num_soak_slots += 1;
                     info!("Received new slot. Rollup has processed {} txs in {} slots. Average throughput: {} txs/slot", num_soak_txs, num_soak_slots, num_soak_txs as f64 / num_soak_slots as f64);
                     // Every N slots, we save a full snapshot of the slot. (This is much more expensive, but also allows more thorough checks)
-                    if num_soak_slots % FULL_SLOT_SAVE_INTERVAL == 0 {
+                    if num_soak_slots % full_snapshot_interval == 0 {
                        match client.get_slot_by_id(&types::IntOrHash::Integer(slot.number), Some(GetSlotByIdChildren::_1)).await {
                             Ok(full_slot) => {
                                 save_slot_snapshot_if_needed(&full_slot, &directories, save_slot_snapshots)?;
@@ -342,6 +601,19 @@ pub async fn run_soak(
                     } else {
                         save_slot_snapshot_if_needed(&slot, &directories, save_slot_snapshots)?;
                     }
+
+                    if num_soak_batches >= num_batches {
+                        tracing::info!(
+                            "Processed {} batches, shutting down rollup",
+                            num_soak_batches
+                        );
+                        if let Ok(mut interrupt) = Command::new("kill")
+                            .args(["-s", "SIGINT", &rollup_id.to_string()])
+                            .spawn() {
+                            let _ = interrupt.wait();
+                        }
+                        break;
+                    }
                 }
             }
             // Signal handlers
@@ -395,6 +667,11 @@ pub async fn run_soak(
 
     tx.send(true)?;
     _ = worker_set.join_all();
+    accepted_task.abort();
+
+    if let Some(stalled) = stalled {
+        return Err(stalled);
+    }
 
     // Wait for rollup to finish if it hasn't already
     if let Ok(rollup_result) = rollup_rx.try_recv() {
@@ -412,8 +689,19 @@ pub async fn run_soak(
         num_soak_slots,
         num_soak_txs as f64 / num_soak_slots as f64
     );
+    let histogram = latencies.histogram();
+    info!(
+        "Confirmed {} txs. Latency p50: {:?} ms, p95: {:?} ms, p99: {:?} ms",
+        histogram.len(),
+        histogram.percentile_ms(50.0),
+        histogram.percentile_ms(95.0),
+        histogram.percentile_ms(99.0)
+    );
     Ok(ThroughputReport {
         num_txs: num_soak_txs,
         num_slots: num_soak_slots,
+        p50_ms: histogram.percentile_ms(50.0),
+        p95_ms: histogram.percentile_ms(95.0),
+        p99_ms: histogram.percentile_ms(99.0),
     })
 }
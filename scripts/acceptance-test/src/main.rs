@@ -1,31 +1,38 @@
-use acceptance_test::fetch_and_compare::SlotFetcher;
-use acceptance_test::ThroughputReport;
+use acceptance_test::da::{interpolate_celestia_config, CelestiaParams, DaMode};
+use acceptance_test::fetch_and_compare::{SlotFetchMode, SlotFetcher, DEFAULT_MAX_RESUBSCRIBES};
+use acceptance_test::resync::{MismatchPolicy, Resync, ResyncProgress};
 use acceptance_test::{
-    cleanup_postgres_container,
-    fetch_and_compare::{compare_against_snapshot, load_snapshot_json},
-    generate_postgres_password, get_rollup_client, interpolate_config, run_soak,
-    start_and_wait_for_postgres_ready, Directories, API_URL, NUM_SOAK_BATCHES,
-    POSTGRES_CONTAINER_NAME,
+    cleanup_postgres_container, generate_postgres_password, get_rollup_client,
+    get_subscription_client, interpolate_config, run_soak, start_and_wait_for_postgres_ready,
+    Directories, API_URL, DEFAULT_NUM_WORKERS, NUM_SOAK_BATCHES, POSTGRES_CONTAINER_NAME,
 };
+use acceptance_test::{SoakConfig, ThroughputReport};
 use clap::Parser;
-use sov_api_spec::types::{self, GetSlotByIdChildren, Slot};
-use std::{process::Command, time::Duration};
+use sov_api_spec::types;
+use std::{path::PathBuf, process::Command, time::Duration};
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    // Initialize tracing subscriber with RUST_LOG environment variable, fallback to info
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug,hyper=info")),
-        )
-        .init();
+    let args = Args::parse();
+    init_logging(args.log_format);
+
+    if let Some(path) = &args.dump_state {
+        acceptance_test::state_dump::dump_state(&args.api_url, path).await?;
+        return Ok(());
+    }
 
     info!("Starting acceptance test");
 
     // Run the test
-    let result = run_test().await;
+    let mismatch_policy = if args.fail_fast {
+        MismatchPolicy::FailFast
+    } else if args.continue_on_mismatch {
+        MismatchPolicy::ContinueOnMismatch
+    } else {
+        MismatchPolicy::CheckAll
+    };
+    let result = run_test(&args, mismatch_policy).await;
     if let Err(e) = &result {
         tracing::error!("Acceptance test failed: {}", e);
     } else {
@@ -36,6 +43,19 @@ async fn main() -> Result<(), anyhow::Error> {
     result
 }
 
+/// Initializes the tracing subscriber in `format`, filtered by the `RUST_LOG` environment
+/// variable with a fallback to debug.
+fn init_logging(format: LogFormat) {
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug,hyper=info")),
+    );
+    match format {
+        LogFormat::Pretty => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
+
 fn copy_persistent_mock_data(directories: &Directories) -> Result<(), anyhow::Error> {
     tracing::info!("Copying persistent mock data back to mock_da.sqlite");
     std::fs::copy(
@@ -73,11 +93,18 @@ fn copy_persistent_mock_data(directories: &Directories) -> Result<(), anyhow::Er
     Ok(())
 }
 
-async fn run_test() -> Result<(), anyhow::Error> {
+async fn run_test(args: &Args, mismatch_policy: MismatchPolicy) -> Result<(), anyhow::Error> {
+    anyhow::ensure!(args.num_batches > 0, "--num-batches must be positive");
+
     // Generate a config file with our db password and all paths set relative to the workspace root
     let password = generate_postgres_password()?;
-    let directories = Directories::new()?;
-    interpolate_config(&password, &directories)?;
+    let directories = Directories::for_da(args.da)?;
+    match args.da {
+        DaMode::Mock => interpolate_config(&password, &directories)?,
+        DaMode::Celestia => {
+            interpolate_celestia_config(&password, &directories, &CelestiaParams::from_env()?)?
+        }
+    }
 
     tracing::info!(
         "Removing rollup data path: {}",
@@ -86,7 +113,9 @@ async fn run_test() -> Result<(), anyhow::Error> {
     std::fs::remove_dir_all(&directories.rollup_data_path)?;
 
     // Copy the persistent mock data back to mock_da.sqlite. This way we don't grow our DA files with each run.
-    copy_persistent_mock_data(&directories)?;
+    if args.da.uses_recorded_mock_data() {
+        copy_persistent_mock_data(&directories)?;
+    }
 
     // Start the sequencer postgres and wait for it to be ready
     start_and_wait_for_postgres_ready(POSTGRES_CONTAINER_NAME, &password)?;
@@ -97,10 +126,11 @@ async fn run_test() -> Result<(), anyhow::Error> {
         directories.rollup_root.display()
     );
 
-    let rollup = Command::new("cargo")
+    let mut rollup = Command::new("cargo");
+    rollup
+        .args(["run", "--release"])
+        .args(args.da.cargo_args())
         .args([
-            "run",
-            "--release",
             "--",
             "--rollup-config-path",
             &directories
@@ -109,14 +139,12 @@ async fn run_test() -> Result<(), anyhow::Error> {
                 .display()
                 .to_string(),
             "--genesis-path",
-            &directories
-                .acceptance_test_dir
-                .join("genesis.json")
-                .display()
-                .to_string(),
-            "--stop-at-rollup-height",
-            &((NUM_SOAK_BATCHES * 2).to_string()),
-        ])
+            &args.da.genesis_path(&directories).display().to_string(),
+        ]);
+    if let Some(stop_height) = args.da.stop_height(args.num_batches) {
+        rollup.args(["--stop-at-rollup-height", &stop_height.to_string()]);
+    }
+    let rollup = rollup
         .current_dir(directories.rollup_root.clone())
         .env("RUST_LOG", "info")
         .spawn()
@@ -133,69 +161,80 @@ async fn run_test() -> Result<(), anyhow::Error> {
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
-    let mut slot_fetcher = SlotFetcher::new(get_rollup_client()?, &directories);
-    slot_fetcher.subscribe_slots(false).await?;
+    let mut slot_fetcher = SlotFetcher::new(
+        get_subscription_client()?,
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
+    slot_fetcher
+        .start_slots(SlotFetchMode::from_env()?, 0, false)
+        .await?;
 
-    let mut checked = 0;
     let client = get_rollup_client()?;
-    let mut latest_batch_num = 0;
-    'outer: loop {
+    let mut resync = Resync::new(directories.snapshots_dir.clone(), mismatch_policy)
+        .with_required_batches(args.da.required_resync_batches());
+    loop {
         let slot = slot_fetcher.next_slot().await?.unwrap();
-        for slot_number in checked..=slot.number {
-            let Ok(snapshot) = load_snapshot_json(slot_number, &directories.snapshots_dir) else {
-                // We might be missing a few slots at the beginning.
-                // If the slot number is less than 10, just ignore the missing snapshot.
-                if slot_number < 10 {
-                    continue;
-                } else if latest_batch_num < NUM_SOAK_BATCHES {
-                    panic!("Missing snapshot for slot {}", slot_number);
-                } else {
-                    // Once we've passed NUM_SOAK_BATCHES, and we find the first missing snapshot, we're done
-                    tracing::info!(
-                        "Missing snapshot found at slot {}. Finished resyncing.",
-                        slot_number
-                    );
-                    break 'outer;
+        let progress = resync
+            .check_up_to(slot.number, |slot_number, include_children| {
+                let client = client.clone();
+                async move {
+                    Ok(client
+                        .get_slot_by_id(&types::IntOrHash::Integer(slot_number), include_children)
+                        .await?
+                        .into_inner())
                 }
-            };
-            let slot_snapshot: Slot = serde_json::from_value(snapshot.clone()).unwrap();
-            latest_batch_num = slot_snapshot.batch_range.end.saturating_sub(1);
-            let include_children = if slot_snapshot.batches.is_empty() {
-                None
-            } else {
-                Some(GetSlotByIdChildren::_1)
-            };
-            let slot = client
-                .get_slot_by_id(&types::IntOrHash::Integer(slot_number), include_children)
-                .await?;
-            compare_against_snapshot(
-                &slot.into_inner(),
-                snapshot,
-                &format!("slot_{}", slot_number),
-                false,
-            )?;
+            })
+            .await?;
+        if progress == ResyncProgress::Finished {
+            break;
         }
-        checked = slot.number;
     }
+    resync.finish()?;
+    let latest_batch_num = resync.latest_batch_num();
 
     tracing::info!(
         "Rollup resync complete. All slots match their snapshots. Found {} batches.",
         latest_batch_num
     );
 
-    let new_throughput_report =
-        run_soak(directories.clone(), rollup, latest_batch_num, false).await?;
-    let previous_throughput_report: ThroughputReport = serde_json::from_str::<ThroughputReport>(
-        &std::fs::read_to_string(directories.output_dir.join("throughput_report.json"))?,
-    )?;
-    let previous_throughput =
-        previous_throughput_report.num_txs as f64 / previous_throughput_report.num_slots as f64;
-    let new_throughput =
-        new_throughput_report.num_txs as f64 / new_throughput_report.num_slots as f64;
-    if new_throughput < (previous_throughput * 0.9) {
-        anyhow::bail!("Throughput is less than 90% of the previous throughput. This is likely due to a bug in the rollup. Old throughput: {:.2} txs/slot, new throughput: {:.2} txs/slot", previous_throughput, new_throughput);
+    let soak_config = SoakConfig {
+        num_workers: args.num_workers,
+        salt: args.salt,
+        num_batches: args.num_batches,
+        da: args.da,
+        ..SoakConfig::default()
+    };
+    let new_throughput_report = run_soak(
+        directories.clone(),
+        rollup,
+        latest_batch_num,
+        args.da.records_soak_snapshots(),
+        soak_config,
+    )
+    .await?;
+
+    let report_path = directories
+        .output_dir
+        .join(args.da.throughput_report_name());
+    // A Celestia devnet has no recorded report until its first run, and each passing run becomes
+    // the baseline of the next one
+    if args.da == DaMode::Celestia {
+        match std::fs::read_to_string(&report_path) {
+            Ok(previous_report) => check_throughput(
+                &serde_json::from_str(&previous_report)?,
+                &new_throughput_report,
+            )?,
+            Err(_) => info!("No previous Celestia throughput report, recording this run's report"),
+        }
+        std::fs::write(&report_path, serde_json::to_string(&new_throughput_report)?)?;
+        return Ok(());
     }
 
+    let previous_throughput_report: ThroughputReport =
+        serde_json::from_str::<ThroughputReport>(&std::fs::read_to_string(report_path)?)?;
+    check_throughput(&previous_throughput_report, &new_throughput_report)?;
+
     // Save throughput report to acceptance test directory
     std::fs::write(
         directories
@@ -206,18 +245,80 @@ async fn run_test() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Fails if `new_throughput_report` regressed too far from `previous_throughput_report`.
+fn check_throughput(
+    previous_throughput_report: &ThroughputReport,
+    new_throughput_report: &ThroughputReport,
+) -> Result<(), anyhow::Error> {
+    // Both throughputs are per-slot averages, so a soak shorter than the recorded one still compares
+    let previous_throughput = previous_throughput_report
+        .txs_per_slot()
+        .ok_or_else(|| anyhow::anyhow!("The recorded throughput report covers no slots"))?;
+    let new_throughput = new_throughput_report.txs_per_slot().ok_or_else(|| {
+        anyhow::anyhow!("The soak ended before any slot was processed. Try a larger --num-batches")
+    })?;
+    if new_throughput < (previous_throughput * 0.9) {
+        anyhow::bail!("Throughput is less than 90% of the previous throughput. This is likely due to a bug in the rollup. Old throughput: {:.2} txs/slot, new throughput: {:.2} txs/slot", previous_throughput, new_throughput);
+    }
+    // Reports recorded before latencies were tracked have no p99 to compare against
+    if let (Some(previous_p99), Some(new_p99)) = (
+        previous_throughput_report.p99_ms,
+        new_throughput_report.p99_ms,
+    ) {
+        if new_p99 as f64 > previous_p99 as f64 * 1.5 {
+            anyhow::bail!("p99 tx latency is more than 150% of the previous p99 latency. This is likely due to a bug in the rollup. Old p99: {} ms, new p99: {} ms", previous_p99, new_p99);
+        }
+    }
+    Ok(())
+}
+
 #[derive(Parser)]
 struct Args {
     #[arg(short, long, default_value = "http://localhost:12346")]
     /// The URL of the rollup node to connect to. Defaults to http://localhost:12346.
     api_url: String,
 
-    #[arg(short, long, default_value = "5")]
-    /// The number of workers to spawn - this controls the number of concurrent transactions. Defaults to 5.
+    #[arg(short, long, default_value_t = DEFAULT_NUM_WORKERS)]
+    /// The number of workers to spawn - this controls the number of concurrent transactions. Defaults to 20.
     num_workers: u32,
 
     #[arg(short, long, default_value = "0")]
     /// The salt to use for RNG. Use this value if you're restarting the generator and want to ensure that the generated
     /// transactions don't overlap with the previous run.
     salt: u32,
+
+    #[arg(long, value_enum, default_value_t = DaMode::Mock)]
+    /// The DA layer to run the rollup against. `celestia` reads the devnet's connection parameters
+    /// from the `ACCEPTANCE_CELESTIA_*` environment variables. Defaults to mock.
+    da: DaMode,
+
+    #[arg(long, default_value_t = NUM_SOAK_BATCHES)]
+    /// The number of new batches to soak the rollup for before shutting it down. Defaults to 1000.
+    num_batches: u64,
+
+    #[arg(long)]
+    /// Instead of running the test, write the session registry state of the node at `api_url` to
+    /// this file as JSON and exit.
+    dump_state: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Stop the resync at the first slot that doesn't match its snapshot instead of checking every slot.
+    fail_fast: bool,
+
+    #[arg(long, conflicts_with = "fail_fast")]
+    /// Keep resyncing past mismatched slots and report all of them once the resync is complete.
+    continue_on_mismatch: bool,
+
+    #[arg(long, value_enum, env = "LOG_FORMAT", default_value_t = LogFormat::Pretty)]
+    /// The format of the log output. `RUST_LOG` filters the logs in either format. Defaults to pretty.
+    log_format: LogFormat,
+}
+
+/// Format of the harness's log output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines.
+    Pretty,
+    /// One JSON object per line, for log aggregation.
+    Json,
 }
@@ -0,0 +1,55 @@
+use sov_api_spec::types::IntOrHash;
+
+/// Number of hex characters in a 32-byte slot, batch or tx hash.
+const HASH_HEX_LEN: usize = 64;
+
+/// Why a user-supplied slot/batch/tx identifier couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("Expected a number or a 0x-prefixed hash, got an empty string")]
+    Empty,
+    #[error("Hash {input:?} has {len} hex characters, expected {HASH_HEX_LEN}")]
+    InvalidHashLength { input: String, len: usize },
+    #[error("Hash {input:?} contains non-hex characters")]
+    InvalidHex { input: String },
+    #[error("{input:?} is neither a non-negative integer nor a 0x-prefixed hash")]
+    InvalidInteger { input: String },
+}
+
+/// Parses a decimal number or a `0x`-prefixed 32-byte hex hash into an [`IntOrHash`].
+pub fn parse_int_or_hash(input: &str) -> Result<IntOrHash, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    if let Some(hex) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        if hex.len() != HASH_HEX_LEN {
+            return Err(ParseError::InvalidHashLength {
+                input: input.to_string(),
+                len: hex.len(),
+            });
+        }
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseError::InvalidHex {
+                input: input.to_string(),
+            });
+        }
+        let hash = format!("0x{}", hex.to_ascii_lowercase())
+            .parse()
+            .map_err(|_| ParseError::InvalidHex {
+                input: input.to_string(),
+            })?;
+        return Ok(IntOrHash::Hash(hash));
+    }
+
+    input
+        .parse::<u64>()
+        .map(IntOrHash::Integer)
+        .map_err(|_| ParseError::InvalidInteger {
+            input: input.to_string(),
+        })
+}
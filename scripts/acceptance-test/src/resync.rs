@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::path::PathBuf;
+
+use sov_api_spec::types::{GetSlotByIdChildren, Slot};
+use tokio::task::JoinSet;
+
+use crate::fetch_and_compare::{
+    compare_against_snapshot, compare_tx_events_against_snapshot, is_finalized, load_snapshot_json,
+    save_slot_snapshot, SnapshotComparison, ValidationError,
+};
+use crate::NUM_SOAK_BATCHES;
+
+/// What the resync does when a live slot diverges from its snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MismatchPolicy {
+    /// Compare every slot up to the current head and then fail, listing all mismatched slots.
+    #[default]
+    CheckAll,
+    /// Stop at the first mismatched slot.
+    FailFast,
+    /// Keep resyncing past mismatched slots and report all of them once the resync finishes.
+    ContinueOnMismatch,
+}
+
+/// Whether the resync has reached the end of the recorded snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResyncProgress {
+    /// All slots up to the requested head matched. Call [`Resync::check_up_to`] again once the
+    /// rollup has produced more slots.
+    InProgress,
+    /// The first missing snapshot past the required batches was found. The resync is complete.
+    Finished,
+}
+
+/// Default number of slots [`Resync::check_up_to`] fetches at once.
+pub const DEFAULT_RESYNC_CONCURRENCY: usize = 8;
+
+/// A slot whose snapshot was loaded, waiting to be fetched and compared.
+struct PendingSlot {
+    number: u64,
+    snapshot: serde_json::Value,
+    /// The last batch number covered by the snapshot.
+    batch_num: u64,
+    /// Which children to fetch the slot with, taken once the fetch is spawned.
+    include_children: Option<GetSlotByIdChildren>,
+}
+
+/// Why the snapshots of a [`Resync::check_up_to`] range stopped before its head.
+enum SnapshotsEnd {
+    /// The first missing snapshot past the required batches: the resync is complete.
+    Finished,
+    /// A snapshot is missing before the required batches were covered.
+    Missing(u64),
+}
+
+/// Compares the slots a resyncing rollup produces against the recorded snapshots.
+pub struct Resync {
+    snapshots_dir: PathBuf,
+    policy: MismatchPolicy,
+    concurrency: usize,
+    checked: u64,
+    latest_batch_num: u64,
+    required_batches: u64,
+    mismatches: Vec<u64>,
+}
+
+impl Resync {
+    pub fn new(snapshots_dir: PathBuf, policy: MismatchPolicy) -> Self {
+        Self {
+            snapshots_dir,
+            policy,
+            concurrency: DEFAULT_RESYNC_CONCURRENCY,
+            checked: 0,
+            latest_batch_num: 0,
+            required_batches: NUM_SOAK_BATCHES,
+            mismatches: Vec::new(),
+        }
+    }
+
+    /// Sets how many batches the snapshots must cover before a missing snapshot finishes the
+    /// resync instead of failing it. Defaults to [`NUM_SOAK_BATCHES`].
+    pub fn with_required_batches(mut self, required_batches: u64) -> Self {
+        self.required_batches = required_batches;
+        self
+    }
+
+    /// Sets how many slots are fetched at once. Defaults to [`DEFAULT_RESYNC_CONCURRENCY`]; `1`
+    /// fetches each slot only once the previous one was compared.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// The last batch number covered by a matching snapshot so far.
+    pub fn latest_batch_num(&self) -> u64 {
+        self.latest_batch_num
+    }
+
+    /// The slots that didn't match their snapshots so far.
+    pub fn mismatches(&self) -> &[u64] {
+        &self.mismatches
+    }
+
+    /// Checks every slot from the last checked slot up to `head`, fetching the live data with `fetch`.
+    ///
+    /// Up to [`Resync::with_concurrency`] slots are fetched at once, but they are compared in
+    /// order, so the outcome is the same as with sequential fetches: a failing resync reports the
+    /// lowest-numbered mismatch, and a missing snapshot only ends the resync once every slot before
+    /// it was compared.
+    pub async fn check_up_to<F, Fut>(
+        &mut self,
+        head: u64,
+        mut fetch: F,
+    ) -> Result<ResyncProgress, anyhow::Error>
+    where
+        F: FnMut(u64, Option<GetSlotByIdChildren>) -> Fut,
+        Fut: Future<Output = Result<Slot, anyhow::Error>> + Send + 'static,
+    {
+        let (pending, end) = self.load_snapshots_up_to(head)?;
+
+        let mut pending = pending.into_iter();
+        let mut in_flight = JoinSet::new();
+        let mut fetched = BTreeMap::new();
+        let mut spawn_next = |in_flight: &mut JoinSet<_>| {
+            let mut pending_slot = pending.next()?;
+            let number = pending_slot.number;
+            let slot = fetch(number, pending_slot.include_children.take());
+            in_flight.spawn(async move { (number, slot.await) });
+            Some(pending_slot)
+        };
+
+        let mut compare_queue = VecDeque::new();
+        for _ in 0..self.concurrency {
+            match spawn_next(&mut in_flight) {
+                Some(pending_slot) => compare_queue.push_back(pending_slot),
+                None => break,
+            }
+        }
+
+        while let Some(pending_slot) = compare_queue.pop_front() {
+            let slot_number = pending_slot.number;
+            let slot = loop {
+                if let Some(slot) = fetched.remove(&slot_number) {
+                    break slot;
+                }
+                let (number, slot) = in_flight
+                    .join_next()
+                    .await
+                    .expect("A fetch is in flight for every queued slot")?;
+                fetched.insert(number, slot);
+            }?;
+
+            self.latest_batch_num = pending_slot.batch_num;
+            self.compare_slot(slot_number, &slot, pending_slot.snapshot)?;
+
+            if let Some(pending_slot) = spawn_next(&mut in_flight) {
+                compare_queue.push_back(pending_slot);
+            }
+        }
+
+        match end {
+            Some(SnapshotsEnd::Finished) => return Ok(ResyncProgress::Finished),
+            Some(SnapshotsEnd::Missing(slot_number)) => {
+                panic!("Missing snapshot for slot {}", slot_number)
+            }
+            None => {}
+        }
+        self.checked = head;
+
+        if self.policy == MismatchPolicy::CheckAll && !self.mismatches.is_empty() {
+            anyhow::bail!(
+                "{} slots do not match their snapshots: {:?}",
+                self.mismatches.len(),
+                self.mismatches
+            );
+        }
+        Ok(ResyncProgress::InProgress)
+    }
+
+    /// Loads the snapshots of the slots from the last checked slot up to `head`, stopping at the
+    /// first missing snapshot past the first slots.
+    fn load_snapshots_up_to(
+        &self,
+        head: u64,
+    ) -> Result<(Vec<PendingSlot>, Option<SnapshotsEnd>), anyhow::Error> {
+        let mut pending = Vec::new();
+        let mut latest_batch_num = self.latest_batch_num;
+        for slot_number in self.checked..=head {
+            let Ok(snapshot) = load_snapshot_json(slot_number, &self.snapshots_dir) else {
+                // We might be missing a few slots at the beginning.
+                // If the slot number is less than 10, just ignore the missing snapshot.
+                if slot_number < 10 {
+                    continue;
+                } else if latest_batch_num < self.required_batches {
+                    return Ok((pending, Some(SnapshotsEnd::Missing(slot_number))));
+                } else {
+                    // Once we've passed the required batches, and we find the first missing snapshot, we're done
+                    tracing::info!(
+                        "Missing snapshot found at slot {}. Finished resyncing.",
+                        slot_number
+                    );
+                    return Ok((pending, Some(SnapshotsEnd::Finished)));
+                }
+            };
+            let slot_snapshot: Slot = serde_json::from_value(snapshot.clone())?;
+            latest_batch_num = slot_snapshot.batch_range.end.saturating_sub(1);
+            let include_children = if slot_snapshot.batches.is_empty() {
+                None
+            } else {
+                Some(GetSlotByIdChildren::_1)
+            };
+            pending.push(PendingSlot {
+                number: slot_number,
+                snapshot,
+                batch_num: latest_batch_num,
+                include_children,
+            });
+        }
+        Ok((pending, None))
+    }
+
+    /// Compares the live `slot` against its `snapshot`, recording or failing on a mismatch
+    /// according to the policy.
+    fn compare_slot(
+        &mut self,
+        slot_number: u64,
+        slot: &Slot,
+        snapshot: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        // Point out the first diverging event, which the whole-slot comparison below doesn't
+        if is_finalized(&snapshot) {
+            let slot_json = serde_json::to_value(slot)?;
+            if let Err(mismatch) = compare_tx_events_against_snapshot(&slot_json, &snapshot) {
+                tracing::error!("slot_{}: {}", slot_number, mismatch);
+            }
+        }
+        match compare_against_snapshot(slot, snapshot, &format!("slot_{}", slot_number), false) {
+            Ok(SnapshotComparison::Matched) => {}
+            Ok(SnapshotComparison::ChangedBeforeFinality) => {
+                save_slot_snapshot(slot, &self.snapshots_dir)?;
+            }
+            Err(ValidationError::InvalidSnapshot) if self.policy == MismatchPolicy::FailFast => {
+                anyhow::bail!(
+                    "Slot {} does not match its snapshot. Stopping early (fail-fast).",
+                    slot_number
+                );
+            }
+            Err(ValidationError::InvalidSnapshot) => {
+                // The range boundary is checked twice, so don't record it again.
+                if self.mismatches.last() != Some(&slot_number) {
+                    self.mismatches.push(slot_number);
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+        Ok(())
+    }
+
+    /// Fails with a summary of every mismatched slot, if there were any.
+    ///
+    /// Only [`MismatchPolicy::ContinueOnMismatch`] gets this far with mismatches recorded.
+    pub fn finish(&self) -> Result<(), anyhow::Error> {
+        if !self.mismatches.is_empty() {
+            anyhow::bail!(
+                "Resync checked slots up to {} and found {} slots that do not match their snapshots: {:?}",
+                self.checked,
+                self.mismatches.len(),
+                self.mismatches
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use std::future::Future;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The REST prefix of the session registry module.
+pub const SESSION_REGISTRY_PREFIX: &str = "modules/session-registry";
+
+/// A portable snapshot of the session registry's state, assembled from the node's REST API.
+///
+/// Every field is `None` when the corresponding endpoint is not served by the node, so a dump
+/// taken against an older deployment still records everything that *is* available.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryStateDump {
+    pub owner: Option<Value>,
+    pub manager: Option<Value>,
+    pub enforcement_enabled: Option<Value>,
+    pub expiry_offset: Option<Value>,
+    pub sessions: Option<Value>,
+    pub signers: Option<Value>,
+}
+
+/// Assembles a [`RegistryStateDump`] from `fetch`, which is called with a path relative to the
+/// node's base URL and returns `Ok(None)` when the endpoint is not found.
+///
+/// Single state values are read from the generated `state/...` endpoints and unwrapped from their
+/// `{"value": ...}` envelope. Sessions and signers are read from the module's `GET /sessions`
+/// and `GET /signers` listings and stored as returned.
+pub async fn assemble_state_dump<F, Fut>(fetch: F) -> anyhow::Result<RegistryStateDump>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<Value>>>,
+{
+    let state_value = |item: &str| {
+        let response = fetch(format!("{}/state/{}", SESSION_REGISTRY_PREFIX, item));
+        async move {
+            Ok::<_, anyhow::Error>(
+                response
+                    .await?
+                    .and_then(|mut response| response.get_mut("value").map(Value::take)),
+            )
+        }
+    };
+
+    Ok(RegistryStateDump {
+        owner: state_value("owner").await?,
+        manager: state_value("manager").await?,
+        enforcement_enabled: state_value("enforcement-enabled").await?,
+        expiry_offset: state_value("expiry-offset").await?,
+        sessions: fetch(format!("{}/sessions", SESSION_REGISTRY_PREFIX)).await?,
+        signers: fetch(format!("{}/signers", SESSION_REGISTRY_PREFIX)).await?,
+    })
+}
+
+/// Fetches the session registry state from the node at `api_url` and writes it to `path` as JSON.
+pub async fn dump_state(api_url: &str, path: &Path) -> anyhow::Result<RegistryStateDump> {
+    let client = reqwest::Client::new();
+    let dump = assemble_state_dump(|url| {
+        let client = client.clone();
+        let url = format!("{}/{}", api_url, url);
+        async move {
+            let response = client.get(&url).send().await?;
+            if response.status().is_success() {
+                Ok(Some(response.json::<Value>().await?))
+            } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+                tracing::warn!("{} not found. Leaving it out of the state dump.", url);
+                Ok(None)
+            } else {
+                Err(anyhow::anyhow!(
+                    "Failed to get {}: {}",
+                    url,
+                    response.status()
+                ))
+            }
+        }
+    })
+    .await?;
+
+    std::fs::write(path, serde_json::to_string_pretty(&dump)?)?;
+    tracing::info!("Wrote session registry state dump to {}", path.display());
+    Ok(dump)
+}
@@ -0,0 +1,78 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::stream::Stream;
+
+/// Counts the subscriptions a harness run has open, so it can check they were all closed.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionTracker {
+    open: Arc<AtomicUsize>,
+}
+
+impl SubscriptionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps `stream` so that it counts as open until it is closed or dropped.
+    pub fn track<S: Stream>(&self, stream: S) -> Subscription<S> {
+        self.open.fetch_add(1, Ordering::SeqCst);
+        Subscription {
+            inner: Some(Box::pin(stream)),
+            open: self.open.clone(),
+        }
+    }
+
+    /// Number of tracked subscriptions that are still open.
+    pub fn open(&self) -> usize {
+        self.open.load(Ordering::SeqCst)
+    }
+
+    /// Fails if any tracked subscription is still open.
+    pub fn ensure_all_closed(&self) -> Result<(), anyhow::Error> {
+        let open = self.open();
+        anyhow::ensure!(open == 0, "{} subscriptions are still open", open);
+        Ok(())
+    }
+}
+
+/// A subscription stream registered with a [`SubscriptionTracker`].
+///
+/// Dropping the underlying stream closes the connection to the node, which happens on
+/// [`Subscription::close`] or when the handle is dropped. A closed subscription yields no more
+/// items.
+pub struct Subscription<S> {
+    inner: Option<Pin<Box<S>>>,
+    open: Arc<AtomicUsize>,
+}
+
+impl<S> Subscription<S> {
+    pub fn close(&mut self) {
+        if self.inner.take().is_some() {
+            self.open.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<S> Drop for Subscription<S> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<S: Stream> Stream for Subscription<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut().inner.as_mut() {
+            Some(inner) => inner.as_mut().poll_next(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
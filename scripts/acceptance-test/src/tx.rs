@@ -0,0 +1,55 @@
+use sov_bank::Amount;
+use sov_modules_api::Spec as SpecT;
+use stf_starter::sov_modules_api::capabilities::UniquenessData;
+use stf_starter::sov_modules_api::macros::config_value;
+use stf_starter::sov_modules_api::transaction::{
+    PriorityFeeBips, Transaction, UnsignedTransaction,
+};
+use stf_starter::sov_modules_api::{CryptoSpec, RawTx};
+use stf_starter::RuntimeCall;
+
+use crate::{Runtime, Spec};
+
+/// The fee and gas parameters of a transaction signed by the harness.
+#[derive(Debug, Clone)]
+pub struct TxFees {
+    pub max_priority_fee_bips: PriorityFeeBips,
+    pub max_fee: Amount,
+    /// Most gas the transaction may use, or `None` to be bounded by `max_fee` alone.
+    pub gas_limit: Option<<Spec as SpecT>::Gas>,
+}
+
+impl Default for TxFees {
+    fn default() -> Self {
+        Self {
+            max_priority_fee_bips: PriorityFeeBips(0),
+            max_fee: Amount::new(100_000_000),
+            gas_limit: None,
+        }
+    }
+}
+
+/// Signs `msg` with the harness key and `fees`, and encodes it for submission to the sequencer.
+pub fn encode_and_sign_tx(msg: RuntimeCall<Spec>, fees: TxFees) -> Result<RawTx, anyhow::Error> {
+    let utx = UnsignedTransaction::<Runtime, Spec>::new(
+        msg,
+        config_value!("CHAIN_ID"),
+        fees.max_priority_fee_bips,
+        fees.max_fee,
+        UniquenessData::Generation(0),
+        fees.gas_limit,
+    );
+    let priv_key: <<Spec as SpecT>::CryptoSpec as CryptoSpec>::PrivateKey = serde_json::from_str(
+        "\"0d87c12ea7c12024b3f70a26d735874608f17c8bce2b48e6fe87389310191264\"",
+    )
+    .unwrap();
+
+    let tx: Transaction<Runtime, Spec> = Transaction::new_signed_tx(
+        &priv_key,
+        &<Runtime as sov_modules_stf_blueprint::Runtime<Spec>>::CHAIN_HASH,
+        utx,
+    );
+    let tx = RawTx::new(borsh::to_vec(&tx).unwrap());
+
+    Ok(tx)
+}
@@ -0,0 +1,42 @@
+use acceptance_test::fetch_and_compare::{check_batch_hashes, BatchHashMismatch};
+
+/// Every fetch method reporting the lookup hash passes.
+#[test]
+fn test_batch_hash_stable_across_fetch_methods() {
+    let hash = format!("0x{:064x}", 7);
+
+    check_batch_hashes(
+        3,
+        &hash,
+        &[
+            ("by hash", &hash),
+            ("by number with children", &hash),
+            ("by hash with children", &hash),
+        ],
+    )
+    .unwrap();
+}
+
+/// A single fetch method returning a different hash is reported with the method that diverged.
+#[test]
+fn test_batch_hash_mismatch_names_the_fetch_method() {
+    let hash = format!("0x{:064x}", 7);
+    let other = format!("0x{:064x}", 8);
+
+    let err = check_batch_hashes(
+        3,
+        &hash,
+        &[("by hash", &hash), ("by hash with children", &other)],
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        BatchHashMismatch {
+            number: 3,
+            method: "by hash with children",
+            expected: format!("{:?}", hash),
+            actual: format!("{:?}", other),
+        }
+    );
+}
@@ -0,0 +1,72 @@
+use acceptance_test::{
+    RollupClientConfig, CONNECT_TIMEOUT_ENV, READ_TIMEOUT_ENV, REQUEST_TIMEOUT_ENV,
+};
+use std::time::Duration;
+
+#[test]
+fn test_subscription_client_outlasts_query_timeouts() {
+    let query = RollupClientConfig::query();
+    let subscription = RollupClientConfig::subscription();
+
+    assert!(subscription.read_timeout > query.read_timeout);
+    assert_eq!(
+        subscription.timeout, None,
+        "A total timeout would cut off a long-lived stream"
+    );
+}
+
+#[test]
+fn test_client_configs_build() {
+    RollupClientConfig::query()
+        .build("http://localhost:12348")
+        .unwrap();
+    RollupClientConfig::subscription()
+        .build("http://localhost:12348")
+        .unwrap();
+}
+
+#[test]
+fn test_client_config_overrides() {
+    let lookup = |name: &str| match name {
+        REQUEST_TIMEOUT_ENV => Some("30".to_string()),
+        READ_TIMEOUT_ENV => Some("5".to_string()),
+        _ => None,
+    };
+
+    let query = RollupClientConfig::query().with_overrides(lookup).unwrap();
+    assert_eq!(query.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(query.read_timeout, Duration::from_secs(5));
+    assert_eq!(
+        query.connect_timeout,
+        RollupClientConfig::query().connect_timeout,
+        "Unset variables should keep their default"
+    );
+
+    let subscription = RollupClientConfig::subscription()
+        .with_overrides(lookup)
+        .unwrap();
+    assert_eq!(
+        subscription.timeout, None,
+        "The request timeout shouldn't apply to subscription streams"
+    );
+    assert_eq!(subscription.read_timeout, Duration::from_secs(5));
+}
+
+#[test]
+fn test_client_config_overrides_reject_invalid_values() {
+    for value in ["0", "-1", "soon"] {
+        let result = RollupClientConfig::query()
+            .with_overrides(|name| (name == CONNECT_TIMEOUT_ENV).then(|| value.to_string()));
+        assert!(result.is_err(), "{value:?} should be rejected");
+    }
+}
+
+#[test]
+fn test_client_config_defaults_without_overrides() {
+    assert_eq!(
+        RollupClientConfig::query()
+            .with_overrides(|_| None)
+            .unwrap(),
+        RollupClientConfig::query()
+    );
+}
@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+
+// Fixtures shared by the acceptance-test harness tests.
+
+use acceptance_test::Directories;
+use serde_json::json;
+use sov_api_spec::types::Slot;
+
+/// Directories rooted in a fresh temporary directory, so tests never touch real harness data.
+pub fn test_directories() -> Directories {
+    let root = std::env::temp_dir().join(format!(
+        "acceptance-test-{}-{}",
+        std::process::id(),
+        rand::random::<u64>()
+    ));
+    let output_dir = root.join("acceptance-test-data");
+    let directories = Directories {
+        rollup_root: root.clone(),
+        acceptance_test_dir: root.clone(),
+        rollup_data_path: output_dir.join("rollup-starter-data"),
+        snapshots_dir: output_dir.join("snapshots"),
+        output_dir,
+    };
+    std::fs::create_dir_all(&directories.snapshots_dir).unwrap();
+    std::fs::create_dir_all(&directories.rollup_data_path).unwrap();
+    directories
+}
+
+/// Builds a minimal finalized slot with no batches.
+pub fn slot(number: u64) -> Slot {
+    slot_with(number, "finalized", &format!("0x{:064x}", number))
+}
+
+/// Builds a minimal slot with no batches and the given finality status and state root.
+pub fn slot_with(number: u64, finality_status: &str, state_root: &str) -> Slot {
+    serde_json::from_value(json!({
+        "type": "slot",
+        "number": number,
+        "hash": format!("0x{:064x}", number + 1),
+        "state_root": state_root,
+        "batch_range": { "start": 0, "end": 0 },
+        "batches": [],
+        "finality_status": finality_status,
+        "timestamp": 1_700_000_000u64 + number,
+    }))
+    .expect("slot fixture should deserialize")
+}
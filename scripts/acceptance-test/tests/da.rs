@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use acceptance_test::da::{
+    CelestiaParams, DaMode, CELESTIA_GENESIS_HEIGHT_ENV, CELESTIA_GRPC_URL_ENV,
+    CELESTIA_RPC_URL_ENV, CELESTIA_SIGNER_KEY_ENV,
+};
+use acceptance_test::NUM_SOAK_BATCHES;
+
+fn params_from(vars: &[(&str, &str)]) -> Result<CelestiaParams, anyhow::Error> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    CelestiaParams::from_lookup(|name| vars.get(name).cloned())
+}
+
+#[test]
+fn test_celestia_params_default_to_a_local_devnet() {
+    let params = params_from(&[(CELESTIA_SIGNER_KEY_ENV, "aec3")]).unwrap();
+
+    assert_eq!(params.rpc_url, "ws://127.0.0.1:26658");
+    assert_eq!(params.grpc_url, "http://127.0.0.1:9091");
+    assert_eq!(params.signer_private_key, "aec3");
+    assert_eq!(params.genesis_height, 3);
+}
+
+#[test]
+fn test_celestia_params_read_every_variable() {
+    let params = params_from(&[
+        (CELESTIA_SIGNER_KEY_ENV, "aec3"),
+        (CELESTIA_RPC_URL_ENV, "ws://celestia:26658"),
+        (CELESTIA_GRPC_URL_ENV, "http://celestia:9090"),
+        (CELESTIA_GENESIS_HEIGHT_ENV, "1200"),
+    ])
+    .unwrap();
+
+    assert_eq!(params.rpc_url, "ws://celestia:26658");
+    assert_eq!(params.grpc_url, "http://celestia:9090");
+    assert_eq!(params.genesis_height, 1200);
+}
+
+#[test]
+fn test_celestia_params_require_a_signer_key() {
+    let err = params_from(&[]).unwrap_err();
+    assert!(err.to_string().contains(CELESTIA_SIGNER_KEY_ENV));
+
+    let err = params_from(&[
+        (CELESTIA_SIGNER_KEY_ENV, "aec3"),
+        (CELESTIA_GENESIS_HEIGHT_ENV, "soon"),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains(CELESTIA_GENESIS_HEIGHT_ENV));
+}
+
+#[test]
+fn test_only_mock_da_replays_the_recorded_history() {
+    assert!(DaMode::Mock.uses_recorded_mock_data());
+    assert!(!DaMode::Mock.records_soak_snapshots());
+    assert_eq!(DaMode::Mock.stop_height(10), Some(NUM_SOAK_BATCHES + 10));
+    assert_eq!(DaMode::Mock.required_resync_batches(), NUM_SOAK_BATCHES);
+
+    assert!(!DaMode::Celestia.uses_recorded_mock_data());
+    assert!(DaMode::Celestia.records_soak_snapshots());
+    assert_eq!(DaMode::Celestia.stop_height(10), None);
+    assert_eq!(DaMode::Celestia.required_resync_batches(), 0);
+    assert!(DaMode::Celestia.default_stall_timeout() > DaMode::Mock.default_stall_timeout());
+}
@@ -0,0 +1,54 @@
+use acceptance_test::event_order::{check_event_order, EventOrderError};
+
+#[test]
+fn test_matching_event_order() {
+    let events = ["create_token", "mint", "transfer"];
+    assert_eq!(check_event_order(&events, &events), Ok(()));
+    assert_eq!(check_event_order::<&str>(&[], &[]), Ok(()));
+}
+
+#[test]
+fn test_reordered_events() {
+    let ledger = ["create_token", "mint", "transfer"];
+    let subscribed = ["create_token", "transfer", "mint"];
+
+    assert_eq!(
+        check_event_order(&subscribed, &ledger),
+        Err(EventOrderError::Reordered {
+            index: 1,
+            subscribed: "\"transfer\"".to_string(),
+            ledger: "\"mint\"".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_unknown_event() {
+    let ledger = ["create_token", "mint"];
+    let subscribed = ["create_token", "burn"];
+
+    assert!(matches!(
+        check_event_order(&subscribed, &ledger),
+        Err(EventOrderError::Mismatch { index: 1, .. })
+    ));
+}
+
+#[test]
+fn test_missing_and_extra_events() {
+    let ledger = ["create_token", "mint", "transfer"];
+
+    assert_eq!(
+        check_event_order(&ledger[..2], &ledger),
+        Err(EventOrderError::LengthMismatch {
+            subscribed: 2,
+            ledger: 3,
+        })
+    );
+    assert_eq!(
+        check_event_order(&ledger, &ledger[..2]),
+        Err(EventOrderError::LengthMismatch {
+            subscribed: 3,
+            ledger: 2,
+        })
+    );
+}
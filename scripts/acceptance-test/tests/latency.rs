@@ -0,0 +1,46 @@
+use std::time::{Duration, Instant};
+
+use acceptance_test::latency::{LatencyHistogram, TxLatencyTracker};
+
+#[test]
+fn test_percentiles_use_nearest_rank() {
+    let mut histogram = LatencyHistogram::new();
+    for ms in 1..=100 {
+        histogram.record(Duration::from_millis(ms));
+    }
+
+    assert_eq!(histogram.len(), 100);
+    assert_eq!(histogram.percentile_ms(50.0), Some(50));
+    assert_eq!(histogram.percentile_ms(95.0), Some(95));
+    assert_eq!(histogram.percentile_ms(99.0), Some(99));
+    assert_eq!(histogram.percentile_ms(0.0), Some(1));
+    assert_eq!(histogram.percentile_ms(100.0), Some(100));
+}
+
+#[test]
+fn test_empty_histogram_has_no_percentiles() {
+    assert_eq!(LatencyHistogram::new().percentile_ms(50.0), None);
+}
+
+#[test]
+fn test_tracker_confirms_txs_below_the_batch_end() {
+    let start = Instant::now();
+    let mut tracker = TxLatencyTracker::new();
+    tracker.accepted(0, start);
+    tracker.accepted(1, start + Duration::from_millis(10));
+    tracker.accepted(2, start + Duration::from_millis(20));
+
+    tracker.confirmed_before(2, start + Duration::from_millis(100));
+    assert_eq!(tracker.histogram().len(), 2);
+    assert_eq!(tracker.num_pending(), 1);
+    assert_eq!(tracker.histogram().percentile_ms(50.0), Some(90));
+    assert_eq!(tracker.histogram().percentile_ms(100.0), Some(100));
+
+    // Confirming the same range again doesn't record anything new
+    tracker.confirmed_before(2, start + Duration::from_millis(200));
+    assert_eq!(tracker.histogram().len(), 2);
+
+    tracker.confirmed_before(3, start + Duration::from_millis(50));
+    assert_eq!(tracker.histogram().len(), 3);
+    assert_eq!(tracker.num_pending(), 0);
+}
@@ -0,0 +1,62 @@
+use acceptance_test::parse::{parse_int_or_hash, ParseError};
+use sov_api_spec::types::IntOrHash;
+
+const HASH: &str = "0x9c081539d40ef7b02d359c5d694e006f0c1130097466cd22d062e07065c6987a";
+
+#[test]
+fn test_parse_decimal() {
+    assert!(matches!(parse_int_or_hash("0"), Ok(IntOrHash::Integer(0))));
+    assert!(matches!(
+        parse_int_or_hash(" 1234 "),
+        Ok(IntOrHash::Integer(1234))
+    ));
+}
+
+#[test]
+fn test_parse_hash() {
+    match parse_int_or_hash(HASH) {
+        Ok(IntOrHash::Hash(hash)) => assert_eq!(serde_json::to_value(hash).unwrap(), HASH),
+        other => panic!("expected a hash, got {:?}", other),
+    }
+
+    // Uppercase input is normalized
+    match parse_int_or_hash(&HASH.to_uppercase()) {
+        Ok(IntOrHash::Hash(hash)) => assert_eq!(serde_json::to_value(hash).unwrap(), HASH),
+        other => panic!("expected a hash, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_malformed() {
+    assert_eq!(parse_int_or_hash("").unwrap_err(), ParseError::Empty);
+    assert_eq!(parse_int_or_hash("   ").unwrap_err(), ParseError::Empty);
+
+    assert_eq!(
+        parse_int_or_hash("0x1234").unwrap_err(),
+        ParseError::InvalidHashLength {
+            input: "0x1234".to_string(),
+            len: 4,
+        }
+    );
+    assert!(matches!(
+        parse_int_or_hash(&format!("{}ab", HASH)),
+        Err(ParseError::InvalidHashLength { len: 66, .. })
+    ));
+
+    let not_hex = format!("0x{}", "g".repeat(64));
+    assert_eq!(
+        parse_int_or_hash(&not_hex).unwrap_err(),
+        ParseError::InvalidHex { input: not_hex }
+    );
+
+    for input in ["-1", "12abc", "1.5", "18446744073709551616"] {
+        assert_eq!(
+            parse_int_or_hash(input).unwrap_err(),
+            ParseError::InvalidInteger {
+                input: input.to_string()
+            },
+            "{:?} should be rejected",
+            input
+        );
+    }
+}
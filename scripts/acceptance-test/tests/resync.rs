@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use acceptance_test::fetch_and_compare::save_slot_snapshot;
+use acceptance_test::resync::{MismatchPolicy, Resync, ResyncProgress, DEFAULT_RESYNC_CONCURRENCY};
+
+mod common;
+use common::{slot, slot_with, test_directories};
+
+const HEAD: u64 = 12;
+const MISMATCHED_SLOT: u64 = 3;
+
+/// Records snapshots for slots `0..=head`, with the snapshots of `mismatched` diverging from the
+/// live data.
+fn record_snapshots(head: u64, mismatched: &[u64]) -> std::path::PathBuf {
+    let directories = test_directories();
+    for number in 0..=head {
+        let snapshot = if mismatched.contains(&number) {
+            slot_with(number, "finalized", &format!("0x{:064x}", u64::MAX))
+        } else {
+            slot(number)
+        };
+        save_slot_snapshot(&snapshot, &directories.snapshots_dir).unwrap();
+    }
+    directories.snapshots_dir
+}
+
+/// Runs a resync up to [`HEAD`] with [`MISMATCHED_SLOT`] diverging from the live data, fetching
+/// `concurrency` slots at once. Returns the result and the fetched slots.
+async fn resync_with_early_mismatch(
+    policy: MismatchPolicy,
+    concurrency: usize,
+) -> (anyhow::Result<()>, Vec<u64>) {
+    let snapshots_dir = record_snapshots(HEAD, &[MISMATCHED_SLOT]);
+
+    let fetched = Arc::new(Mutex::new(Vec::new()));
+    let mut resync = Resync::new(snapshots_dir, policy).with_concurrency(concurrency);
+    let result = resync
+        .check_up_to(HEAD, |slot_number, _| {
+            fetched.lock().unwrap().push(slot_number);
+            async move { Ok(slot(slot_number)) }
+        })
+        .await
+        .map(|_| ());
+
+    let fetched = fetched.lock().unwrap().clone();
+    (result, fetched)
+}
+
+/// Under fail-fast, a sequential resync stops fetching at the first mismatched slot.
+#[tokio::test]
+async fn test_fail_fast_stops_at_first_mismatch() {
+    let (result, fetched) = resync_with_early_mismatch(MismatchPolicy::FailFast, 1).await;
+
+    let err = result.expect_err("resync should fail on the mismatched slot");
+    assert!(err.to_string().contains(&MISMATCHED_SLOT.to_string()));
+    assert_eq!(fetched, (0..=MISMATCHED_SLOT).collect::<Vec<_>>());
+}
+
+/// By default, every slot is still checked before the resync reports the mismatch.
+#[tokio::test]
+async fn test_check_all_checks_every_slot_before_failing() {
+    let (result, fetched) =
+        resync_with_early_mismatch(MismatchPolicy::CheckAll, DEFAULT_RESYNC_CONCURRENCY).await;
+
+    let err = result.expect_err("resync should report the mismatched slot");
+    assert!(err.to_string().contains(&format!("[{}]", MISMATCHED_SLOT)));
+    assert_eq!(fetched, (0..=HEAD).collect::<Vec<_>>());
+}
+
+/// With `--continue-on-mismatch`, the resync keeps going across heads and reports every
+/// mismatched slot once at the end.
+#[tokio::test]
+async fn test_continue_on_mismatch_collects_all_mismatches() {
+    let mismatched = [2, 7, 8, 12];
+    let snapshots_dir = record_snapshots(20, &mismatched);
+    let mut resync = Resync::new(snapshots_dir, MismatchPolicy::ContinueOnMismatch);
+
+    // Slot 8 is the boundary between the two heads, so it's compared twice.
+    for head in [8, 20] {
+        let progress = resync
+            .check_up_to(head, |slot_number, _| async move { Ok(slot(slot_number)) })
+            .await
+            .expect("mismatches should not stop the resync");
+        assert_eq!(progress, ResyncProgress::InProgress);
+    }
+
+    assert_eq!(resync.mismatches(), &mismatched);
+    let err = resync
+        .finish()
+        .expect_err("finish should report the mismatches");
+    assert!(err.to_string().contains(&format!("{:?}", mismatched)));
+}
+
+/// Without required batches, the first missing snapshot past the first slots finishes the resync.
+#[tokio::test]
+async fn test_resync_without_required_batches_finishes_at_missing_snapshot() {
+    let snapshots_dir = record_snapshots(HEAD, &[]);
+    let mut resync = Resync::new(snapshots_dir, MismatchPolicy::FailFast).with_required_batches(0);
+
+    let progress = resync
+        .check_up_to(
+            HEAD + 5,
+            |slot_number, _| async move { Ok(slot(slot_number)) },
+        )
+        .await
+        .expect("matching snapshots should not fail the resync");
+    assert_eq!(progress, ResyncProgress::Finished);
+    resync.finish().unwrap();
+}
+
+/// With concurrent fetches, a resync still fails on the lowest mismatched slot, even when a later
+/// mismatched slot is fetched first.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_resync_reports_lowest_mismatch() {
+    let snapshots_dir = record_snapshots(HEAD, &[MISMATCHED_SLOT, MISMATCHED_SLOT + 2]);
+
+    for policy in [MismatchPolicy::FailFast, MismatchPolicy::CheckAll] {
+        let mut resync = Resync::new(snapshots_dir.clone(), policy).with_concurrency(4);
+        let err = resync
+            .check_up_to(HEAD, |slot_number, _| async move {
+                // Let the later slots overtake the first mismatched one
+                if slot_number == MISMATCHED_SLOT {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                Ok(slot(slot_number))
+            })
+            .await
+            .expect_err("resync should fail on the mismatched slots");
+
+        match policy {
+            MismatchPolicy::FailFast => assert!(
+                err.to_string()
+                    .contains(&format!("Slot {} does not", MISMATCHED_SLOT)),
+                "{err}"
+            ),
+            _ => assert_eq!(resync.mismatches(), &[MISMATCHED_SLOT, MISMATCHED_SLOT + 2]),
+        }
+    }
+}
+
+/// A concurrent resync only finishes at a missing snapshot once every slot before it matched.
+#[tokio::test]
+async fn test_concurrent_resync_compares_slots_before_missing_snapshot() {
+    let snapshots_dir = record_snapshots(HEAD, &[HEAD]);
+    let mut resync = Resync::new(snapshots_dir, MismatchPolicy::FailFast).with_required_batches(0);
+
+    let err = resync
+        .check_up_to(
+            HEAD + 5,
+            |slot_number, _| async move { Ok(slot(slot_number)) },
+        )
+        .await
+        .expect_err("the mismatch before the missing snapshot should fail the resync");
+    assert!(err.to_string().contains(&HEAD.to_string()));
+}
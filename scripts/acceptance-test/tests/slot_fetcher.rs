@@ -0,0 +1,284 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use acceptance_test::fetch_and_compare::{
+    poll_slot_stream, retry_while_missing, PollConfig, RetryConfig, SlotFetcher, SlotStalled,
+    DEFAULT_MAX_RESUBSCRIBES,
+};
+use sov_api_spec::types::Slot;
+use tokio_stream::StreamExt;
+
+mod common;
+use common::{slot, test_directories};
+
+/// The polling backend retries a slot until it becomes available and then moves on to the next
+/// number, yielding slots in order.
+#[tokio::test]
+async fn test_poll_slot_stream_waits_for_missing_slots() {
+    // Slot 6 is "not produced yet" for the first two requests.
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let misses = Arc::new(Mutex::new(2));
+
+    let stream = poll_slot_stream(
+        5,
+        PollConfig {
+            interval: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        },
+        {
+            let calls = calls.clone();
+            move |slot_number| {
+                let calls = calls.clone();
+                let misses = misses.clone();
+                async move {
+                    calls.lock().unwrap().push(slot_number);
+                    let mut misses = misses.lock().unwrap();
+                    if slot_number == 6 && *misses > 0 {
+                        *misses -= 1;
+                        return Ok(None);
+                    }
+                    Ok(Some(slot(slot_number)))
+                }
+            }
+        },
+    );
+
+    let directories = test_directories();
+    let mut slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new("http://localhost:1"),
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
+    slot_fetcher.set_slot_stream(stream);
+
+    for expected in 5..8 {
+        let next = slot_fetcher.next_slot().await.unwrap().unwrap();
+        assert_eq!(next.number, expected);
+    }
+
+    assert_eq!(*calls.lock().unwrap(), vec![5, 6, 6, 6, 7]);
+}
+
+/// Errors other than "slot not available yet" are surfaced to the caller.
+#[tokio::test]
+async fn test_poll_slot_stream_surfaces_errors() {
+    let stream = poll_slot_stream(0, PollConfig::default(), |_| async {
+        Err(anyhow::anyhow!("node unavailable"))
+    });
+    let mut stream = Box::pin(stream);
+
+    assert!(stream.next().await.unwrap().is_err());
+}
+
+/// A slot stream that goes silent trips the watchdog, while slots arriving in time reset it.
+#[tokio::test]
+async fn test_stall_watchdog_fires_on_silent_stream() {
+    let stream = futures::stream::iter(vec![Ok::<_, anyhow::Error>(slot(1)), Ok(slot(2))])
+        .chain(futures::stream::pending());
+
+    let directories = test_directories();
+    let mut slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new("http://localhost:1"),
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
+    slot_fetcher.set_slot_stream(stream);
+
+    let timeout = Duration::from_millis(50);
+    for expected in 1..=2 {
+        let next = slot_fetcher
+            .next_slot_within(timeout)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.number, expected);
+    }
+
+    let err = slot_fetcher.next_slot_within(timeout).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<SlotStalled>(),
+        Some(&SlotStalled { timeout })
+    );
+    assert!(err.to_string().contains("Rollup appears stalled"));
+}
+
+/// A slot stream for the resubscription tests: `slots`, followed by an error if `fail` is set.
+fn dropping_stream(
+    slots: std::ops::RangeInclusive<u64>,
+    fail: bool,
+) -> futures::stream::Iter<std::vec::IntoIter<Result<sov_api_spec::types::Slot, anyhow::Error>>> {
+    let mut items: Vec<_> = slots.map(|number| Ok(slot(number))).collect();
+    if fail {
+        items.push(Err(anyhow::anyhow!("websocket closed")));
+    }
+    futures::stream::iter(items)
+}
+
+/// A dropped subscription is reopened at the slot after the last one returned, and slots the new
+/// stream delivers again are skipped.
+#[tokio::test]
+async fn test_dropped_stream_is_resubscribed() {
+    let directories = test_directories();
+    let mut slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new("http://localhost:1"),
+        &directories,
+        2,
+    );
+    let resubscribed_at = Arc::new(Mutex::new(Vec::new()));
+    slot_fetcher.set_resubscribable_slot_stream(dropping_stream(1..=3, true), {
+        let resubscribed_at = resubscribed_at.clone();
+        move |next| {
+            resubscribed_at.lock().unwrap().push(next);
+            // The first reopened stream ends without a new slot, the second one re-delivers
+            // slot 3 before moving on.
+            let stream = match next {
+                4 if resubscribed_at.lock().unwrap().len() == 1 => dropping_stream(1..=0, false),
+                _ => dropping_stream(3..=5, false),
+            };
+            async move { Ok(stream) }
+        }
+    });
+
+    let mut numbers = Vec::new();
+    while let Some(slot) = slot_fetcher.next_slot().await.unwrap() {
+        numbers.push(slot.number);
+        if slot.number == 5 {
+            break;
+        }
+    }
+
+    assert_eq!(numbers, vec![1, 2, 3, 4, 5]);
+    assert_eq!(*resubscribed_at.lock().unwrap(), vec![4, 4]);
+}
+
+/// Once the resubscribe budget is spent, the stream failure is returned instead of a panic.
+#[tokio::test]
+async fn test_resubscribing_gives_up_after_max_attempts() {
+    let directories = test_directories();
+    let mut slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new("http://localhost:1"),
+        &directories,
+        2,
+    );
+    let attempts = Arc::new(Mutex::new(0));
+    slot_fetcher.set_resubscribable_slot_stream(dropping_stream(1..=1, true), {
+        let attempts = attempts.clone();
+        move |_| {
+            *attempts.lock().unwrap() += 1;
+            async { Err(anyhow::anyhow!("node unreachable")) }
+        }
+    });
+
+    assert_eq!(slot_fetcher.next_slot().await.unwrap().unwrap().number, 1);
+    let err = slot_fetcher.next_slot().await.unwrap_err();
+    assert!(format!("{:#}", err).contains("after 2 resubscribes"));
+    assert_eq!(*attempts.lock().unwrap(), 2);
+}
+
+/// A plain slot stream is not reopened, so its end is reported as the end of the slots.
+#[tokio::test]
+async fn test_plain_stream_is_not_resubscribed() {
+    let directories = test_directories();
+    let mut slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new("http://localhost:1"),
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
+    slot_fetcher.set_slot_stream(dropping_stream(1..=1, false));
+
+    assert_eq!(slot_fetcher.next_slot().await.unwrap().unwrap().number, 1);
+    assert!(slot_fetcher.next_slot().await.unwrap().is_none());
+}
+
+const TEST_RETRY: RetryConfig = RetryConfig {
+    attempts: 3,
+    initial_backoff: Duration::from_millis(1),
+};
+
+/// Calls `retry_while_missing` with a fetch that answers with `responses` in order, returning
+/// the result and the number of calls made.
+async fn retry_with_responses(
+    responses: Vec<Result<Option<u64>, anyhow::Error>>,
+) -> (Result<u64, anyhow::Error>, usize) {
+    let responses = Arc::new(Mutex::new(responses.into_iter()));
+    let calls = Arc::new(Mutex::new(0));
+    let result = retry_while_missing(TEST_RETRY, {
+        let calls = calls.clone();
+        move || {
+            *calls.lock().unwrap() += 1;
+            let response = responses.lock().unwrap().next().expect("Too many calls");
+            async move { response }
+        }
+    })
+    .await;
+    let calls = *calls.lock().unwrap();
+    (result, calls)
+}
+
+/// A missing batch is retried until it shows up.
+#[tokio::test]
+async fn test_retry_while_missing_waits_for_missing_value() {
+    let (result, calls) = retry_with_responses(vec![Ok(None), Ok(None), Ok(Some(7))]).await;
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(calls, 3);
+}
+
+/// A value that is still missing after the last attempt fails the fetch.
+#[tokio::test]
+async fn test_retry_while_missing_gives_up_after_max_attempts() {
+    let (result, calls) = retry_with_responses(vec![Ok(None), Ok(None), Ok(None)]).await;
+    assert!(result.is_err());
+    assert_eq!(calls, 3);
+}
+
+/// Errors other than "not found" fail right away.
+#[tokio::test]
+async fn test_retry_while_missing_does_not_retry_errors() {
+    let (result, calls) =
+        retry_with_responses(vec![Ok(None), Err(anyhow::anyhow!("connection reset"))]).await;
+    assert_eq!(result.unwrap_err().to_string(), "connection reset");
+    assert_eq!(calls, 2);
+}
+
+/// Serves `GET /ledger/slots/{number}` with the fixture slot of that number, like a node whose
+/// ledger has every slot. Returns the base URL of the server.
+async fn serve_mock_ledger() -> String {
+    let app = axum::Router::new().route(
+        "/ledger/slots/:slot_number",
+        axum::routing::get(
+            |axum::extract::Path(slot_number): axum::extract::Path<u64>| async move {
+                axum::Json(slot(slot_number))
+            },
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{address}")
+}
+
+/// The exported file is a single JSON array holding every slot of the range, in order.
+#[tokio::test]
+async fn test_export_range_writes_slots_as_json_array() {
+    let directories = test_directories();
+    let slot_fetcher = SlotFetcher::new(
+        sov_api_spec::Client::new(&serve_mock_ledger().await),
+        &directories,
+        DEFAULT_MAX_RESUBSCRIBES,
+    );
+
+    let path = directories.output_dir.join("slots_3_to_7.json");
+    slot_fetcher.export_range(3, 7, &path).await.unwrap();
+
+    let exported: Vec<Slot> =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(
+        exported.iter().map(|slot| slot.number).collect::<Vec<_>>(),
+        vec![3, 4, 5, 6]
+    );
+    assert_eq!(
+        serde_json::to_value(&exported[0]).unwrap(),
+        serde_json::to_value(slot(3)).unwrap(),
+        "Slots should be exported unchanged"
+    );
+}
@@ -0,0 +1,34 @@
+use acceptance_test::fetch_and_compare::DedupSlots;
+
+mod common;
+use common::{slot, slot_with};
+
+/// An exact re-delivery of the previous slot is skipped.
+#[tokio::test]
+async fn test_duplicate_slot_is_skipped() {
+    let mut slots = DedupSlots::new(futures::stream::iter(vec![
+        Ok(slot(1)),
+        Ok(slot(1)),
+        Ok(slot(2)),
+        Ok(slot(2)),
+        Ok(slot(3)),
+    ]));
+
+    for expected in 1..=3 {
+        assert_eq!(slots.next().await.unwrap().unwrap().number, expected);
+    }
+    assert!(slots.next().await.is_none());
+}
+
+/// A different slot carrying the previous slot's number fails instead of being skipped.
+#[tokio::test]
+async fn test_conflicting_duplicate_slot_fails() {
+    let mut slots = DedupSlots::new(futures::stream::iter(vec![
+        Ok(slot(1)),
+        Ok(slot_with(1, "pending", &format!("0x{:064x}", u64::MAX))),
+    ]));
+
+    assert_eq!(slots.next().await.unwrap().unwrap().number, 1);
+    let err = slots.next().await.unwrap().unwrap_err();
+    assert!(err.to_string().contains("different contents"));
+}
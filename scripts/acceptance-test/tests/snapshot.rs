@@ -0,0 +1,47 @@
+use acceptance_test::fetch_and_compare::{
+    compare_against_snapshot, load_snapshot_json, save_slot_snapshot, validate_against_snapshot,
+    SnapshotComparison, ValidationError,
+};
+
+mod common;
+use common::{slot, slot_with, test_directories};
+
+/// A slot whose snapshot was taken before finality may change, and is re-snapshotted.
+#[test]
+fn test_change_before_finality_is_tolerated() {
+    let directories = test_directories();
+    let pending = slot_with(4, "pending", &format!("0x{:064x}", u64::MAX));
+    save_slot_snapshot(&pending, &directories.snapshots_dir).unwrap();
+
+    let reorged = slot(4);
+    let snapshot = load_snapshot_json(4, &directories.snapshots_dir).unwrap();
+    assert_eq!(
+        compare_against_snapshot(&reorged, snapshot, "reorged slot", false).unwrap(),
+        SnapshotComparison::ChangedBeforeFinality
+    );
+
+    validate_against_snapshot(&reorged, &directories.snapshots_dir, "reorged slot").unwrap();
+    let snapshot = load_snapshot_json(4, &directories.snapshots_dir).unwrap();
+    assert_eq!(
+        compare_against_snapshot(&reorged, snapshot, "re-snapshotted slot", false).unwrap(),
+        SnapshotComparison::Matched
+    );
+}
+
+/// Any change to a slot that was finalized when snapshotted fails the comparison.
+#[test]
+fn test_change_after_finality_fails() {
+    let directories = test_directories();
+    let finalized = slot_with(4, "finalized", &format!("0x{:064x}", u64::MAX));
+    save_slot_snapshot(&finalized, &directories.snapshots_dir).unwrap();
+
+    let result = validate_against_snapshot(&slot(4), &directories.snapshots_dir, "finalized slot");
+    assert!(matches!(result, Err(ValidationError::InvalidSnapshot)));
+
+    let snapshot = load_snapshot_json(4, &directories.snapshots_dir).unwrap();
+    assert_eq!(
+        snapshot,
+        serde_json::to_value(&finalized).unwrap(),
+        "A failed comparison must not overwrite the snapshot"
+    );
+}
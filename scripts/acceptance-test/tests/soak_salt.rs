@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use acceptance_test::soak_salt;
+use rollup_starter_soak_test::worker_seed;
+
+/// Soaks following different numbers of batches never share a worker seed.
+#[test]
+fn test_soak_salts_never_share_worker_seeds() {
+    let mut seeds = HashSet::new();
+    for num_previous_batches in 0..5 {
+        let salt = soak_salt(1, num_previous_batches).unwrap();
+        for id in 0..20 {
+            assert!(
+                seeds.insert(worker_seed(salt, id)),
+                "Worker {} after {} batches reuses another worker's seed",
+                id,
+                num_previous_batches
+            );
+        }
+    }
+}
+
+/// A salt too large to be offset is turned down instead of overflowing.
+#[test]
+fn test_soak_salt_overflow_is_an_error() {
+    assert_eq!(soak_salt(u32::MAX - 3, 3).unwrap(), u32::MAX);
+    assert!(soak_salt(u32::MAX, 1).is_err());
+    assert!(soak_salt(0, u64::from(u32::MAX) + 1).is_err());
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use acceptance_test::state_dump::{assemble_state_dump, RegistryStateDump};
+use serde_json::{json, Value};
+
+/// State values are unwrapped from their `{"value": ...}` envelope and listings are kept as
+/// returned.
+#[tokio::test]
+async fn test_assemble_state_dump_from_stubbed_endpoints() {
+    let endpoints: HashMap<&str, Value> = HashMap::from([
+        (
+            "modules/session-registry/state/owner",
+            json!({ "value": "sov1owner" }),
+        ),
+        (
+            "modules/session-registry/state/manager",
+            json!({ "value": "sov1manager" }),
+        ),
+        (
+            "modules/session-registry/state/enforcement-enabled",
+            json!({ "value": true }),
+        ),
+        (
+            "modules/session-registry/state/expiry-offset",
+            json!({ "value": null }),
+        ),
+        (
+            "modules/session-registry/sessions",
+            json!({ "sessions": [["sov1wallet", { "expiry_ts": 1700000000, "bypass": false }]] }),
+        ),
+        (
+            "modules/session-registry/signers",
+            json!({ "signers": ["sov1signer"] }),
+        ),
+    ]);
+
+    let dump = assemble_state_dump(|path| {
+        let response = endpoints.get(path.as_str()).cloned();
+        async move { Ok(response) }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        dump,
+        RegistryStateDump {
+            owner: Some(json!("sov1owner")),
+            manager: Some(json!("sov1manager")),
+            enforcement_enabled: Some(json!(true)),
+            expiry_offset: Some(Value::Null),
+            sessions: Some(json!({
+                "sessions": [["sov1wallet", { "expiry_ts": 1700000000, "bypass": false }]]
+            })),
+            signers: Some(json!({ "signers": ["sov1signer"] })),
+        }
+    );
+
+    let serialized = serde_json::to_value(&dump).unwrap();
+    for key in [
+        "owner",
+        "manager",
+        "enforcement_enabled",
+        "expiry_offset",
+        "sessions",
+        "signers",
+    ] {
+        assert!(
+            serialized.get(key).is_some(),
+            "dump should contain `{}`",
+            key
+        );
+    }
+}
+
+/// Endpoints the node doesn't serve are recorded as missing.
+#[tokio::test]
+async fn test_assemble_state_dump_records_missing_endpoints() {
+    let dump = assemble_state_dump(|path| async move {
+        Ok((path == "modules/session-registry/state/owner")
+            .then(|| json!({ "value": "sov1owner" })))
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        dump,
+        RegistryStateDump {
+            owner: Some(json!("sov1owner")),
+            manager: None,
+            enforcement_enabled: None,
+            expiry_offset: None,
+            sessions: None,
+            signers: None,
+        }
+    );
+}
+
+/// A failing endpoint aborts the dump instead of producing a partial snapshot.
+#[tokio::test]
+async fn test_assemble_state_dump_surfaces_errors() {
+    let result = assemble_state_dump(|_| async { Err(anyhow::anyhow!("node unavailable")) }).await;
+    assert!(result.is_err());
+}
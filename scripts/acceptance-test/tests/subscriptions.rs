@@ -0,0 +1,42 @@
+use acceptance_test::fetch_and_compare::DedupSlots;
+use acceptance_test::subscriptions::SubscriptionTracker;
+use tokio_stream::StreamExt;
+
+mod common;
+use common::slot;
+
+/// Tracked subscriptions count as open until closed, and a closed one yields nothing more.
+#[tokio::test]
+async fn test_closed_subscription_ends() {
+    let tracker = SubscriptionTracker::new();
+    let mut events = tracker.track(futures::stream::iter(vec![1, 2, 3]));
+    let mut txs = tracker.track(futures::stream::iter(vec!["a", "b"]));
+    assert_eq!(tracker.open(), 2);
+    assert!(tracker.ensure_all_closed().is_err());
+
+    assert_eq!(events.next().await, Some(1));
+    events.close();
+    assert!(events.is_closed());
+    assert_eq!(events.next().await, None);
+    assert_eq!(tracker.open(), 1);
+
+    // Closing twice doesn't release the other subscription's slot.
+    events.close();
+    assert_eq!(tracker.open(), 1);
+
+    txs.close();
+    tracker.ensure_all_closed().unwrap();
+}
+
+/// Dropping the consumer of a subscription, as happens to the slot monitor at the end of the
+/// setup, closes it.
+#[tokio::test]
+async fn test_dropped_subscription_is_closed() {
+    let tracker = SubscriptionTracker::new();
+    let mut slots = DedupSlots::new(tracker.track(futures::stream::iter(vec![Ok(slot(1))])));
+    assert_eq!(slots.next().await.unwrap().unwrap().number, 1);
+    assert_eq!(tracker.open(), 1);
+
+    drop(slots);
+    tracker.ensure_all_closed().unwrap();
+}
@@ -0,0 +1,40 @@
+use acceptance_test::ThroughputReport;
+
+#[test]
+fn test_txs_per_slot_is_an_average() {
+    let report = ThroughputReport {
+        num_txs: 300,
+        num_slots: 40,
+        ..Default::default()
+    };
+    assert_eq!(report.txs_per_slot(), Some(7.5));
+
+    // A shorter soak at the same rate has the same throughput
+    let short_report = ThroughputReport {
+        num_txs: 30,
+        num_slots: 4,
+        ..Default::default()
+    };
+    assert_eq!(short_report.txs_per_slot(), report.txs_per_slot());
+}
+
+#[test]
+fn test_txs_per_slot_without_slots_is_none() {
+    let report = ThroughputReport {
+        num_txs: 0,
+        num_slots: 0,
+        ..Default::default()
+    };
+    assert_eq!(report.txs_per_slot(), None);
+}
+
+#[test]
+fn test_old_report_without_latencies_deserializes() {
+    let report: ThroughputReport =
+        serde_json::from_str(r#"{"num_txs": 300, "num_slots": 40}"#).unwrap();
+
+    assert_eq!(report.num_txs, 300);
+    assert_eq!(report.p50_ms, None);
+    assert_eq!(report.p95_ms, None);
+    assert_eq!(report.p99_ms, None);
+}
@@ -0,0 +1,77 @@
+use acceptance_test::fetch_and_compare::{compare_tx_events_against_snapshot, TxEventMismatch};
+use serde_json::{json, Value};
+
+/// A finalized slot with one batch of two txs, holding one and two events.
+fn snapshot() -> Value {
+    serde_json::from_str(include_str!("fixtures/slot_with_tx_events.json")).unwrap()
+}
+
+fn events_mut(slot: &mut Value, tx: usize) -> &mut Vec<Value> {
+    slot["batches"][0]["txs"][tx]["events"]
+        .as_array_mut()
+        .unwrap()
+}
+
+#[test]
+fn test_identical_events_match() {
+    assert_eq!(
+        compare_tx_events_against_snapshot(&snapshot(), &snapshot()),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_changed_event_reports_its_index() {
+    let mut slot = snapshot();
+    events_mut(&mut slot, 1)[1]["value"]["token_transferred"]["coins"]["amount"] = json!("11");
+
+    let mismatch = compare_tx_events_against_snapshot(&slot, &snapshot()).unwrap_err();
+    assert_eq!((mismatch.batch, mismatch.tx, mismatch.index), (2, 1, 1));
+    assert!(mismatch.actual.contains("\"11\""));
+    assert!(mismatch.expected.contains("\"10\""));
+}
+
+#[test]
+fn test_missing_and_extra_events_are_reported() {
+    let mut slot = snapshot();
+    events_mut(&mut slot, 1).pop();
+    let mismatch = compare_tx_events_against_snapshot(&slot, &snapshot()).unwrap_err();
+    assert_eq!(mismatch.index, 1);
+    assert_eq!(mismatch.actual, "none");
+
+    let mut slot = snapshot();
+    let extra = events_mut(&mut slot, 0)[0].clone();
+    events_mut(&mut slot, 0).push(extra);
+    let mismatch = compare_tx_events_against_snapshot(&slot, &snapshot()).unwrap_err();
+    assert_eq!((mismatch.tx, mismatch.index), (0, 1));
+    assert_eq!(mismatch.expected, "none");
+}
+
+#[test]
+fn test_reordered_events_report_the_first_difference() {
+    let mut slot = snapshot();
+    events_mut(&mut slot, 1).swap(0, 1);
+
+    let mismatch = compare_tx_events_against_snapshot(&slot, &snapshot()).unwrap_err();
+    assert_eq!(
+        mismatch,
+        TxEventMismatch {
+            batch: 2,
+            tx: 1,
+            index: 0,
+            actual: snapshot()["batches"][0]["txs"][1]["events"][1].to_string(),
+            expected: snapshot()["batches"][0]["txs"][1]["events"][0].to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_snapshot_without_batches_has_nothing_to_compare() {
+    let mut without_batches = snapshot();
+    without_batches.as_object_mut().unwrap().remove("batches");
+
+    assert_eq!(
+        compare_tx_events_against_snapshot(&without_batches, &without_batches),
+        Ok(())
+    );
+}
@@ -37,6 +37,7 @@ interface SetSessionSignerCall {
     set_session_signer: {
       signer: string;
       allowed: boolean;
+      expires_at: number;
     };
   };
 }
@@ -161,6 +162,7 @@ async function main() {
       set_session_signer: {
         signer: signerAddress.toLowerCase(),
         allowed: !revoke,
+        expires_at: 0,
       },
     },
   };
@@ -1,7 +1,28 @@
+use clap::Parser;
 use futures::StreamExt;
+use rollup_starter_soak_test::EventFilter;
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long)]
+    /// Only print events of this variant, e.g. `session_set` or `bypass_set`. Prints every event
+    /// when not set.
+    event_kind: Option<String>,
+
+    #[arg(long)]
+    /// Only print events whose `wallet` field is this address. Events without a `wallet` field
+    /// are skipped.
+    address: Option<String>,
+}
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
+    let args = Args::parse();
+    let filter = EventFilter {
+        kind: args.event_kind,
+        address: args.address,
+    };
+
     let api_url = "http://localhost:12346";
     let client = sov_api_spec::Client::new(api_url);
 
@@ -10,6 +31,11 @@ async fn main() -> Result<(), anyhow::Error> {
 
     println!("Subscription started");
     while let Some(event) = sub.next().await {
+        if let Ok(parsed) = &event {
+            if !filter.matches(&serde_json::to_value(parsed)?) {
+                continue;
+            }
+        }
         println!("{:?}", event);
     }
     Ok(())
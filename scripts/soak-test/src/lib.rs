@@ -0,0 +1,71 @@
+//! Helpers shared by the soak-test binaries.
+
+use rollup_starter::rollup::StarterRollup;
+use sov_modules_rollup_blueprint::RollupBlueprint;
+use sov_rollup_interface::execution_mode::Native;
+use sov_soak_testing_lib::SoakTestRunner;
+
+/// Runtime of the soaked rollup.
+pub type Runtime = <StarterRollup<Native> as RollupBlueprint<Native>>::Runtime;
+/// Spec of the soaked rollup.
+pub type Spec = <StarterRollup<Native> as RollupBlueprint<Native>>::Spec;
+
+/// Returns the runner each soak worker generates and submits its transactions with.
+pub fn soak_runner() -> SoakTestRunner<Runtime, Spec> {
+    SoakTestRunner::<Runtime, Spec>::new().with_bank()
+}
+
+/// Returns the id a soak worker passes to `SoakTestRunner::run`.
+///
+/// The runner seeds the worker's transaction generator from this id, so a given `(salt, worker_id)`
+/// pair always reproduces the same transaction sequence. The salt occupies the upper bits, so runs
+/// with different salts never share a seed, whatever the number of workers.
+pub fn worker_seed(salt: u32, worker_id: u32) -> u128 {
+    ((salt as u128) << 32) | worker_id as u128
+}
+
+/// Selects which subscribed events the `subscriber` binary prints.
+///
+/// Events are matched on their JSON form, whose `value` is the externally tagged module event,
+/// e.g. `{"session_set": {"wallet": "0x..", "expiry_ts": 1}}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Only events whose variant tag equals this, e.g. `session_set`.
+    pub kind: Option<String>,
+    /// Only events with a `wallet` field equal to this address, compared case-insensitively.
+    pub address: Option<String>,
+}
+
+impl EventFilter {
+    /// Returns `true` if `event` passes every filter that is set. An empty filter matches all.
+    pub fn matches(&self, event: &serde_json::Value) -> bool {
+        let (tag, fields) = match event_variant(event) {
+            Some(variant) => variant,
+            None => return self.kind.is_none() && self.address.is_none(),
+        };
+
+        if self.kind.as_deref().is_some_and(|kind| kind != tag) {
+            return false;
+        }
+
+        match &self.address {
+            Some(address) => fields
+                .get("wallet")
+                .and_then(serde_json::Value::as_str)
+                .is_some_and(|wallet| wallet.eq_ignore_ascii_case(address)),
+            None => true,
+        }
+    }
+}
+
+/// Returns the variant tag and fields of the module event in `event["value"]`.
+fn event_variant(event: &serde_json::Value) -> Option<(&str, &serde_json::Value)> {
+    let variant = event.get("value")?.as_object()?;
+    if variant.len() != 1 {
+        return None;
+    }
+    variant
+        .iter()
+        .next()
+        .map(|(tag, fields)| (tag.as_str(), fields))
+}
@@ -1,10 +1,8 @@
 use std::time::Duration;
 
 use clap::Parser;
-use rollup_starter::rollup::StarterRollup;
-use sov_modules_rollup_blueprint::RollupBlueprint;
-use sov_rollup_interface::execution_mode::Native;
-use sov_soak_testing_lib::{SoakTestRunner, ValidityProfile};
+use rollup_starter_soak_test::{soak_runner, worker_seed};
+use sov_soak_testing_lib::ValidityProfile;
 use tokio::signal::unix::SignalKind;
 use tokio::sync::watch::Receiver;
 use tokio::task::JoinSet;
@@ -21,21 +19,18 @@ struct Args {
 
     #[arg(short, long, default_value = "0")]
     /// The salt to use for RNG. Use this value if you're restarting the generator and want to ensure that the generated
-    /// transactions don't overlap with the previous run.
+    /// transactions don't overlap with the previous run. Runs with the same salt and number of workers generate the
+    /// same transaction sequences, so a load pattern can be reproduced by reusing its salt.
     salt: u32,
 }
 
-type Runtime = <StarterRollup<Native> as RollupBlueprint<Native>>::Runtime;
-type Spec = <StarterRollup<Native> as RollupBlueprint<Native>>::Spec;
-
 async fn worker_task(
     client: sov_api_spec::Client,
     rx: Receiver<bool>,
     worker_id: u128,
     num_workers: u32,
 ) -> anyhow::Result<()> {
-    let runner = SoakTestRunner::<Runtime, Spec>::new().with_bank();
-    let result = runner
+    let result = soak_runner()
         .run(
             client,
             rx,
@@ -69,7 +64,7 @@ async fn main() -> Result<(), anyhow::Error> {
         worker_set.spawn(worker_task(
             client.clone(),
             rx.clone(),
-            (i + args.salt) as u128,
+            worker_seed(args.salt, i),
             args.num_workers,
         ));
     }
@@ -0,0 +1,45 @@
+use rollup_starter_soak_test::EventFilter;
+use serde_json::json;
+
+fn session_set(wallet: &str) -> serde_json::Value {
+    json!({
+        "key": "SessionRegistry/SessionSet",
+        "value": { "session_set": { "wallet": wallet, "expiry_ts": 2764177788i64 } },
+    })
+}
+
+#[test]
+fn test_empty_filter_matches_everything() {
+    let filter = EventFilter::default();
+
+    assert!(filter.matches(&session_set("0xabc")));
+    assert!(filter.matches(&json!({ "value": "not an object" })));
+}
+
+#[test]
+fn test_kind_filter_matches_the_variant_tag() {
+    let filter = EventFilter {
+        kind: Some("session_set".to_string()),
+        address: None,
+    };
+
+    assert!(filter.matches(&session_set("0xabc")));
+    assert!(!filter.matches(&json!({
+        "value": { "bypass_set": { "wallet": "0xabc", "bypass": true } },
+    })));
+    assert!(!filter.matches(&json!({ "value": "not an object" })));
+}
+
+#[test]
+fn test_address_filter_matches_the_wallet_field() {
+    let filter = EventFilter {
+        kind: None,
+        address: Some("0xABC".to_string()),
+    };
+
+    assert!(filter.matches(&session_set("0xabc")));
+    assert!(!filter.matches(&session_set("0xdef")));
+    assert!(!filter.matches(&json!({
+        "value": { "enforcement_enabled_set": { "enabled": true } },
+    })));
+}
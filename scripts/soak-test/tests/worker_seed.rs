@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use rollup_starter_soak_test::{soak_runner, worker_seed};
+use sov_soak_testing_lib::ValidityProfile;
+
+/// Number of transactions compared between two runs of a worker.
+const COMPARED_TXS: usize = 10;
+
+/// Runs a single soak worker seeded with `seed` against a stub sequencer, and returns the bodies
+/// of the first [`COMPARED_TXS`] transactions it submitted.
+async fn generated_txs(seed: u128) -> Vec<String> {
+    let submitted = Arc::new(Mutex::new(Vec::new()));
+    let router = Router::new()
+        .route(
+            "/sequencer/txs",
+            post(
+                |State(submitted): State<Arc<Mutex<Vec<String>>>>,
+                 Json(tx): Json<serde_json::Value>| async move {
+                    let mut submitted = submitted.lock().unwrap();
+                    submitted.push(tx["body"].as_str().unwrap_or_default().to_string());
+                    Json(serde_json::json!({
+                        "id": format!("0x{:064x}", submitted.len()),
+                        "status": "submitted",
+                        "events": [],
+                    }))
+                },
+            ),
+        )
+        .with_state(submitted.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let (stop_sender, stop_receiver) = tokio::sync::watch::channel(false);
+    let client = sov_api_spec::Client::new(&format!("http://{addr}"));
+    let worker = tokio::spawn(async move {
+        soak_runner()
+            .run(
+                client,
+                stop_receiver,
+                seed,
+                1,
+                ValidityProfile::Clean.get_validity(),
+            )
+            .await
+    });
+
+    tokio::time::timeout(Duration::from_secs(60), async {
+        while submitted.lock().unwrap().len() < COMPARED_TXS {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The worker should keep submitting transactions");
+    stop_sender.send(true).unwrap();
+    let _ = tokio::time::timeout(Duration::from_secs(10), worker).await;
+    server.abort();
+
+    let mut submitted = submitted.lock().unwrap().clone();
+    submitted.truncate(COMPARED_TXS);
+    submitted
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_same_worker_seed_reproduces_txs() {
+    let first_run = generated_txs(worker_seed(42, 3)).await;
+    let second_run = generated_txs(worker_seed(42, 3)).await;
+    assert_eq!(
+        first_run, second_run,
+        "Workers with the same seed should submit the same transactions"
+    );
+
+    let other_salt = generated_txs(worker_seed(43, 3)).await;
+    assert_ne!(
+        first_run, other_salt,
+        "Workers with different seeds should submit different transactions"
+    );
+}
+
+#[test]
+fn test_salts_never_share_worker_seeds() {
+    let mut seeds = HashSet::new();
+    for salt in 0..10 {
+        for id in 0..20 {
+            assert!(
+                seeds.insert(worker_seed(salt, id)),
+                "Salt {salt} worker {id} reuses another worker's seed"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_salt_zero_keeps_plain_worker_ids() {
+    for id in 0..5 {
+        assert_eq!(worker_seed(0, id), id as u128);
+    }
+}